@@ -1,6 +1,6 @@
 use crate::z_ignore_test_common::*;
 
-use core::{borrow::Borrow, ffi::c_void};
+use core::borrow::Borrow;
 use flecs_ecs::prelude::*;
 use std::time::{SystemTime, UNIX_EPOCH};
 
@@ -45,13 +45,14 @@ fn main() {
     // following example shows how to pass a custom query into a system for a simple
     // collision detection example.
 
-    let mut query_collide = world.new_query::<(&Position, &Radius)>();
+    let query_collide = world.new_query::<(&Position, &Radius)>();
 
+    // The system takes ownership of the context, which is dropped when the system is deleted.
     let sys = world
         .system::<(&Position, &Radius)>()
-        .set_context(&mut query_collide as *mut Query<(&Position, &Radius)> as *mut c_void)
-        .each_iter(|mut it, index, (p1, r1)| {
-            let query = unsafe { it.context::<Query<(&Position, &Radius)>>() };
+        .ctx(query_collide)
+        .each_iter(|it, index, (p1, r1)| {
+            let query = it.ctx::<Query<(&Position, &Radius)>>();
             let e1 = it.entity(index);
 
             query.each_entity(|e2, (p2, r2)| {
@@ -0,0 +1,114 @@
+use crate::core::*;
+use crate::sys;
+
+extern crate alloc;
+use alloc::{borrow::ToOwned, string::String, vec::Vec};
+
+/// An alert that is active for an entity.
+///
+/// These are typically obtained via [`World::active_alerts()`].
+#[derive(Debug, Clone)]
+pub struct ActiveAlert<'a> {
+    /// The alert instance, which is a child of the alert.
+    pub instance: EntityView<'a>,
+    /// The alert.
+    pub alert: EntityView<'a>,
+    /// The entity that the alert is active for.
+    pub source: EntityView<'a>,
+    /// The severity, which is one of the `Info`, `Warning`, `Error` or `Critical` entities.
+    pub severity: EntityView<'a>,
+    /// The message of the alert, or an empty string if the alert doesn't have a message or if
+    /// the message hasn't been generated yet.
+    pub message: String,
+    /// Seconds for which the alert has been active.
+    pub duration: f64,
+}
+
+impl<'a> ActiveAlert<'a> {
+    /// Reads the state of an alert instance.
+    ///
+    /// # Returns
+    ///
+    /// The alert, or `None` if the instance is disabled because its alert is no longer
+    /// active, but is retained for the retain period of the alert.
+    pub(crate) fn from_instance(world: WorldRef<'a>, instance: sys::ecs_entity_t) -> Option<Self> {
+        let world_ptr = world.world_ptr();
+        unsafe {
+            if sys::ecs_has_id(world_ptr, instance, ECS_DISABLED) {
+                return None;
+            }
+
+            let source = sys::ecs_get_id(world_ptr, instance, sys::FLECS_IDEcsMetricSourceID_)
+                as *const sys::EcsMetricSource;
+            let value = sys::ecs_get_id(world_ptr, instance, sys::FLECS_IDEcsMetricValueID_)
+                as *const sys::EcsMetricValue;
+            let data = sys::ecs_get_id(world_ptr, instance, sys::FLECS_IDEcsAlertInstanceID_)
+                as *const sys::EcsAlertInstance;
+            let message = data.as_ref().map_or(core::ptr::null(), |data| data.message);
+
+            Some(Self {
+                instance: EntityView::new_from(world, instance),
+                alert: EntityView::new_from(world, sys::ecs_get_parent(world_ptr, instance)),
+                source: EntityView::new_from(world, source.as_ref()?.entity),
+                severity: EntityView::new_from(
+                    world,
+                    sys::ecs_get_target(world_ptr, instance, sys::FLECS_IDEcsAlertID_, 0),
+                ),
+                message: if message.is_null() {
+                    String::new()
+                } else {
+                    core::ffi::CStr::from_ptr(message)
+                        .to_string_lossy()
+                        .into_owned()
+                },
+                duration: value.as_ref().map_or(0.0, |value| value.value),
+            })
+        }
+    }
+
+    /// Returns the active alerts of a world.
+    ///
+    /// # Arguments
+    ///
+    /// * `alert` - Only return the instances of this alert, or all instances if `None`.
+    pub(crate) fn collect(world: WorldRef<'a>, alert: Option<Entity>) -> Vec<Self> {
+        let mut instances = Vec::new();
+        unsafe {
+            let mut it = sys::ecs_each_id(world.world_ptr(), sys::FLECS_IDEcsAlertInstanceID_);
+            while sys::ecs_each_next(&mut it) {
+                instances
+                    .extend_from_slice(core::slice::from_raw_parts(it.entities, it.count as usize));
+            }
+        }
+
+        instances
+            .into_iter()
+            .filter(|&instance| {
+                alert.is_none_or(|alert| unsafe {
+                    sys::ecs_get_parent(world.world_ptr(), instance) == *alert
+                })
+            })
+            .filter_map(|instance| Self::from_instance(world, instance))
+            .collect()
+    }
+}
+
+/// An alert that was raised for an entity, kept so it can be passed to the clear callback
+/// after the alert instance has been deleted.
+pub(crate) struct RaisedAlert {
+    pub(crate) instance: Entity,
+    pub(crate) source: Entity,
+    pub(crate) severity: Entity,
+    pub(crate) message: String,
+}
+
+impl From<&ActiveAlert<'_>> for RaisedAlert {
+    fn from(alert: &ActiveAlert<'_>) -> Self {
+        Self {
+            instance: alert.instance.id(),
+            source: alert.source.id(),
+            severity: alert.severity.id(),
+            message: alert.message.to_owned(),
+        }
+    }
+}
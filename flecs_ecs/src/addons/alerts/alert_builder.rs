@@ -11,7 +11,15 @@ use core::mem::ManuallyDrop;
 extern crate std;
 
 extern crate alloc;
-use alloc::{format, string::String, vec::Vec};
+use alloc::{
+    boxed::Box,
+    collections::{BTreeMap, BTreeSet},
+    format,
+    string::String,
+    vec::Vec,
+};
+
+type AlertCallback = Box<dyn FnMut(EntityView, EntityView, EntityView, &str)>;
 
 /// [`AlertBuilder`] is a builder pattern for creating [`Alert`]s.
 pub struct AlertBuilder<'a, T>
@@ -23,6 +31,8 @@ where
     world: WorldRef<'a>,
     severity_filter_count: i32,
     str_ptrs_to_free: Vec<ManuallyDrop<String>>,
+    on_raise: Option<AlertCallback>,
+    on_clear: Option<AlertCallback>,
     _phantom: core::marker::PhantomData<&'a T>,
 }
 
@@ -50,6 +60,8 @@ where
             world: world.into(),
             severity_filter_count: 0,
             str_ptrs_to_free: Vec::new(),
+            on_raise: None,
+            on_clear: None,
             _phantom: core::marker::PhantomData,
         };
 
@@ -65,6 +77,8 @@ where
             world: world.into(),
             severity_filter_count: 0,
             str_ptrs_to_free: Vec::new(),
+            on_raise: None,
+            on_clear: None,
             _phantom: core::marker::PhantomData,
         };
 
@@ -98,6 +112,8 @@ where
             world: world.into(),
             severity_filter_count: 0,
             str_ptrs_to_free: Vec::new(),
+            on_raise: None,
+            on_clear: None,
             _phantom: core::marker::PhantomData,
         };
 
@@ -373,6 +389,148 @@ where
         self.str_ptrs_to_free.push(var);
         self
     }
+
+    /// Set a callback that is invoked when the alert becomes active for an entity.
+    ///
+    /// The callback is invoked once the message of the alert has been generated, at the end of
+    /// the frame. An alert with a retain period is raised again when it becomes active again
+    /// within that period.
+    ///
+    /// # Arguments
+    ///
+    /// * `callback` - The callback, which is passed the alert instance, the entity that the
+    ///   alert is active for, the severity and the message of the alert.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use flecs_ecs::prelude::*;
+    ///
+    /// #[derive(Component)]
+    /// struct Position {
+    ///     x: f32,
+    ///     y: f32,
+    /// }
+    ///
+    /// let world = World::new();
+    /// world.import::<AlertsModule>();
+    ///
+    /// world
+    ///     .alert::<()>()
+    ///     .without(Position::id())
+    ///     .with(flecs::alerts::Warning::id())
+    ///     .message("$this has no position")
+    ///     .on_raise(|_instance, source, severity, message| {
+    ///         println!("{}: {message}", severity.name());
+    ///     })
+    ///     .on_clear(|_instance, source, _, _| {
+    ///         println!("{} has a position again", source.name());
+    ///     })
+    ///     .build();
+    /// ```
+    ///
+    /// # See also
+    ///
+    /// * [`AlertBuilder::on_clear()`]
+    /// * [`World::active_alerts()`]
+    pub fn on_raise(
+        &mut self,
+        callback: impl FnMut(EntityView, EntityView, EntityView, &str) + 'static,
+    ) -> &mut Self {
+        self.on_raise = Some(Box::new(callback));
+        self
+    }
+
+    /// Set a callback that is invoked when the alert is no longer active for an entity.
+    ///
+    /// The callback is passed the same values as the callback of [`AlertBuilder::on_raise()`]
+    /// for the alert instance. The alert instance and the entity may no longer be alive.
+    ///
+    /// # Arguments
+    ///
+    /// * `callback` - The callback, which is passed the alert instance, the entity that the
+    ///   alert was active for, the severity and the message of the alert.
+    ///
+    /// # See also
+    ///
+    /// * [`AlertBuilder::on_raise()`]
+    pub fn on_clear(
+        &mut self,
+        callback: impl FnMut(EntityView, EntityView, EntityView, &str) + 'static,
+    ) -> &mut Self {
+        self.on_clear = Some(Box::new(callback));
+        self
+    }
+
+    /// Create the system that invokes the callbacks of the alert. The system runs after the
+    /// alerts addon has updated the alert instances, matches the enabled instances of the alert,
+    /// and is deleted with the alert.
+    fn watch(&mut self, alert: Entity) {
+        let mut on_raise = self.on_raise.take();
+        let mut on_clear = self.on_clear.take();
+        if on_raise.is_none() && on_clear.is_none() {
+            return;
+        }
+
+        let has_message = !self.desc.message.is_null();
+        let instance_id = unsafe { sys::FLECS_IDEcsAlertInstanceID_ };
+        let mut raised: BTreeMap<Entity, RaisedAlert> = BTreeMap::new();
+        let mut active: BTreeSet<Entity> = BTreeSet::new();
+        let system = self
+            .world
+            .system::<()>()
+            .kind(flecs::pipeline::OnStore)
+            .with(instance_id)
+            .with((flecs::ChildOf::ID, alert))
+            .run(move |mut it| {
+                let world = it.world();
+                active.clear();
+                while it.next() {
+                    for i in it.iter() {
+                        let instance = it.entity(i).id();
+                        active.insert(instance);
+                        if raised.contains_key(&instance) {
+                            continue;
+                        }
+
+                        // the message is generated after the instance is created
+                        let message = unsafe {
+                            sys::ecs_get_id(world.world_ptr(), *instance, instance_id)
+                                .cast::<sys::EcsAlertInstance>()
+                                .as_ref()
+                                .map_or(core::ptr::null(), |data| data.message)
+                        };
+                        if has_message && message.is_null() {
+                            continue;
+                        }
+
+                        let Some(cur) = ActiveAlert::from_instance(world, *instance) else {
+                            continue;
+                        };
+                        if let Some(on_raise) = on_raise.as_mut() {
+                            on_raise(cur.instance, cur.source, cur.severity, &cur.message);
+                        }
+                        raised.insert(instance, (&cur).into());
+                    }
+                }
+
+                raised.retain(|instance, prev| {
+                    if active.contains(instance) {
+                        return true;
+                    }
+                    if let Some(on_clear) = on_clear.as_mut() {
+                        on_clear(
+                            EntityView::new_from(world, prev.instance),
+                            EntityView::new_from(world, prev.source),
+                            EntityView::new_from(world, prev.severity),
+                            &prev.message,
+                        );
+                    }
+                    false
+                });
+            });
+        system.child_of(alert);
+    }
 }
 
 #[doc(hidden)]
@@ -415,6 +573,7 @@ where
     /// Build the `AlertBuilder` into an Alert
     fn build(&mut self) -> Self::BuiltType {
         let alert = Alert::new(self.world(), self.desc);
+        self.watch(alert.id());
         for s in self.term_builder.str_ptrs_to_free.iter_mut() {
             unsafe { ManuallyDrop::drop(s) };
         }
@@ -6,5 +6,7 @@ mod types;
 pub use types::*;
 mod alerts;
 pub use alerts::*;
+mod active_alert;
+pub use active_alert::*;
 mod entity_view;
 mod world;
@@ -72,4 +72,36 @@ impl World {
     {
         AlertBuilder::<Components>::new_from_desc(self, desc)
     }
+
+    /// Returns the alerts that are active.
+    ///
+    /// Alerts that are no longer active, but whose instances are retained for the retain
+    /// period of the alert, are not returned.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use flecs_ecs::prelude::*;
+    ///
+    /// let world = World::new();
+    /// world.import::<AlertsModule>();
+    ///
+    /// for alert in world.active_alerts() {
+    ///     println!(
+    ///         "{} ({}): {} for {:.1}s",
+    ///         alert.source.name(),
+    ///         alert.severity.name(),
+    ///         alert.message,
+    ///         alert.duration
+    ///     );
+    /// }
+    /// ```
+    ///
+    /// # See also
+    ///
+    /// * [`AlertBuilder::on_raise()`]
+    /// * [`EntityView::alert_count()`]
+    pub fn active_alerts(&self) -> impl Iterator<Item = ActiveAlert<'_>> {
+        ActiveAlert::collect(self.world(), None).into_iter()
+    }
 }
@@ -1,15 +1,21 @@
 //! addon for running the main application loop.
 
+use super::{AppFrames, AppHooks, FrameDriver, LoopDriver};
 use crate::core::*;
 use crate::sys;
 use core::ffi::c_void;
 
+extern crate alloc;
+use alloc::boxed::Box;
+
 /// Application interface.
 ///
 /// These are typically constructed via [`World::app()`]
 pub struct App<'a> {
     pub(crate) world: WorldRef<'a>,
     pub(crate) desc: sys::ecs_app_desc_t,
+    hooks: AppHooks<'a>,
+    driver: Option<Box<dyn FrameDriver + 'a>>,
 }
 
 impl<'a> App<'a> {
@@ -26,6 +32,8 @@ impl<'a> App<'a> {
         let mut obj = Self {
             world: world.world(),
             desc: sys::ecs_app_desc_t::default(),
+            hooks: AppHooks::default(),
+            driver: None,
         };
 
         let stats = unsafe { sys::ecs_get_world_info(obj.world.ptr_mut()) };
@@ -125,15 +133,89 @@ impl<'a> App<'a> {
         self
     }
 
+    /// Set a callback that is invoked before the first frame runs.
+    ///
+    /// # Arguments
+    ///
+    /// * `callback` - The callback, which is passed the world.
+    pub fn on_init(&mut self, callback: impl FnOnce(&World) + 'a) -> &mut Self {
+        self.hooks.on_init = Some(Box::new(callback));
+        self
+    }
+
+    /// Set a callback that is invoked before each frame.
+    ///
+    /// # Arguments
+    ///
+    /// * `callback` - The callback, which is passed the world and the expected delta time of
+    ///   the frame. When the delta time is measured, this is the delta time of the previous
+    ///   frame.
+    pub fn on_frame_begin(&mut self, callback: impl FnMut(&World, FTime) + 'a) -> &mut Self {
+        self.hooks.on_frame_begin = Some(Box::new(callback));
+        self
+    }
+
+    /// Set a callback that is invoked after each frame.
+    ///
+    /// # Arguments
+    ///
+    /// * `callback` - The callback, which is passed the world and the delta time of the frame.
+    pub fn on_frame_end(&mut self, callback: impl FnMut(&World, FTime) + 'a) -> &mut Self {
+        self.hooks.on_frame_end = Some(Box::new(callback));
+        self
+    }
+
+    /// Set a condition that stops the application. The condition is tested after each frame.
+    ///
+    /// # Arguments
+    ///
+    /// * `condition` - Returns `true` when the application should stop.
+    pub fn until(&mut self, condition: impl FnMut(&World) -> bool + 'a) -> &mut Self {
+        self.hooks.until = Some(Box::new(condition));
+        self
+    }
+
+    /// Set the driver of the main loop, which decides when frames run.
+    ///
+    /// # Arguments
+    ///
+    /// * `driver` - The driver. The default, [`LoopDriver`], runs frames back to back.
+    ///
+    /// # See also
+    ///
+    /// * [`FrameDriver`]
+    pub fn driver(&mut self, driver: impl FrameDriver + 'a) -> &mut Self {
+        self.driver = Some(Box::new(driver));
+        self
+    }
+
     /// Run application. This will run the application with the parameters specified in desc.
     /// After the application quits ([`World::quit()`] is called) this will return.
     /// If a custom run action is set, it will be invoked by this operation.
     /// The default run action calls the frame action in a loop until it returns a non-zero value.
     ///
+    /// When a driver or one of the callbacks of the application is set, or when the world has a
+    /// fixed timestep, the main loop is run by the driver instead, so that frames progress the
+    /// world like [`World::progress()`] does, and custom run and frame actions are not invoked.
+    /// The world is then not cleaned up when the application quits, but when the world is
+    /// dropped.
+    ///
     /// # Returns
     ///
     /// The exit code of the application.
+    ///
+    /// # See also
+    ///
+    /// * [`World::set_fixed_timestep()`]
     pub fn run(&mut self) -> i32 {
+        crate::addons::pipeline::ordering::apply(&self.world.real_world());
+
+        // the main loop of flecs doesn't run the fixed timestep pipeline
+        if self.driver.is_some() || !self.hooks.is_empty() || self.world.fixed_timestep().is_some()
+        {
+            return self.run_driver();
+        }
+
         let world_ptr = self.world.ptr_mut();
         let result = unsafe { sys::ecs_app_run(world_ptr, &mut self.desc) };
         unsafe {
@@ -148,4 +230,47 @@ impl<'a> App<'a> {
         }
         result
     }
+
+    /// Set up the world like `ecs_app_run` does, and run the main loop with the driver.
+    fn run_driver(&mut self) -> i32 {
+        let world = self.world;
+        if self.desc.target_fps > 0.0 {
+            world.set_target_fps(self.desc.target_fps);
+        }
+        if self.desc.threads > 0 {
+            world.set_threads(self.desc.threads);
+        }
+
+        #[cfg(feature = "flecs_rest")]
+        if self.desc.enable_rest {
+            world.set(flecs::rest::Rest {
+                port: self.desc.port,
+                ..Default::default()
+            });
+        }
+
+        #[cfg(feature = "flecs_stats")]
+        if self.desc.enable_stats {
+            world.import::<crate::addons::stats::Stats>();
+        }
+
+        if let Some(init) = self.desc.init {
+            unsafe { init(world.ptr_mut()) };
+        }
+        if let Some(on_init) = self.hooks.on_init.take() {
+            on_init(&world);
+        }
+
+        let mut driver = self.driver.take().unwrap_or_else(|| Box::new(LoopDriver));
+        let mut frames = AppFrames::new(
+            world,
+            &mut self.hooks,
+            self.desc.delta_time,
+            self.desc.target_fps,
+            self.desc.frames,
+        );
+        let result = driver.run(&mut frames);
+        self.driver = Some(driver);
+        result
+    }
 }
@@ -0,0 +1,215 @@
+use crate::core::*;
+
+extern crate alloc;
+use alloc::boxed::Box;
+
+type InitCallback<'a> = Box<dyn FnOnce(&World) + 'a>;
+type FrameCallback<'a> = Box<dyn FnMut(&World, FTime) + 'a>;
+type UntilCallback<'a> = Box<dyn FnMut(&World) -> bool + 'a>;
+
+/// The callbacks that an [`App`](super::App) invokes while it runs.
+#[derive(Default)]
+pub(crate) struct AppHooks<'a> {
+    pub(crate) on_init: Option<InitCallback<'a>>,
+    pub(crate) on_frame_begin: Option<FrameCallback<'a>>,
+    pub(crate) on_frame_end: Option<FrameCallback<'a>>,
+    pub(crate) until: Option<UntilCallback<'a>>,
+}
+
+impl AppHooks<'_> {
+    pub(crate) fn is_empty(&self) -> bool {
+        self.on_init.is_none()
+            && self.on_frame_begin.is_none()
+            && self.on_frame_end.is_none()
+            && self.until.is_none()
+    }
+}
+
+/// Drives the main loop of an [`App`](super::App).
+///
+/// A driver decides when frames run, by calling [`AppFrames::frame()`]. This allows the app to
+/// run inside the event loop of a windowing library, to run a fixed number of frames in a
+/// test, or to run frames with a fixed time step.
+///
+/// Closures that take an [`AppFrames`] and return the exit code implement this trait.
+///
+/// # Example
+///
+/// ```
+/// use flecs_ecs::prelude::*;
+///
+/// struct Headless {
+///     frames: usize,
+/// }
+///
+/// impl FrameDriver for Headless {
+///     fn run(&mut self, frames: &mut AppFrames) -> i32 {
+///         for _ in 0..self.frames {
+///             if !frames.frame_time(1.0 / 60.0) {
+///                 break;
+///             }
+///         }
+///         0
+///     }
+/// }
+///
+/// let world = World::new();
+/// world.app().driver(Headless { frames: 10 }).run();
+/// ```
+///
+/// # See also
+///
+/// * [`App::driver()`](super::App::driver)
+pub trait FrameDriver {
+    /// Run frames until the app should stop.
+    ///
+    /// # Arguments
+    ///
+    /// * `frames` - Runs the frames of the app.
+    ///
+    /// # Returns
+    ///
+    /// The exit code of the app.
+    fn run(&mut self, frames: &mut AppFrames) -> i32;
+}
+
+impl<F> FrameDriver for F
+where
+    F: FnMut(&mut AppFrames) -> i32,
+{
+    fn run(&mut self, frames: &mut AppFrames) -> i32 {
+        self(frames)
+    }
+}
+
+/// The driver that an [`App`](super::App) uses when no other driver is set, which runs frames
+/// back to back until the app should stop.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct LoopDriver;
+
+impl FrameDriver for LoopDriver {
+    fn run(&mut self, frames: &mut AppFrames) -> i32 {
+        while frames.frame() {}
+        0
+    }
+}
+
+/// Runs the frames of an [`App`](super::App), and is passed to its [`FrameDriver`].
+pub struct AppFrames<'a, 'h> {
+    world: WorldRef<'a>,
+    hooks: &'h mut AppHooks<'a>,
+    delta_time: FTime,
+    last_delta_time: FTime,
+    max_frames: i32,
+    frame_count: i32,
+    done: bool,
+}
+
+impl<'a, 'h> AppFrames<'a, 'h> {
+    pub(crate) fn new(
+        world: WorldRef<'a>,
+        hooks: &'h mut AppHooks<'a>,
+        delta_time: FTime,
+        target_fps: FTime,
+        max_frames: i32,
+    ) -> Self {
+        // before the first frame, assume that frames run at the target rate
+        let first_delta_time = if target_fps > 0.0 {
+            1.0 / target_fps
+        } else {
+            1.0 / 60.0
+        };
+        Self {
+            world,
+            hooks,
+            delta_time,
+            last_delta_time: first_delta_time,
+            max_frames,
+            frame_count: 0,
+            done: false,
+        }
+    }
+
+    /// The world of the app.
+    pub fn world(&self) -> &World {
+        &self.world
+    }
+
+    /// The delta time of the app, or `0` if the delta time is measured.
+    ///
+    /// # See also
+    ///
+    /// * [`App::set_delta_time()`](super::App::set_delta_time)
+    pub fn delta_time(&self) -> FTime {
+        self.delta_time
+    }
+
+    /// Number of frames that have run.
+    pub fn frame_count(&self) -> i32 {
+        self.frame_count
+    }
+
+    /// Returns whether the app should stop, because [`World::quit()`] was called, the
+    /// condition of [`App::until()`](super::App::until) was met, or the number of frames of
+    /// the app has run.
+    pub fn is_done(&self) -> bool {
+        self.done
+    }
+
+    /// Run a frame with the delta time of the app.
+    ///
+    /// # Returns
+    ///
+    /// Whether the app should keep running.
+    ///
+    /// # See also
+    ///
+    /// * [`AppFrames::frame_time()`]
+    pub fn frame(&mut self) -> bool {
+        self.frame_time(self.delta_time)
+    }
+
+    /// Run a frame.
+    ///
+    /// This invokes the frame begin callback, progresses the world, and invokes the frame end
+    /// callback. Frames are not run once the app should stop.
+    ///
+    /// # Arguments
+    ///
+    /// * `delta_time` - The time passed since the last frame, or `0` to measure it.
+    ///
+    /// # Returns
+    ///
+    /// Whether the app should keep running.
+    pub fn frame_time(&mut self, delta_time: FTime) -> bool {
+        if self.done {
+            return false;
+        }
+
+        let world = &*self.world;
+        if let Some(on_frame_begin) = self.hooks.on_frame_begin.as_mut() {
+            // the measured delta time isn't known until the frame starts
+            let expected = if delta_time > 0.0 {
+                delta_time
+            } else {
+                self.last_delta_time
+            };
+            on_frame_begin(world, expected);
+        }
+
+        let running = world.progress_time(delta_time);
+        self.last_delta_time = world.info().delta_time;
+        self.frame_count += 1;
+
+        if let Some(on_frame_end) = self.hooks.on_frame_end.as_mut() {
+            on_frame_end(world, self.last_delta_time);
+        }
+
+        let until = self.hooks.until.as_mut().is_some_and(|until| until(world));
+        self.done = !running
+            || until
+            || world.should_quit()
+            || (self.max_frames > 0 && self.frame_count >= self.max_frames);
+        !self.done
+    }
+}
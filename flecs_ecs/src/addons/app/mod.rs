@@ -1,3 +1,5 @@
 mod app;
+mod frame_driver;
 mod world;
 pub use app::*;
+pub use frame_driver::*;
@@ -0,0 +1,450 @@
+use core::cell::{Cell, RefCell};
+use core::ffi::c_void;
+use core::ptr::NonNull;
+
+use flecs_ecs::core::*;
+use flecs_ecs::sys;
+use flecs_ecs_derive::extern_abi;
+
+extern crate alloc;
+use alloc::{
+    borrow::ToOwned,
+    boxed::Box,
+    ffi::CString,
+    rc::{Rc, Weak},
+    string::String,
+    vec::Vec,
+};
+
+use super::{HttpMethod, HttpReply, HttpRequest, HttpResponse};
+
+type Handler = Box<dyn FnMut(&HttpRequest, &mut HttpReply)>;
+
+struct Route {
+    method: HttpMethod,
+    path: String,
+    handler: Handler,
+}
+
+impl Route {
+    /// Paths ending with `*` match every path that starts with the part before the `*`.
+    fn matches(&self, path: &str) -> bool {
+        match self.path.strip_suffix('*') {
+            Some(prefix) => path.starts_with(prefix),
+            None => self.path == path,
+        }
+    }
+}
+
+/// Server and routes, owned by the system that processes the requests of the server.
+struct ServerState {
+    server: NonNull<sys::ecs_http_server_t>,
+    running: Cell<bool>,
+    // the server keeps pointers to these, so they must outlive it
+    _routes: Box<RefCell<Vec<Route>>>,
+    _ipaddr: Option<CString>,
+}
+
+impl Drop for ServerState {
+    fn drop(&mut self) {
+        unsafe { sys::ecs_http_server_fini(self.server.as_ptr()) };
+    }
+}
+
+#[extern_abi]
+fn http_reply(
+    req: *const sys::ecs_http_request_t,
+    reply: *mut sys::ecs_http_reply_t,
+    ctx: *mut c_void,
+) -> bool {
+    let routes = unsafe { &*(ctx as *const RefCell<Vec<Route>>) };
+    let mut reply = HttpReply::new(unsafe { &mut *reply });
+
+    // a handler sent a request to its own server, and the handlers can't run twice at once
+    let Ok(mut routes) = routes.try_borrow_mut() else {
+        reply
+            .status(500)
+            .content_type(c"text/plain")
+            .body("cannot send a request to an HTTP server from one of its handlers");
+        return true;
+    };
+    let request = HttpRequest::new(unsafe { &*req });
+
+    let path = request.path();
+    let method = request.method();
+    let mut path_found = false;
+    for route in routes.iter_mut().filter(|route| route.matches(path)) {
+        if route.method == method {
+            (route.handler)(&request, &mut reply);
+            return true;
+        }
+        path_found = true;
+    }
+
+    // requests that weren't handled get a 404 reply from flecs, whatever their status is
+    reply.status(if path_found { 405 } else { 404 });
+    path_found
+}
+
+/// Builder for an [`HttpServer`].
+///
+/// These are typically constructed via [`World::http_server()`].
+pub struct HttpServerBuilder<'a> {
+    world: WorldRef<'a>,
+    port: u16,
+    ipaddr: Option<CString>,
+    cache_timeout: f64,
+    routes: Vec<Route>,
+}
+
+impl<'a> HttpServerBuilder<'a> {
+    /// Create a new builder for a server without routes.
+    ///
+    /// # See also
+    ///
+    /// * [`World::http_server()`]
+    pub(crate) fn new(world: impl WorldProvider<'a>) -> Self {
+        Self {
+            world: world.world(),
+            port: 0,
+            ipaddr: None,
+            cache_timeout: 0.0,
+            routes: Vec::new(),
+        }
+    }
+
+    /// Set the port the server listens on.
+    ///
+    /// # Arguments
+    ///
+    /// * `port` - The port. The default, `0`, lets flecs pick its default HTTP port.
+    pub fn port(&mut self, port: u16) -> &mut Self {
+        self.port = port;
+        self
+    }
+
+    /// Set the address of the interface the server listens on.
+    ///
+    /// # Arguments
+    ///
+    /// * `ipaddr` - The address, like `127.0.0.1`. By default the server listens on all
+    ///   interfaces.
+    pub fn ipaddr(&mut self, ipaddr: &str) -> &mut Self {
+        self.ipaddr = Some(CString::new(ipaddr).expect("address contains a null byte"));
+        self
+    }
+
+    /// Cache the replies to `GET` requests.
+    ///
+    /// # Arguments
+    ///
+    /// * `seconds` - How long a reply is reused for requests with the same path and query
+    ///   parameters. The default, `0`, disables caching.
+    pub fn cache_timeout(&mut self, seconds: f64) -> &mut Self {
+        self.cache_timeout = seconds;
+        self
+    }
+
+    /// Add a handler for requests with a method and path.
+    ///
+    /// Routes are matched in the order in which they are added. A request for a path without
+    /// a route gets a `404` reply, and a request for a path that only has routes for other
+    /// methods gets a `405` reply.
+    ///
+    /// # Arguments
+    ///
+    /// * `method` - The method of the requests.
+    /// * `path` - The path of the requests, like `/debug/stats`. A path ending with `*`
+    ///   matches all paths that start with the part before it.
+    /// * `handler` - The handler that writes the reply to a request.
+    ///
+    /// # See also
+    ///
+    /// * [`HttpServerBuilder::get()`]
+    /// * [`HttpServerBuilder::post()`]
+    /// * [`HttpServerBuilder::put()`]
+    /// * [`HttpServerBuilder::delete()`]
+    pub fn route(
+        &mut self,
+        method: HttpMethod,
+        path: &str,
+        handler: impl FnMut(&HttpRequest, &mut HttpReply) + 'static,
+    ) -> &mut Self {
+        self.routes.push(Route {
+            method,
+            // request paths don't start with a `/`
+            path: path.trim_start_matches('/').to_owned(),
+            handler: Box::new(handler),
+        });
+        self
+    }
+
+    /// Add a handler for `GET` requests with a path.
+    ///
+    /// # See also
+    ///
+    /// * [`HttpServerBuilder::route()`]
+    pub fn get(
+        &mut self,
+        path: &str,
+        handler: impl FnMut(&HttpRequest, &mut HttpReply) + 'static,
+    ) -> &mut Self {
+        self.route(HttpMethod::Get, path, handler)
+    }
+
+    /// Add a handler for `POST` requests with a path.
+    ///
+    /// # See also
+    ///
+    /// * [`HttpServerBuilder::route()`]
+    pub fn post(
+        &mut self,
+        path: &str,
+        handler: impl FnMut(&HttpRequest, &mut HttpReply) + 'static,
+    ) -> &mut Self {
+        self.route(HttpMethod::Post, path, handler)
+    }
+
+    /// Add a handler for `PUT` requests with a path.
+    ///
+    /// # See also
+    ///
+    /// * [`HttpServerBuilder::route()`]
+    pub fn put(
+        &mut self,
+        path: &str,
+        handler: impl FnMut(&HttpRequest, &mut HttpReply) + 'static,
+    ) -> &mut Self {
+        self.route(HttpMethod::Put, path, handler)
+    }
+
+    /// Add a handler for `DELETE` requests with a path.
+    ///
+    /// # See also
+    ///
+    /// * [`HttpServerBuilder::route()`]
+    pub fn delete(
+        &mut self,
+        path: &str,
+        handler: impl FnMut(&HttpRequest, &mut HttpReply) + 'static,
+    ) -> &mut Self {
+        self.route(HttpMethod::Delete, path, handler)
+    }
+
+    /// Add a route that replies to `GET` requests with the values of all metrics, so that the
+    /// world can be scraped by Prometheus.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the route, like `/metrics`.
+    ///
+    /// # See also
+    ///
+    /// * [`World::metrics_to_prometheus()`]
+    #[cfg(feature = "flecs_metrics")]
+    pub fn metrics_route(&mut self, path: &str) -> &mut Self {
+        // the server is owned by a system of the world, so the world outlives the handler
+        let world = self.world.world_ptr_mut();
+        self.get(path, move |_, reply| {
+            let world = unsafe { WorldRef::from_ptr(world) };
+            reply
+                .content_type(crate::addons::metrics::PROMETHEUS_CONTENT_TYPE)
+                .body(&world.metrics_to_prometheus());
+        })
+    }
+
+    /// Create the server.
+    ///
+    /// The server is owned by a system in the `OnLoad` phase, which runs the handlers of the
+    /// requests that were received since the last frame. The server doesn't accept connections
+    /// until [`HttpServer::start()`] is called.
+    ///
+    /// # See also
+    ///
+    /// * C API: `ecs_http_server_init`
+    pub fn build(&mut self) -> HttpServer<'a> {
+        let routes = Box::new(RefCell::new(core::mem::take(&mut self.routes)));
+        let ipaddr = self.ipaddr.take();
+
+        let desc = sys::ecs_http_server_desc_t {
+            callback: Some(http_reply),
+            ctx: &*routes as *const RefCell<Vec<Route>> as *mut c_void,
+            port: self.port,
+            ipaddr: ipaddr
+                .as_ref()
+                .map_or(core::ptr::null(), |ipaddr| ipaddr.as_ptr()),
+            send_queue_wait_ms: 0,
+            cache_timeout: self.cache_timeout,
+            cache_purge_timeout: 0.0,
+        };
+        let server = unsafe { sys::ecs_http_server_init(&desc) };
+
+        let state = Rc::new(ServerState {
+            server: NonNull::new(server).expect("failed to create HTTP server"),
+            running: Cell::new(false),
+            _routes: routes,
+            _ipaddr: ipaddr,
+        });
+        let weak = Rc::downgrade(&state);
+
+        let system = self
+            .world
+            .system::<()>()
+            .kind(flecs::pipeline::OnLoad)
+            .run(move |it| {
+                if state.running.get() {
+                    unsafe { sys::ecs_http_server_dequeue(state.server.as_ptr(), it.delta_time()) };
+                }
+            });
+
+        HttpServer {
+            entity: EntityView::new_from(self.world, system.id()),
+            state: weak,
+        }
+    }
+}
+
+/// An HTTP server that passes requests to Rust handlers.
+///
+/// Requests are received on a background thread, and are handled on the main thread when the
+/// world progresses, so handlers can safely access the world. The server is deleted, and stops
+/// listening, when its system entity is deleted.
+///
+/// # Example
+///
+/// ```no_run
+/// use flecs_ecs::prelude::*;
+/// use flecs_ecs::addons::http::*;
+/// use core::fmt::Write;
+///
+/// let world = World::new();
+///
+/// let server = world
+///     .http_server()
+///     .port(9000)
+///     .get("/debug/entities", |req, reply| {
+///         let limit = req.param_as::<usize>("limit").unwrap_or(10);
+///         reply.content_type(c"text/plain");
+///         write!(reply, "showing {limit} entities").unwrap();
+///     })
+///     .build();
+///
+/// server.start();
+///
+/// loop {
+///     world.progress();
+/// }
+/// ```
+pub struct HttpServer<'a> {
+    entity: EntityView<'a>,
+    state: Weak<ServerState>,
+}
+
+impl<'a> HttpServer<'a> {
+    /// The system entity that owns the server.
+    pub fn entity(&self) -> EntityView<'a> {
+        self.entity
+    }
+
+    /// Start accepting connections.
+    ///
+    /// Connections are accepted on a background thread. The requests are handled when the
+    /// world progresses.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the server was started, `false` if the server was already running or could
+    /// not be started.
+    ///
+    /// # See also
+    ///
+    /// * [`HttpServer::stop()`]
+    /// * C API: `ecs_http_server_start`
+    pub fn start(&self) -> bool {
+        let state = self.state();
+        if state.running.get() {
+            return false;
+        }
+        let started = unsafe { sys::ecs_http_server_start(state.server.as_ptr()) } == 0;
+        state.running.set(started);
+        started
+    }
+
+    /// Stop accepting connections.
+    ///
+    /// # See also
+    ///
+    /// * [`HttpServer::start()`]
+    /// * C API: `ecs_http_server_stop`
+    pub fn stop(&self) {
+        let state = self.state();
+        if state.running.replace(false) {
+            unsafe { sys::ecs_http_server_stop(state.server.as_ptr()) };
+        }
+    }
+
+    /// Returns whether the server is accepting connections.
+    pub fn is_running(&self) -> bool {
+        self.state().running.get()
+    }
+
+    /// Send a request to the server without a connection, and handle it immediately.
+    ///
+    /// This doesn't require the server to be started, which makes it useful for testing
+    /// handlers.
+    ///
+    /// # Arguments
+    ///
+    /// * `method` - The method of the request.
+    /// * `path` - The path of the request, optionally with a query string, like
+    ///   `/debug/entities?limit=5`.
+    /// * `body` - The body of the request.
+    ///
+    /// # Returns
+    ///
+    /// The reply of the handler.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the server was deleted.
+    ///
+    /// When called from one of the handlers of the server, the handlers aren't run again, and the
+    /// reply has status `500`.
+    ///
+    /// # See also
+    ///
+    /// * C API: `ecs_http_server_request`
+    pub fn request(&self, method: HttpMethod, path: &str, body: Option<&str>) -> HttpResponse {
+        let state = self.state();
+        let method = compact_str::format_compact!("{}\0", method.as_str());
+        let path = compact_str::format_compact!("/{}\0", path.trim_start_matches('/'));
+        let body = body.map(|body| compact_str::format_compact!("{}\0", body));
+
+        let mut reply = sys::ecs_http_reply_t {
+            code: 200,
+            body: unsafe { core::mem::zeroed() },
+            status: c"OK".as_ptr(),
+            content_type: c"application/json".as_ptr(),
+            headers: unsafe { core::mem::zeroed() },
+        };
+
+        unsafe {
+            sys::ecs_http_server_request(
+                state.server.as_ptr(),
+                method.as_ptr() as *const _,
+                path.as_ptr() as *const _,
+                body.as_ref()
+                    .map_or(core::ptr::null(), |body| body.as_ptr() as *const _),
+                &mut reply,
+            );
+        }
+
+        HttpResponse::from_reply(&mut reply)
+    }
+
+    fn state(&self) -> Rc<ServerState> {
+        self.state
+            .upgrade()
+            .expect("HTTP server was deleted together with its system")
+    }
+}
@@ -0,0 +1,9 @@
+//! Embedded HTTP server that passes requests to Rust handlers.
+//!
+//! The server can be used to add endpoints, like custom debug endpoints, next to the REST API
+//! without depending on a separate web framework.
+mod http_server;
+pub use http_server::*;
+mod types;
+pub use types::*;
+mod world;
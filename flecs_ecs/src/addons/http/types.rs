@@ -0,0 +1,366 @@
+use core::ffi::{CStr, c_char};
+use core::fmt::Write;
+use core::str::FromStr;
+
+use flecs_ecs::sys;
+
+extern crate alloc;
+use alloc::{borrow::ToOwned, string::String};
+
+/// The method of an HTTP request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum HttpMethod {
+    Get,
+    Post,
+    Put,
+    Delete,
+    Options,
+    /// A method that the server doesn't support. Requests with this method are never passed
+    /// to a handler.
+    Unsupported,
+}
+
+impl HttpMethod {
+    /// The name of the method as it appears in a request, like `GET`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            HttpMethod::Get => "GET",
+            HttpMethod::Post => "POST",
+            HttpMethod::Put => "PUT",
+            HttpMethod::Delete => "DELETE",
+            HttpMethod::Options => "OPTIONS",
+            HttpMethod::Unsupported => "UNSUPPORTED",
+        }
+    }
+
+    pub(crate) fn from_sys(method: sys::ecs_http_method_t) -> Self {
+        match method {
+            sys::ecs_http_method_t_EcsHttpGet => HttpMethod::Get,
+            sys::ecs_http_method_t_EcsHttpPost => HttpMethod::Post,
+            sys::ecs_http_method_t_EcsHttpPut => HttpMethod::Put,
+            sys::ecs_http_method_t_EcsHttpDelete => HttpMethod::Delete,
+            sys::ecs_http_method_t_EcsHttpOptions => HttpMethod::Options,
+            _ => HttpMethod::Unsupported,
+        }
+    }
+}
+
+impl core::fmt::Display for HttpMethod {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Converts a string owned by the server to a `&str`. Strings that aren't valid UTF-8 are
+/// treated as missing.
+///
+/// # Safety
+///
+/// `str` must be null or point to a null terminated string that outlives `'a`.
+unsafe fn to_str<'a>(str: *const c_char) -> Option<&'a str> {
+    if str.is_null() {
+        return None;
+    }
+    unsafe { CStr::from_ptr(str) }.to_str().ok()
+}
+
+/// A request that is passed to the handler of an [`HttpServer`](super::HttpServer) route.
+pub struct HttpRequest<'a> {
+    req: &'a sys::ecs_http_request_t,
+}
+
+impl<'a> HttpRequest<'a> {
+    pub(crate) fn new(req: &'a sys::ecs_http_request_t) -> Self {
+        Self { req }
+    }
+
+    /// The method of the request.
+    pub fn method(&self) -> HttpMethod {
+        HttpMethod::from_sys(self.req.method)
+    }
+
+    /// The path of the request, without the leading `/` and the query string.
+    pub fn path(&self) -> &'a str {
+        unsafe { to_str(self.req.path) }.unwrap_or_default()
+    }
+
+    /// The body of the request, if it has one.
+    pub fn body(&self) -> Option<&'a str> {
+        unsafe { to_str(self.req.body) }.filter(|body| !body.is_empty())
+    }
+
+    /// Get the value of a query parameter.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the parameter.
+    ///
+    /// # Returns
+    ///
+    /// The decoded value of the parameter, or `None` if the request doesn't have it.
+    ///
+    /// # See also
+    ///
+    /// * [`HttpRequest::param_as()`]
+    /// * C API: `ecs_http_get_param`
+    pub fn param(&self, name: &str) -> Option<&'a str> {
+        let name = compact_str::format_compact!("{}\0", name);
+        unsafe { to_str(sys::ecs_http_get_param(self.req, name.as_ptr() as *const _)) }
+    }
+
+    /// Get the value of a query parameter, parsed as `T`.
+    ///
+    /// # Type Parameters
+    ///
+    /// * `T` - The type of the parameter.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the parameter.
+    ///
+    /// # Returns
+    ///
+    /// The parsed value, or `None` if the request doesn't have the parameter or if it can't be
+    /// parsed as `T`.
+    pub fn param_as<T: FromStr>(&self, name: &str) -> Option<T> {
+        self.param(name)?.parse().ok()
+    }
+
+    /// Get the value of a header.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the header.
+    ///
+    /// # Returns
+    ///
+    /// The value of the header, or `None` if the request doesn't have it.
+    ///
+    /// # See also
+    ///
+    /// * [`HttpRequest::header_as()`]
+    /// * C API: `ecs_http_get_header`
+    pub fn header(&self, name: &str) -> Option<&'a str> {
+        let name = compact_str::format_compact!("{}\0", name);
+        unsafe {
+            to_str(sys::ecs_http_get_header(
+                self.req,
+                name.as_ptr() as *const _,
+            ))
+        }
+    }
+
+    /// Get the value of a header, parsed as `T`.
+    ///
+    /// # Type Parameters
+    ///
+    /// * `T` - The type of the header value.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the header.
+    ///
+    /// # Returns
+    ///
+    /// The parsed value, or `None` if the request doesn't have the header or if it can't be
+    /// parsed as `T`.
+    pub fn header_as<T: FromStr>(&self, name: &str) -> Option<T> {
+        self.header(name)?.parse().ok()
+    }
+
+    /// Returns the query parameters of the request as `(name, value)` pairs.
+    pub fn params(&self) -> impl Iterator<Item = (&'a str, &'a str)> + use<'a> {
+        key_values(&self.req.params[..self.req.param_count as usize])
+    }
+
+    /// Returns the headers of the request as `(name, value)` pairs.
+    pub fn headers(&self) -> impl Iterator<Item = (&'a str, &'a str)> + use<'a> {
+        key_values(&self.req.headers[..self.req.header_count as usize])
+    }
+}
+
+fn key_values(pairs: &[sys::ecs_http_key_value_t]) -> impl Iterator<Item = (&str, &str)> {
+    pairs.iter().filter_map(|pair| unsafe {
+        Some((to_str(pair.key)?, to_str(pair.value).unwrap_or_default()))
+    })
+}
+
+/// Returns the reason phrase for a status code, like `Not Found` for 404.
+fn status_text(code: u16) -> &'static CStr {
+    match code {
+        200 => c"OK",
+        201 => c"Created",
+        202 => c"Accepted",
+        204 => c"No Content",
+        301 => c"Moved Permanently",
+        302 => c"Found",
+        304 => c"Not Modified",
+        400 => c"Bad Request",
+        401 => c"Unauthorized",
+        403 => c"Forbidden",
+        404 => c"Not Found",
+        405 => c"Method Not Allowed",
+        409 => c"Conflict",
+        422 => c"Unprocessable Content",
+        500 => c"Internal Server Error",
+        501 => c"Not Implemented",
+        503 => c"Service Unavailable",
+        _ => c"",
+    }
+}
+
+/// The reply that the handler of an [`HttpServer`](super::HttpServer) route writes to.
+///
+/// A reply starts out with status `200` and content type `application/json`. The body can be
+/// written with [`HttpReply::body()`], or with the `write!` macro.
+pub struct HttpReply<'a> {
+    reply: &'a mut sys::ecs_http_reply_t,
+}
+
+impl<'a> HttpReply<'a> {
+    pub(crate) fn new(reply: &'a mut sys::ecs_http_reply_t) -> Self {
+        Self { reply }
+    }
+
+    /// Set the status code of the reply.
+    ///
+    /// # Arguments
+    ///
+    /// * `code` - The status code, like `404`.
+    pub fn status(&mut self, code: u16) -> &mut Self {
+        self.reply.code = code as i32;
+        self.reply.status = status_text(code).as_ptr();
+        self
+    }
+
+    /// Set the content type of the reply.
+    ///
+    /// # Arguments
+    ///
+    /// * `content_type` - The content type, like `c"text/plain"`.
+    pub fn content_type(&mut self, content_type: &'static CStr) -> &mut Self {
+        self.reply.content_type = content_type.as_ptr();
+        self
+    }
+
+    /// Append text to the body of the reply.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - The text to append.
+    pub fn body(&mut self, text: &str) -> &mut Self {
+        unsafe {
+            sys::ecs_strbuf_appendstrn(
+                &mut self.reply.body,
+                text.as_ptr() as *const _,
+                text.len() as i32,
+            );
+        }
+        self
+    }
+
+    /// Add a header to the reply.
+    ///
+    /// Headers are written to the reply as is, so a header with an empty name, a name that
+    /// contains a `:`, whitespace or a control character, or a value that contains a line break is
+    /// ignored, as it could add other headers to the reply.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the header.
+    /// * `value` - The value of the header.
+    pub fn header(&mut self, name: &str, value: &str) -> &mut Self {
+        let valid_name = !name.is_empty()
+            && !name
+                .chars()
+                .any(|c| c == ':' || c.is_whitespace() || c.is_control());
+        if !valid_name || value.contains(['\r', '\n']) {
+            return self;
+        }
+
+        let header = compact_str::format_compact!("{name}: {value}\r\n");
+        unsafe {
+            sys::ecs_strbuf_appendstrn(
+                &mut self.reply.headers,
+                header.as_ptr() as *const _,
+                header.len() as i32,
+            );
+        }
+        self
+    }
+}
+
+impl Write for HttpReply<'_> {
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        self.body(s);
+        Ok(())
+    }
+}
+
+/// The reply to a request that was sent with [`HttpServer::request()`](super::HttpServer::request).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HttpResponse {
+    code: u16,
+    status: String,
+    content_type: Option<String>,
+    headers: String,
+    body: String,
+}
+
+impl HttpResponse {
+    /// Takes the content of a reply that was filled in by the server, and frees the reply.
+    pub(crate) fn from_reply(reply: &mut sys::ecs_http_reply_t) -> Self {
+        let take = |buf: &mut sys::ecs_strbuf_t| {
+            let str = unsafe { sys::ecs_strbuf_get(buf) };
+            let owned = unsafe { to_str(str) }.unwrap_or_default().to_owned();
+            if !str.is_null() {
+                unsafe { sys::ecs_os_api.free_.expect("os api is missing")(str as *mut _) };
+            }
+            owned
+        };
+
+        Self {
+            code: reply.code as u16,
+            status: unsafe { to_str(reply.status) }
+                .unwrap_or_default()
+                .to_owned(),
+            content_type: unsafe { to_str(reply.content_type) }.map(ToOwned::to_owned),
+            headers: take(&mut reply.headers),
+            body: take(&mut reply.body),
+        }
+    }
+
+    /// The status code of the reply.
+    pub fn code(&self) -> u16 {
+        self.code
+    }
+
+    /// The reason phrase of the status code, like `OK`.
+    pub fn status(&self) -> &str {
+        &self.status
+    }
+
+    /// The content type of the reply, if it has one.
+    pub fn content_type(&self) -> Option<&str> {
+        self.content_type.as_deref()
+    }
+
+    /// Get the value of a header that the handler added to the reply.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the header, compared case insensitively.
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers.lines().find_map(|line| {
+            let (key, value) = line.split_once(':')?;
+            key.trim()
+                .eq_ignore_ascii_case(name)
+                .then_some(value.trim())
+        })
+    }
+
+    /// The body of the reply.
+    pub fn body(&self) -> &str {
+        &self.body
+    }
+}
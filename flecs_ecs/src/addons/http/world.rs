@@ -0,0 +1,17 @@
+use super::HttpServerBuilder;
+use crate::core::*;
+
+/// HTTP server mixin implementation
+impl World {
+    /// Create a builder for an HTTP server with Rust request handlers.
+    ///
+    /// The requests that the server receives are handled when the world progresses.
+    ///
+    /// # See also
+    ///
+    /// * [`addons::http`](crate::addons::http)
+    #[inline(always)]
+    pub fn http_server(&self) -> HttpServerBuilder<'_> {
+        HttpServerBuilder::new(self)
+    }
+}
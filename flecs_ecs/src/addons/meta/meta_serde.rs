@@ -0,0 +1,874 @@
+//! Serde support for values of types with reflection data.
+//!
+//! Values are serialized by walking the type serializer instructions of the type, the same
+//! way the JSON serializer does. Deserialization drives a [`Cursor`], so opaque types are
+//! assigned through their `assign_*` / `ensure_*` callbacks.
+
+use core::ffi::{CStr, c_char, c_void};
+
+extern crate alloc;
+use alloc::{format, string::String};
+
+use serde::de::{self, DeserializeSeed, MapAccess, SeqAccess, Visitor};
+use serde::ser::{self, Serialize, SerializeMap, SerializeSeq, Serializer};
+
+use super::Cursor;
+use crate::core::*;
+use crate::sys;
+
+/// Serializable view of a value, created by [`World::to_serde()`] or [`World::to_serde_id()`].
+///
+/// Structs are serialized as maps, arrays and vectors as sequences, enum and bitmask
+/// constants as their names (bitmask flags joined with `|`), and entities as their path.
+pub struct MetaSerialize<'a> {
+    world: WorldRef<'a>,
+    type_id: sys::ecs_entity_t,
+    ptr: *const c_void,
+}
+
+/// Deserialization seed for a value, created by [`World::from_serde()`] or
+/// [`World::from_serde_id()`].
+///
+/// Accepts the same layout that [`MetaSerialize`] produces and writes it into the value.
+pub struct MetaDeserialize<'a> {
+    world: WorldRef<'a>,
+    type_id: sys::ecs_entity_t,
+    ptr: *mut c_void,
+}
+
+impl World {
+    /// Return a [`serde::Serialize`] view of an untyped value.
+    ///
+    /// # Arguments
+    ///
+    /// * `tid` - The type of the value. Must have reflection data.
+    /// * `value` - Pointer to the value.
+    ///
+    /// # See also
+    ///
+    /// * [`World::to_serde()`]
+    /// * [`World::from_serde_id()`]
+    pub fn to_serde_id(&self, tid: impl IntoId, value: *const c_void) -> MetaSerialize<'_> {
+        MetaSerialize {
+            world: self.world(),
+            type_id: *tid.into_id(self),
+            ptr: value,
+        }
+    }
+
+    /// Return a [`serde::Serialize`] view of a value.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use flecs_ecs::prelude::*;
+    /// #[derive(Component)]
+    /// #[flecs(meta)]
+    /// struct Position {
+    ///     x: f32,
+    ///     y: f32,
+    /// }
+    ///
+    /// let world = World::new();
+    /// world.component::<Position>();
+    ///
+    /// let pos = Position { x: 1.0, y: 2.0 };
+    /// let json = serde_json::to_string(&world.to_serde::<Position>(&pos)).unwrap();
+    /// assert_eq!(json, r#"{"x":1.0,"y":2.0}"#);
+    /// ```
+    ///
+    /// # See also
+    ///
+    /// * [`World::to_serde_id()`]
+    /// * [`World::from_serde()`]
+    pub fn to_serde<'a, T: ComponentOrPairId>(
+        &'a self,
+        value: &'a T::CastType,
+    ) -> MetaSerialize<'a> {
+        self.to_serde_id(
+            T::get_id(self),
+            value as *const T::CastType as *const c_void,
+        )
+    }
+
+    /// Return a [`serde::de::DeserializeSeed`] that writes into an untyped value.
+    ///
+    /// # Arguments
+    ///
+    /// * `tid` - The type of the value. Must have reflection data.
+    /// * `value` - Pointer to the value.
+    ///
+    /// # See also
+    ///
+    /// * [`World::from_serde()`]
+    /// * [`World::to_serde_id()`]
+    pub fn from_serde_id(&self, tid: impl IntoId, value: *mut c_void) -> MetaDeserialize<'_> {
+        MetaDeserialize {
+            world: self.world(),
+            type_id: *tid.into_id(self),
+            ptr: value,
+        }
+    }
+
+    /// Return a [`serde::de::DeserializeSeed`] that writes into a value.
+    ///
+    /// Members that are not present in the input keep their current value.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use flecs_ecs::prelude::*;
+    /// use serde::de::DeserializeSeed;
+    ///
+    /// #[derive(Component, Default)]
+    /// #[flecs(meta)]
+    /// struct Position {
+    ///     x: f32,
+    ///     y: f32,
+    /// }
+    ///
+    /// let world = World::new();
+    /// world.component::<Position>();
+    ///
+    /// let mut pos = Position::default();
+    /// let mut de = serde_json::Deserializer::from_str(r#"{"x":1.0,"y":2.0}"#);
+    /// world.from_serde::<Position>(&mut pos).deserialize(&mut de).unwrap();
+    /// assert_eq!(pos.x, 1.0);
+    /// assert_eq!(pos.y, 2.0);
+    /// ```
+    ///
+    /// # See also
+    ///
+    /// * [`World::from_serde_id()`]
+    /// * [`World::to_serde()`]
+    pub fn from_serde<'a, T: ComponentOrPairId>(
+        &'a self,
+        value: &'a mut T::CastType,
+    ) -> MetaDeserialize<'a> {
+        self.from_serde_id(
+            T::CastType::get_id(self),
+            value as *mut T::CastType as *mut c_void,
+        )
+    }
+}
+
+/// Instructions of the type serializer of `type_id`.
+fn type_ops<'a>(
+    world: WorldRef<'a>,
+    type_id: sys::ecs_entity_t,
+) -> Option<&'a [sys::ecs_meta_op_t]> {
+    let ts = unsafe { sys::ecs_get_id(world.world_ptr(), type_id, ECS_META_TYPE_SERIALIZER) }
+        as *const sys::EcsTypeSerializer;
+    if ts.is_null() {
+        return None;
+    }
+    let ops = unsafe { &(*ts).ops };
+    if ops.count <= 0 || ops.array.is_null() {
+        return None;
+    }
+    Some(unsafe {
+        core::slice::from_raw_parts(ops.array as *const sys::ecs_meta_op_t, ops.count as usize)
+    })
+}
+
+fn type_ops_or_err(
+    world: WorldRef<'_>,
+    type_id: sys::ecs_entity_t,
+) -> Result<&[sys::ecs_meta_op_t], String> {
+    type_ops(world, type_id)
+        .ok_or_else(|| format!("missing reflection data for '{}'", id_str(world, type_id)))
+}
+
+/// Instructions for the type an opaque type is serialized as.
+fn opaque_as_type_ops<'a>(
+    world: WorldRef<'a>,
+    op: &sys::ecs_meta_op_t,
+) -> Result<&'a [sys::ecs_meta_op_t], String> {
+    let opaque = unsafe { sys::ecs_get_id(world.world_ptr(), op.type_, ECS_OPAQUE) }
+        as *const sys::EcsOpaque;
+    if opaque.is_null() {
+        return Err(format!(
+            "'{}' is not an opaque type",
+            id_str(world, op.type_)
+        ));
+    }
+    type_ops_or_err(world, unsafe { (*opaque).as_type })
+}
+
+/// The members of a struct, or the element of a collection, described by a push op.
+fn scope_ops(ops: &[sys::ecs_meta_op_t]) -> &[sys::ecs_meta_op_t] {
+    &ops[1..ops[0].op_count as usize - 1]
+}
+
+fn id_str(world: WorldRef, id: sys::ecs_id_t) -> String {
+    unsafe {
+        let str = sys::ecs_id_str(world.world_ptr(), id);
+        if str.is_null() {
+            return format!("#{id}");
+        }
+        let result = CStr::from_ptr(str).to_string_lossy().into_owned();
+        sys::ecs_os_api.free_.expect("os api is missing")(str as *mut c_void);
+        result
+    }
+}
+
+fn entity_path(world: WorldRef, entity: sys::ecs_entity_t) -> String {
+    if entity == 0 {
+        return String::from("#0");
+    }
+    EntityView::new_from(world, entity)
+        .path_from_w_sep(0, ".", "")
+        .unwrap_or_else(|| format!("#{entity}"))
+}
+
+unsafe fn c_str<'a>(str: *const c_char) -> &'a str {
+    if str.is_null() {
+        ""
+    } else {
+        unsafe { CStr::from_ptr(str).to_str().unwrap_or("") }
+    }
+}
+
+impl Serialize for MetaSerialize<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let ops = type_ops_or_err(self.world, self.type_id).map_err(ser::Error::custom)?;
+        OpSerialize {
+            world: self.world,
+            ops,
+            base: self.ptr,
+        }
+        .serialize(serializer)
+    }
+}
+
+/// Serializes the value described by `ops[0]`, located at `base + ops[0].offset`.
+struct OpSerialize<'a> {
+    world: WorldRef<'a>,
+    ops: &'a [sys::ecs_meta_op_t],
+    base: *const c_void,
+}
+
+impl Serialize for OpSerialize<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let world = self.world;
+        let op = &self.ops[0];
+        let ptr = unsafe { self.base.byte_offset(op.offset as isize) };
+
+        unsafe {
+            match op.kind {
+                sys::ecs_meta_op_kind_t_EcsOpPushStruct => {
+                    let members = scope_ops(self.ops);
+                    let mut count = 0;
+                    let mut i = 0;
+                    while i < members.len() {
+                        count += 1;
+                        i += members[i].op_count as usize;
+                    }
+
+                    let mut map = serializer.serialize_map(Some(count))?;
+                    let mut i = 0;
+                    while i < members.len() {
+                        map.serialize_entry(
+                            c_str(members[i].name),
+                            &OpSerialize {
+                                world,
+                                ops: &members[i..],
+                                base: ptr,
+                            },
+                        )?;
+                        i += members[i].op_count as usize;
+                    }
+                    map.end()
+                }
+                sys::ecs_meta_op_kind_t_EcsOpPushArray
+                | sys::ecs_meta_op_kind_t_EcsOpPushVector => {
+                    let (array, count) = if op.kind == sys::ecs_meta_op_kind_t_EcsOpPushVector {
+                        let vec = &*(ptr as *const sys::ecs_vec_t);
+                        (vec.array as *const c_void, vec.count)
+                    } else {
+                        (ptr, sys::ecs_meta_op_get_elem_count(op, ptr))
+                    };
+
+                    let elem = scope_ops(self.ops);
+                    let mut seq = serializer.serialize_seq(Some(count as usize))?;
+                    for i in 0..count {
+                        seq.serialize_element(&OpSerialize {
+                            world,
+                            ops: elem,
+                            base: array.byte_offset((i * op.elem_size) as isize),
+                        })?;
+                    }
+                    seq.end()
+                }
+                sys::ecs_meta_op_kind_t_EcsOpForward => {
+                    let ops = type_ops_or_err(world, op.type_).map_err(ser::Error::custom)?;
+                    OpSerialize {
+                        world,
+                        ops,
+                        base: ptr,
+                    }
+                    .serialize(serializer)
+                }
+                sys::ecs_meta_op_kind_t_EcsOpOpaqueValue => {
+                    let mut serializer = Some(serializer);
+                    let mut result = None;
+                    let ok = visit_opaque(world, op, ptr, &mut |event| {
+                        if let (OpaqueEvent::Value(type_id, value), Some(serializer)) =
+                            (event, serializer.take())
+                        {
+                            result = Some(
+                                OpaqueValue {
+                                    world,
+                                    type_id,
+                                    value,
+                                }
+                                .serialize(serializer),
+                            );
+                        }
+                        true
+                    });
+                    match result {
+                        Some(result) if ok => result,
+                        Some(Err(e)) => Err(e),
+                        _ => Err(ser::Error::custom(format!(
+                            "failed to serialize opaque value of type '{}'",
+                            id_str(world, op.type_)
+                        ))),
+                    }
+                }
+                sys::ecs_meta_op_kind_t_EcsOpOpaqueArray
+                | sys::ecs_meta_op_kind_t_EcsOpOpaqueVector => {
+                    let mut count = 0;
+                    visit_opaque(world, op, ptr, &mut |event| {
+                        if let OpaqueEvent::Value(..) = event {
+                            count += 1;
+                        }
+                        true
+                    });
+
+                    let mut seq = serializer.serialize_seq(Some(count))?;
+                    let mut error = None;
+                    let ok = visit_opaque(world, op, ptr, &mut |event| {
+                        if let OpaqueEvent::Value(type_id, value) = event
+                            && let Err(e) = seq.serialize_element(&OpaqueValue {
+                                world,
+                                type_id,
+                                value,
+                            })
+                        {
+                            error = Some(e);
+                            return false;
+                        }
+                        true
+                    });
+                    if let Some(e) = error {
+                        return Err(e);
+                    }
+                    if !ok {
+                        return Err(ser::Error::custom(format!(
+                            "failed to serialize opaque collection of type '{}'",
+                            id_str(world, op.type_)
+                        )));
+                    }
+                    seq.end()
+                }
+                sys::ecs_meta_op_kind_t_EcsOpOpaqueStruct => {
+                    let mut count = 0;
+                    visit_opaque(world, op, ptr, &mut |event| {
+                        if let OpaqueEvent::Member(_) = event {
+                            count += 1;
+                        }
+                        true
+                    });
+
+                    let mut map = serializer.serialize_map(Some(count))?;
+                    let mut error = None;
+                    let mut member = String::new();
+                    let ok = visit_opaque(world, op, ptr, &mut |event| {
+                        match event {
+                            OpaqueEvent::Member(name) => {
+                                member = String::from(name);
+                            }
+                            OpaqueEvent::Value(type_id, value) => {
+                                if let Err(e) = map.serialize_entry(
+                                    &member,
+                                    &OpaqueValue {
+                                        world,
+                                        type_id,
+                                        value,
+                                    },
+                                ) {
+                                    error = Some(e);
+                                    return false;
+                                }
+                            }
+                        }
+                        true
+                    });
+                    if let Some(e) = error {
+                        return Err(e);
+                    }
+                    if !ok {
+                        return Err(ser::Error::custom(format!(
+                            "failed to serialize opaque struct of type '{}'",
+                            id_str(world, op.type_)
+                        )));
+                    }
+                    map.end()
+                }
+                sys::ecs_meta_op_kind_t_EcsOpEnum => {
+                    let value = *(ptr as *const i32);
+                    let constant = sys::ecs_map_get(op.is.constants, value as i64 as u64);
+                    if constant.is_null() {
+                        return Err(ser::Error::custom(format!(
+                            "enumeration value '{value}' of type '{}' is not a valid constant",
+                            id_str(world, op.type_)
+                        )));
+                    }
+                    let constant = &*(*constant as *const sys::ecs_enum_constant_t);
+                    serializer.serialize_str(c_str(sys::ecs_get_name(
+                        world.world_ptr(),
+                        constant.constant,
+                    )))
+                }
+                sys::ecs_meta_op_kind_t_EcsOpBitmask => {
+                    let mut value = *(ptr as *const u32);
+                    if value == 0 {
+                        return serializer.serialize_str("0");
+                    }
+
+                    let mut flags = String::new();
+                    let mut it = sys::ecs_map_iter(op.is.constants);
+                    while sys::ecs_map_next(&mut it) {
+                        let key = *it.res as u32;
+                        if value & key == key {
+                            let constant = &*(*it.res.add(1) as *const sys::ecs_bitmask_constant_t);
+                            if !flags.is_empty() {
+                                flags.push('|');
+                            }
+                            flags.push_str(c_str(sys::ecs_get_name(
+                                world.world_ptr(),
+                                constant.constant,
+                            )));
+                            value -= key;
+                        }
+                    }
+                    if value != 0 {
+                        return Err(ser::Error::custom(format!(
+                            "bitmask value '{value}' of type '{}' contains invalid/unknown bits",
+                            id_str(world, op.type_)
+                        )));
+                    }
+                    serializer.serialize_str(&flags)
+                }
+                sys::ecs_meta_op_kind_t_EcsOpEntity => {
+                    serializer.serialize_str(&entity_path(world, *(ptr as *const u64)))
+                }
+                sys::ecs_meta_op_kind_t_EcsOpId => {
+                    let id = *(ptr as *const u64);
+                    if id == 0 {
+                        serializer.serialize_str("#0")
+                    } else {
+                        serializer.serialize_str(&id_str(world, id))
+                    }
+                }
+                sys::ecs_meta_op_kind_t_EcsOpString => {
+                    let str = *(ptr as *const *const c_char);
+                    if str.is_null() {
+                        serializer.serialize_none()
+                    } else {
+                        serializer.serialize_some(c_str(str))
+                    }
+                }
+                sys::ecs_meta_op_kind_t_EcsOpBool => {
+                    serializer.serialize_bool(*(ptr as *const bool))
+                }
+                sys::ecs_meta_op_kind_t_EcsOpChar => {
+                    serializer.serialize_char(*(ptr as *const u8) as char)
+                }
+                sys::ecs_meta_op_kind_t_EcsOpByte | sys::ecs_meta_op_kind_t_EcsOpU8 => {
+                    serializer.serialize_u8(*(ptr as *const u8))
+                }
+                sys::ecs_meta_op_kind_t_EcsOpU16 => serializer.serialize_u16(*(ptr as *const u16)),
+                sys::ecs_meta_op_kind_t_EcsOpU32 => serializer.serialize_u32(*(ptr as *const u32)),
+                sys::ecs_meta_op_kind_t_EcsOpU64 => serializer.serialize_u64(*(ptr as *const u64)),
+                sys::ecs_meta_op_kind_t_EcsOpUPtr => {
+                    serializer.serialize_u64(*(ptr as *const usize) as u64)
+                }
+                sys::ecs_meta_op_kind_t_EcsOpI8 => serializer.serialize_i8(*(ptr as *const i8)),
+                sys::ecs_meta_op_kind_t_EcsOpI16 => serializer.serialize_i16(*(ptr as *const i16)),
+                sys::ecs_meta_op_kind_t_EcsOpI32 => serializer.serialize_i32(*(ptr as *const i32)),
+                sys::ecs_meta_op_kind_t_EcsOpI64 => serializer.serialize_i64(*(ptr as *const i64)),
+                sys::ecs_meta_op_kind_t_EcsOpIPtr => {
+                    serializer.serialize_i64(*(ptr as *const isize) as i64)
+                }
+                sys::ecs_meta_op_kind_t_EcsOpF32 => serializer.serialize_f32(*(ptr as *const f32)),
+                sys::ecs_meta_op_kind_t_EcsOpF64 => serializer.serialize_f64(*(ptr as *const f64)),
+                kind => Err(ser::Error::custom(format!(
+                    "unexpected serializer operation {kind} for type '{}'",
+                    id_str(world, op.type_)
+                ))),
+            }
+        }
+    }
+}
+
+/// A value forwarded by the `serialize` callback of an opaque type.
+///
+/// Strings are serialized as plain strings rather than as optional C strings, since the
+/// value only exists for the duration of the callback and is never null.
+struct OpaqueValue<'a> {
+    world: WorldRef<'a>,
+    type_id: sys::ecs_entity_t,
+    value: *const c_void,
+}
+
+impl Serialize for OpaqueValue<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let ops = type_ops_or_err(self.world, self.type_id).map_err(ser::Error::custom)?;
+        if ops[0].kind == sys::ecs_meta_op_kind_t_EcsOpString {
+            let str = unsafe { *(self.value as *const *const c_char) };
+            return serializer.serialize_str(unsafe { c_str(str) });
+        }
+
+        OpSerialize {
+            world: self.world,
+            ops,
+            base: self.value,
+        }
+        .serialize(serializer)
+    }
+}
+
+enum OpaqueEvent<'a> {
+    Member(&'a str),
+    Value(sys::ecs_entity_t, *const c_void),
+}
+
+type OpaqueVisitor<'v> = dyn FnMut(OpaqueEvent<'_>) -> bool + 'v;
+
+/// Invoke the `serialize` callback of an opaque type, forwarding the members and values it
+/// produces to `visitor`. Returns `false` if the callback or the visitor failed.
+fn visit_opaque(
+    world: WorldRef,
+    op: &sys::ecs_meta_op_t,
+    ptr: *const c_void,
+    visitor: &mut OpaqueVisitor<'_>,
+) -> bool {
+    #[flecs_ecs_derive::extern_abi]
+    fn value(
+        ser: *const sys::ecs_serializer_t,
+        type_id: sys::ecs_entity_t,
+        value: *const c_void,
+    ) -> i32 {
+        let visitor = unsafe { &mut *((*ser).ctx as *mut &mut OpaqueVisitor<'_>) };
+        if visitor(OpaqueEvent::Value(type_id, value)) {
+            0
+        } else {
+            -1
+        }
+    }
+
+    #[flecs_ecs_derive::extern_abi]
+    fn member(ser: *const sys::ecs_serializer_t, name: *const c_char) -> i32 {
+        let visitor = unsafe { &mut *((*ser).ctx as *mut &mut OpaqueVisitor<'_>) };
+        if visitor(OpaqueEvent::Member(unsafe { c_str(name) })) {
+            0
+        } else {
+            -1
+        }
+    }
+
+    let Some(serialize) = (unsafe { op.is.opaque }) else {
+        return false;
+    };
+
+    let mut visitor = visitor;
+    let ser = sys::ecs_serializer_t {
+        value: Some(value),
+        member: Some(member),
+        world: world.world_ptr(),
+        ctx: &mut visitor as *mut &mut OpaqueVisitor<'_> as *mut c_void,
+    };
+
+    unsafe { serialize(&ser, ptr) == 0 }
+}
+
+impl<'de> DeserializeSeed<'de> for MetaDeserialize<'_> {
+    type Value = ();
+
+    fn deserialize<D: de::Deserializer<'de>>(self, deserializer: D) -> Result<(), D::Error> {
+        let ops = type_ops_or_err(self.world, self.type_id).map_err(de::Error::custom)?;
+        let mut cursor = Cursor::new(self.world, self.type_id, self.ptr);
+        OpDeserialize {
+            world: self.world,
+            ops,
+            cursor: &mut cursor,
+            advance: false,
+        }
+        .deserialize(deserializer)
+    }
+}
+
+fn check<E: de::Error>(result: i32, what: impl FnOnce() -> String) -> Result<(), E> {
+    if result == 0 {
+        Ok(())
+    } else {
+        Err(E::custom(what()))
+    }
+}
+
+/// Deserializes the value described by `ops[0]` into the current position of the cursor.
+struct OpDeserialize<'a, 'c> {
+    world: WorldRef<'a>,
+    ops: &'a [sys::ecs_meta_op_t],
+    cursor: &'c mut Cursor<'a>,
+    /// Move the cursor to the next element before assigning (collection elements).
+    advance: bool,
+}
+
+impl<'de> DeserializeSeed<'de> for OpDeserialize<'_, '_> {
+    type Value = ();
+
+    fn deserialize<D: de::Deserializer<'de>>(self, deserializer: D) -> Result<(), D::Error> {
+        let world = self.world;
+        let op = &self.ops[0];
+        if self.advance {
+            check(self.cursor.next(), || {
+                format!("too many elements for '{}'", id_str(world, op.type_))
+            })?;
+        }
+
+        match op.kind {
+            sys::ecs_meta_op_kind_t_EcsOpPushStruct => {
+                deserializer.deserialize_map(StructVisitor {
+                    world,
+                    members: scope_ops(self.ops),
+                    cursor: self.cursor,
+                })
+            }
+            sys::ecs_meta_op_kind_t_EcsOpPushArray | sys::ecs_meta_op_kind_t_EcsOpPushVector => {
+                deserializer.deserialize_seq(SeqVisitor {
+                    world,
+                    elem: scope_ops(self.ops),
+                    cursor: self.cursor,
+                })
+            }
+            sys::ecs_meta_op_kind_t_EcsOpForward => {
+                let ops = type_ops_or_err(world, op.type_).map_err(de::Error::custom)?;
+                OpDeserialize {
+                    world,
+                    ops,
+                    cursor: self.cursor,
+                    advance: false,
+                }
+                .deserialize(deserializer)
+            }
+            sys::ecs_meta_op_kind_t_EcsOpOpaqueStruct => {
+                let ops = opaque_as_type_ops(world, op).map_err(de::Error::custom)?;
+                deserializer.deserialize_map(StructVisitor {
+                    world,
+                    members: scope_ops(ops),
+                    cursor: self.cursor,
+                })
+            }
+            sys::ecs_meta_op_kind_t_EcsOpOpaqueArray
+            | sys::ecs_meta_op_kind_t_EcsOpOpaqueVector => {
+                let ops = opaque_as_type_ops(world, op).map_err(de::Error::custom)?;
+                deserializer.deserialize_seq(SeqVisitor {
+                    world,
+                    elem: scope_ops(ops),
+                    cursor: self.cursor,
+                })
+            }
+            sys::ecs_meta_op_kind_t_EcsOpOpaqueValue => {
+                let ops = opaque_as_type_ops(world, op).map_err(de::Error::custom)?;
+                let kind = ops[0].kind;
+                let visitor = ValueVisitor {
+                    cursor: self.cursor,
+                    kind,
+                };
+                if kind == sys::ecs_meta_op_kind_t_EcsOpString {
+                    deserializer.deserialize_str(visitor)
+                } else {
+                    deserialize_value(deserializer, visitor)
+                }
+            }
+            _ => deserialize_value(
+                deserializer,
+                ValueVisitor {
+                    cursor: self.cursor,
+                    kind: op.kind,
+                },
+            ),
+        }
+    }
+}
+
+/// Request the serde data type that matches the serialized layout of a value op.
+fn deserialize_value<'de, D: de::Deserializer<'de>>(
+    deserializer: D,
+    visitor: ValueVisitor<'_, '_>,
+) -> Result<(), D::Error> {
+    match visitor.kind {
+        sys::ecs_meta_op_kind_t_EcsOpBool => deserializer.deserialize_bool(visitor),
+        sys::ecs_meta_op_kind_t_EcsOpChar => deserializer.deserialize_char(visitor),
+        sys::ecs_meta_op_kind_t_EcsOpByte | sys::ecs_meta_op_kind_t_EcsOpU8 => {
+            deserializer.deserialize_u8(visitor)
+        }
+        sys::ecs_meta_op_kind_t_EcsOpU16 => deserializer.deserialize_u16(visitor),
+        sys::ecs_meta_op_kind_t_EcsOpU32 => deserializer.deserialize_u32(visitor),
+        sys::ecs_meta_op_kind_t_EcsOpU64 | sys::ecs_meta_op_kind_t_EcsOpUPtr => {
+            deserializer.deserialize_u64(visitor)
+        }
+        sys::ecs_meta_op_kind_t_EcsOpI8 => deserializer.deserialize_i8(visitor),
+        sys::ecs_meta_op_kind_t_EcsOpI16 => deserializer.deserialize_i16(visitor),
+        sys::ecs_meta_op_kind_t_EcsOpI32 => deserializer.deserialize_i32(visitor),
+        sys::ecs_meta_op_kind_t_EcsOpI64 | sys::ecs_meta_op_kind_t_EcsOpIPtr => {
+            deserializer.deserialize_i64(visitor)
+        }
+        sys::ecs_meta_op_kind_t_EcsOpF32 => deserializer.deserialize_f32(visitor),
+        sys::ecs_meta_op_kind_t_EcsOpF64 => deserializer.deserialize_f64(visitor),
+        sys::ecs_meta_op_kind_t_EcsOpString => deserializer.deserialize_option(visitor),
+        // enum and bitmask constants, entities and ids
+        _ => deserializer.deserialize_str(visitor),
+    }
+}
+
+struct StructVisitor<'a, 'c> {
+    world: WorldRef<'a>,
+    members: &'a [sys::ecs_meta_op_t],
+    cursor: &'c mut Cursor<'a>,
+}
+
+impl<'de> Visitor<'de> for StructVisitor<'_, '_> {
+    type Value = ();
+
+    fn expecting(&self, formatter: &mut core::fmt::Formatter) -> core::fmt::Result {
+        formatter.write_str("a map of struct members")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<(), A::Error> {
+        check(self.cursor.push(), || {
+            String::from("failed to push struct scope")
+        })?;
+
+        while let Some(name) = map.next_key::<String>()? {
+            let mut i = 0;
+            while i < self.members.len() && unsafe { c_str(self.members[i].name) } != name {
+                i += self.members[i].op_count as usize;
+            }
+            if i >= self.members.len() {
+                return Err(de::Error::custom(format!("unknown member '{name}'")));
+            }
+
+            check(self.cursor.member(&name), || {
+                format!("unknown member '{name}'")
+            })?;
+            map.next_value_seed(OpDeserialize {
+                world: self.world,
+                ops: &self.members[i..],
+                cursor: self.cursor,
+                advance: false,
+            })?;
+        }
+
+        check(self.cursor.pop(), || {
+            String::from("failed to pop struct scope")
+        })
+    }
+}
+
+struct SeqVisitor<'a, 'c> {
+    world: WorldRef<'a>,
+    elem: &'a [sys::ecs_meta_op_t],
+    cursor: &'c mut Cursor<'a>,
+}
+
+impl<'de> Visitor<'de> for SeqVisitor<'_, '_> {
+    type Value = ();
+
+    fn expecting(&self, formatter: &mut core::fmt::Formatter) -> core::fmt::Result {
+        formatter.write_str("a sequence of elements")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<(), A::Error> {
+        check(self.cursor.push(), || {
+            String::from("failed to push collection scope")
+        })?;
+
+        let mut advance = false;
+        while seq
+            .next_element_seed(OpDeserialize {
+                world: self.world,
+                ops: self.elem,
+                cursor: self.cursor,
+                advance,
+            })?
+            .is_some()
+        {
+            advance = true;
+        }
+
+        check(self.cursor.pop(), || {
+            String::from("failed to pop collection scope")
+        })
+    }
+}
+
+/// Assigns a primitive, constant, entity or id value through the cursor.
+struct ValueVisitor<'a, 'c> {
+    cursor: &'c mut Cursor<'a>,
+    kind: sys::ecs_meta_op_kind_t,
+}
+
+impl<'de> Visitor<'de> for ValueVisitor<'_, '_> {
+    type Value = ();
+
+    fn expecting(&self, formatter: &mut core::fmt::Formatter) -> core::fmt::Result {
+        formatter.write_str("a primitive value")
+    }
+
+    fn visit_bool<E: de::Error>(self, v: bool) -> Result<(), E> {
+        check(self.cursor.set_bool(v), || format!("cannot assign '{v}'"))
+    }
+
+    fn visit_i64<E: de::Error>(self, v: i64) -> Result<(), E> {
+        check(self.cursor.set_int(v), || format!("cannot assign '{v}'"))
+    }
+
+    fn visit_u64<E: de::Error>(self, v: u64) -> Result<(), E> {
+        check(self.cursor.set_uint(v), || format!("cannot assign '{v}'"))
+    }
+
+    fn visit_f64<E: de::Error>(self, v: f64) -> Result<(), E> {
+        check(self.cursor.set_float(v), || format!("cannot assign '{v}'"))
+    }
+
+    fn visit_char<E: de::Error>(self, v: char) -> Result<(), E> {
+        check(self.cursor.set_char(v), || format!("cannot assign '{v}'"))
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<(), E> {
+        let result = match self.kind {
+            sys::ecs_meta_op_kind_t_EcsOpBitmask if v == "0" => self.cursor.set_uint(0),
+            sys::ecs_meta_op_kind_t_EcsOpId if v == "#0" => self.cursor.set_id(0),
+            _ => self.cursor.set_string(v),
+        };
+        check(result, || format!("cannot assign '{v}'"))
+    }
+
+    fn visit_none<E: de::Error>(self) -> Result<(), E> {
+        check(self.cursor.set_null(), || {
+            String::from("cannot assign null")
+        })
+    }
+
+    fn visit_unit<E: de::Error>(self) -> Result<(), E> {
+        self.visit_none()
+    }
+
+    fn visit_some<D: de::Deserializer<'de>>(self, deserializer: D) -> Result<(), D::Error> {
+        deserializer.deserialize_str(self)
+    }
+}
@@ -11,6 +11,8 @@ mod impl_primitives;
 pub mod macros;
 mod meta_fn_types;
 mod meta_functions;
+#[cfg(feature = "serde")]
+mod meta_serde;
 mod meta_traits;
 mod opaque;
 mod untyped_component;
@@ -25,6 +27,8 @@ pub use declarations::*;
 pub use ecs_serializer::*;
 pub use macros::*;
 pub use meta_fn_types::*;
+#[cfg(feature = "serde")]
+pub use meta_serde::*;
 pub use meta_traits::MetaMember;
 pub use opaque::*;
 
@@ -2,6 +2,8 @@ mod module;
 pub use module::*;
 mod metric_builder;
 pub use metric_builder::*;
+mod prometheus;
+pub use prometheus::PROMETHEUS_CONTENT_TYPE;
 mod types;
 pub use types::*;
 
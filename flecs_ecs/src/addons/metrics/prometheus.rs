@@ -0,0 +1,242 @@
+//! Rendering of metrics in the Prometheus text exposition format.
+//!
+//! Each metric entity becomes a metric family. The name of the family is the path of the
+//! metric entity, followed by the unit of the metric and, for counters, by `_total`. Each
+//! instance of a metric becomes a sample with an `entity` label that holds the path of the
+//! entity that the instance measures.
+
+use core::fmt::Write;
+
+use crate::core::*;
+use crate::sys;
+
+extern crate alloc;
+use alloc::{string::String, vec::Vec};
+
+/// The content type of the Prometheus text exposition format.
+pub const PROMETHEUS_CONTENT_TYPE: &core::ffi::CStr = c"text/plain; version=0.0.4; charset=utf-8";
+
+/// Returns the entities that have `id`.
+fn entities_with(world: &World, id: sys::ecs_id_t) -> Vec<sys::ecs_entity_t> {
+    let mut entities = Vec::new();
+    unsafe {
+        let mut it = sys::ecs_each_id(world.world_ptr(), id);
+        while sys::ecs_each_next(&mut it) {
+            entities.extend_from_slice(core::slice::from_raw_parts(it.entities, it.count as usize));
+        }
+    }
+    entities
+}
+
+/// Returns the value of a metric instance, or of a metric that doesn't have instances.
+fn value_of(entity: EntityView) -> Option<f64> {
+    let value =
+        entity.get_untyped(unsafe { sys::FLECS_IDEcsMetricValueID_ }) as *const sys::EcsMetricValue;
+    unsafe { value.as_ref() }.map(|value| value.value)
+}
+
+/// Returns the members of the type that holds the values of a metric with one value per
+/// relationship target.
+fn struct_members<'a>(metric: EntityView<'a>) -> &'a [sys::ecs_member_t] {
+    let ty = metric.get_untyped(unsafe { sys::FLECS_IDEcsStructID_ }) as *const sys::EcsStruct;
+    match unsafe { ty.as_ref() } {
+        Some(ty) if !ty.members.array.is_null() => unsafe {
+            core::slice::from_raw_parts(
+                ty.members.array as *const sys::ecs_member_t,
+                ty.members.count as usize,
+            )
+        },
+        _ => &[],
+    }
+}
+
+/// Returns the unit of a metric, as set on the member that the metric measures.
+fn unit_of<'a>(metric: EntityView<'a>) -> Option<EntityView<'a>> {
+    let world = metric.world();
+    let mut unit = struct_members(metric).first().map(|member| member.unit);
+    metric.each_component(|id| {
+        if unit.is_none() && id.is_pair() {
+            let member = id
+                .second_id()
+                .get_untyped(unsafe { sys::FLECS_IDEcsMemberID_ })
+                as *const sys::EcsMember;
+            unit = unsafe { member.as_ref() }.map(|member| member.unit);
+        }
+    });
+    unit.filter(|&unit| unit != 0)
+        .map(|unit| EntityView::new_from(world, unit))
+}
+
+/// Replaces the characters that can't appear in a metric name, like the `.` that separates
+/// the elements of a path, with `_`.
+fn sanitize_name(name: &str) -> String {
+    let mut sanitized: String = name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '_' || c == ':' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    if sanitized.starts_with(|c: char| c.is_ascii_digit()) {
+        sanitized.insert(0, '_');
+    }
+    sanitized
+}
+
+/// Converts a name like `MilliSeconds` to `milli_seconds`.
+fn to_snake_case(name: &str) -> String {
+    let mut snake = String::with_capacity(name.len() + 4);
+    for (i, c) in name.chars().enumerate() {
+        if c.is_ascii_uppercase() {
+            if i != 0 {
+                snake.push('_');
+            }
+            snake.push(c.to_ascii_lowercase());
+        } else {
+            snake.push(c);
+        }
+    }
+    snake
+}
+
+/// Returns the name of the metric family of a metric.
+fn family_name(metric: EntityView, is_counter: bool) -> String {
+    let path = metric
+        .path_w_sep(".", "")
+        .unwrap_or_else(|| alloc::format!("{}", metric.id()));
+    let mut name = sanitize_name(&path);
+    if let Some(unit) = unit_of(metric).and_then(EntityView::get_name) {
+        let unit = sanitize_name(&to_snake_case(&unit));
+        if !name.ends_with(&alloc::format!("_{unit}")) {
+            name.push('_');
+            name.push_str(&unit);
+        }
+    }
+    if is_counter && !name.ends_with("_total") {
+        name.push_str("_total");
+    }
+    name
+}
+
+fn write_escaped(out: &mut String, text: &str, escape_quotes: bool) {
+    for c in text.chars() {
+        match c {
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '"' if escape_quotes => out.push_str("\\\""),
+            c => out.push(c),
+        }
+    }
+}
+
+fn write_sample(out: &mut String, name: &str, labels: &[(&str, &str)], value: f64) {
+    out.push_str(name);
+    if !labels.is_empty() {
+        out.push('{');
+        for (i, (label, label_value)) in labels.iter().enumerate() {
+            if i != 0 {
+                out.push(',');
+            }
+            out.push_str(label);
+            out.push_str("=\"");
+            write_escaped(out, label_value, true);
+            out.push('"');
+        }
+        out.push('}');
+    }
+    out.push(' ');
+    if value.is_nan() {
+        out.push_str("NaN");
+    } else if value.is_infinite() {
+        out.push_str(if value > 0.0 { "+Inf" } else { "-Inf" });
+    } else {
+        let _ = write!(out, "{value}");
+    }
+    out.push('\n');
+}
+
+fn write_metric(out: &mut String, metric: EntityView) {
+    let kind = metric.target(unsafe { sys::EcsMetric }, 0);
+    // metrics that count ids without targets don't have a kind, and are counters
+    let is_counter = kind.is_none_or(|kind| kind != unsafe { sys::EcsGauge });
+    let name = family_name(metric, is_counter);
+
+    #[cfg(feature = "flecs_doc")]
+    if let Some(brief) = metric.world().doc_brief(metric) {
+        out.push_str("# HELP ");
+        out.push_str(&name);
+        out.push(' ');
+        write_escaped(out, &brief, false);
+        out.push('\n');
+    }
+    out.push_str("# TYPE ");
+    out.push_str(&name);
+    out.push_str(if is_counter { " counter\n" } else { " gauge\n" });
+
+    if let Some(value) = value_of(metric) {
+        write_sample(out, &name, &[], value);
+    }
+
+    let values_id = ecs_pair(*metric.id(), unsafe { sys::FLECS_IDEcsMetricValueID_ });
+    let members = struct_members(metric);
+    metric.each_child(|instance| {
+        let source = instance.get_untyped(unsafe { sys::FLECS_IDEcsMetricSourceID_ })
+            as *const sys::EcsMetricSource;
+        let Some(source) = (unsafe { source.as_ref() }) else {
+            return;
+        };
+        let path = EntityView::new_from(metric.world(), source.entity)
+            .path_w_sep(".", "")
+            .unwrap_or_default();
+
+        if let Some(value) = value_of(instance) {
+            write_sample(out, &name, &[("entity", &path)], value);
+            return;
+        }
+
+        // metrics of a relationship with one value per target store the values in a struct
+        let values = instance.get_untyped(values_id) as *const u8;
+        if values.is_null() {
+            return;
+        }
+        for member in members {
+            let target = unsafe { core::ffi::CStr::from_ptr(member.name) }
+                .to_str()
+                .unwrap_or_default();
+            let value = unsafe { *(values.add(member.offset as usize) as *const f64) };
+            write_sample(out, &name, &[("entity", &path), ("target", target)], value);
+        }
+    });
+}
+
+/// Renders the metrics of a world in the Prometheus text exposition format.
+pub(crate) fn render(world: &World) -> String {
+    let mut metrics = entities_with(world, unsafe { sys::EcsMetric });
+    // metrics that count ids without targets store their value on the metric entity
+    metrics.extend(
+        entities_with(world, unsafe { sys::FLECS_IDEcsMetricValueID_ })
+            .into_iter()
+            .filter(|&e| unsafe {
+                !sys::ecs_has_id(world.world_ptr(), e, sys::FLECS_IDEcsMetricSourceID_)
+            }),
+    );
+
+    let mut metrics: Vec<(String, EntityView)> = metrics
+        .into_iter()
+        .map(|e| {
+            let metric = EntityView::new_from(world, e);
+            (metric.path().unwrap_or_default(), metric)
+        })
+        .collect();
+    metrics.sort_by(|a, b| a.0.cmp(&b.0).then(a.1.id().cmp(&b.1.id())));
+    metrics.dedup_by_key(|(_, metric)| metric.id());
+
+    let mut out = String::new();
+    for (_, metric) in metrics {
+        write_metric(&mut out, metric);
+    }
+    out
+}
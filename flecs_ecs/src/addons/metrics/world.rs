@@ -3,6 +3,9 @@ use crate::{
     core::{Entity, World},
 };
 
+extern crate alloc;
+use alloc::string::String;
+
 impl World {
     /// Creates a new [`MetricBuilder`] instance.
     ///
@@ -12,4 +15,42 @@ impl World {
     pub fn metric(&self, entity: impl Into<Entity>) -> MetricBuilder<'_> {
         MetricBuilder::new(self, entity.into())
     }
+
+    /// Renders the values of all metrics in the Prometheus text exposition format.
+    ///
+    /// Every metric entity becomes a metric family, named after the path of the entity with
+    /// `_` as separator. The name is followed by the unit of the measured member, like
+    /// `_seconds`, and for counters by `_total`. The brief description of the metric is used
+    /// as help text. Each metric instance becomes a sample, with an `entity` label that holds
+    /// the path of the entity that the instance measures.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use flecs_ecs::prelude::*;
+    /// use flecs_ecs::addons::metrics::*;
+    ///
+    /// #[derive(Component)]
+    /// struct Npc;
+    ///
+    /// let world = World::new();
+    /// world.import::<MetricsModule>();
+    ///
+    /// let npcs = world.entity_named("npcs");
+    /// world
+    ///     .metric(npcs)
+    ///     .kind(CounterId)
+    ///     .id(Npc)
+    ///     .brief("Number of NPCs");
+    ///
+    /// world.entity().add(Npc);
+    /// world.progress_time(1.0);
+    ///
+    /// let text = world.metrics_to_prometheus();
+    /// assert!(text.contains("# HELP npcs_total Number of NPCs\n"));
+    /// assert!(text.contains("# TYPE npcs_total counter\n"));
+    /// ```
+    pub fn metrics_to_prometheus(&self) -> String {
+        super::prometheus::render(self)
+    }
 }
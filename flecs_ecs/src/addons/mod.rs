@@ -59,6 +59,11 @@ pub mod alerts;
 #[cfg(feature = "flecs_alerts")]
 pub use alerts::*;
 
+#[cfg(feature = "flecs_http")]
+pub mod http;
+#[cfg(feature = "flecs_http")]
+pub use http::*;
+
 // this is not feature gated to flecs_meta so calling `.meta()` on a component will always work despite meta being disabled.
 pub trait Meta<Component> {
     fn meta(component: flecs_ecs::core::Component<Component>);
@@ -0,0 +1,170 @@
+//! Fixed timestep simulation, which runs the systems of the [`FixedUpdate`] phase zero or more
+//! times per frame with a constant delta time.
+
+use flecs_ecs_derive::Component;
+
+use crate::core::*;
+use crate::sys;
+
+/// Phase of the systems that run with a fixed timestep.
+///
+/// The phase is not part of the default pipeline. When a fixed timestep is set with
+/// [`World::set_fixed_timestep()`], systems in this phase run before the systems of the default
+/// pipeline, once for each step that fits in the time passed since the last frame.
+///
+/// # Example
+///
+/// ```
+/// use flecs_ecs::prelude::*;
+///
+/// #[derive(Component)]
+/// struct Position {
+///     x: f32,
+/// }
+///
+/// let world = World::new();
+/// world.set_fixed_timestep(0.25, 4);
+///
+/// world
+///     .system::<&mut Position>()
+///     .kind(FixedUpdate)
+///     .each_iter(|it, _, pos| {
+///         assert_eq!(it.delta_time(), 0.25);
+///         pos.x += 1.0;
+///     });
+///
+/// let e = world.entity().set(Position { x: 0.0 });
+///
+/// world.progress_time(0.6);
+///
+/// e.get::<&Position>(|pos| assert_eq!(pos.x, 2.0));
+/// ```
+#[derive(Debug, Clone, Copy, Component, Default)]
+pub struct FixedUpdate;
+
+/// State of the fixed timestep of a world.
+///
+/// This is typically obtained via [`World::fixed_timestep()`].
+#[derive(Debug, Clone, Copy)]
+pub struct FixedTimestep {
+    pub(crate) step: FTime,
+    pub(crate) max_steps: i32,
+    pub(crate) accumulator: FTime,
+    pub(crate) alpha: FTime,
+    pub(crate) steps: i32,
+    pub(crate) pipeline: Entity,
+}
+
+impl FixedTimestep {
+    /// The delta time of a step.
+    pub fn step(&self) -> FTime {
+        self.step
+    }
+
+    /// The maximum number of steps per frame, or `0` if the number of steps isn't limited.
+    pub fn max_steps(&self) -> i32 {
+        self.max_steps
+    }
+
+    /// The time that has passed, but that hasn't been simulated by a step yet.
+    pub fn accumulator(&self) -> FTime {
+        self.accumulator
+    }
+
+    /// The fraction of a step that hasn't been simulated yet, in the range `[0, 1)`.
+    ///
+    /// Render systems can use this to interpolate between the state of the last two steps.
+    pub fn alpha(&self) -> FTime {
+        self.alpha
+    }
+
+    /// Number of steps that ran in the last frame.
+    pub fn steps(&self) -> i32 {
+        self.steps
+    }
+
+    /// The pipeline with the systems of the [`FixedUpdate`] phase.
+    pub fn pipeline(&self) -> Entity {
+        self.pipeline
+    }
+}
+
+/// Creates the pipeline with the systems of the [`FixedUpdate`] phase.
+pub(crate) fn fixed_pipeline_init(world: &World) -> Entity {
+    let mut phase = [sys::ecs_term_t::default(); 2];
+    phase[0].id = ecs_dependson(FixedUpdate::entity_id(world));
+    phase[0].trav = ECS_DEPENDS_ON;
+    // the second term is empty, which pipeline_init skips
+    let pipeline = super::ordering::pipeline_init(world, phase);
+    ecs_assert!(
+        pipeline != 0,
+        FlecsErrorCode::InvalidOperation,
+        "failed to create fixed timestep pipeline"
+    );
+    Entity(pipeline)
+}
+
+/// Runs the systems of the `OnStart` phase, which [`World::progress()`] runs before the
+/// first frame.
+fn run_startup_systems(world: &World) {
+    let world_ptr = world.ptr_mut();
+    if unsafe { sys::ecs_count_id(world_ptr, ecs_dependson(ECS_ON_START)) } == 0 {
+        return;
+    }
+
+    let mut phase = [sys::ecs_term_t::default(); 2];
+    phase[0].id = ECS_PHASE;
+    phase[0].src.id = ECS_CASCADE;
+    phase[0].trav = ECS_DEPENDS_ON;
+    phase[1].id = ecs_dependson(ECS_ON_START);
+    phase[1].trav = ECS_DEPENDS_ON;
+    let pipeline = super::ordering::pipeline_init(world, phase);
+    unsafe {
+        sys::ecs_run_pipeline(world_ptr, pipeline, 0.0);
+        sys::ecs_delete(world_ptr, pipeline);
+    }
+}
+
+/// Progresses a world that has a fixed timestep, like `ecs_progress` does, but runs the
+/// fixed timestep pipeline before the default pipeline.
+pub(crate) fn progress(world: &World, user_delta_time: FTime) -> bool {
+    let world_ptr = world.ptr_mut();
+    let delta_time = unsafe { sys::ecs_frame_begin(world_ptr, user_delta_time) };
+
+    // `ecs_get_world_info` is declared to return the hand-written `WorldInfo`, whose time
+    // fields don't match the C struct, so read the frame count with the bindgen layout
+    let info = unsafe { &*sys::ecs_get_world_info(world_ptr).cast::<sys::ecs_world_info_t>() };
+    if info.frame_count_total == 0 {
+        run_startup_systems(world);
+    }
+
+    if let Some(mut fixed) = world.world_ctx().fixed_timestep {
+        fixed.accumulator += delta_time;
+        fixed.steps = 0;
+        while fixed.accumulator >= fixed.step
+            && (fixed.max_steps <= 0 || fixed.steps < fixed.max_steps)
+        {
+            unsafe { sys::ecs_run_pipeline(world_ptr, *fixed.pipeline, fixed.step) };
+            fixed.accumulator -= fixed.step;
+            fixed.steps += 1;
+        }
+        // drop the steps that didn't fit in the frame, so the simulation can catch up
+        fixed.accumulator %= fixed.step;
+        fixed.alpha = fixed.accumulator / fixed.step;
+
+        // systems may have changed the fixed timestep while it ran
+        if let Some(state) = world.world_ctx_mut().fixed_timestep.as_mut()
+            && state.pipeline == fixed.pipeline
+        {
+            state.accumulator = fixed.accumulator;
+            state.alpha = fixed.alpha;
+            state.steps = fixed.steps;
+        }
+    }
+
+    unsafe {
+        sys::ecs_run_pipeline(world_ptr, 0, delta_time);
+        sys::ecs_frame_end(world_ptr);
+        !sys::ecs_should_quit(world_ptr)
+    }
+}
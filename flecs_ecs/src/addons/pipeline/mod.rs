@@ -1,7 +1,17 @@
 //! Pipelines order and schedule systems for execution.
 
+mod fixed_timestep;
+pub(crate) mod ordering;
 mod pipeline_builder;
+#[cfg(feature = "flecs_perf_trace")]
+pub(crate) mod profiler;
+mod schedule;
+pub use fixed_timestep::{FixedTimestep, FixedUpdate};
+pub(crate) use fixed_timestep::{fixed_pipeline_init, progress};
+pub use ordering::{SystemOrderError, SystemSet};
 pub use pipeline_builder::*;
+pub(crate) use schedule::pipeline_schedule;
+pub use schedule::{PipelineSchedule, ScheduleSegment, ScheduledSystem, SyncReason};
 
 use core::ops::{Deref, DerefMut};
 
@@ -0,0 +1,485 @@
+//! Ordering of the systems within a phase, and system sets that are ordered as a group.
+//!
+//! By default the systems of a phase run in the order in which they were created. Systems can
+//! be ordered relative to each other with [`SystemBuilder::before()`] and
+//! [`SystemBuilder::after()`], and relative to the systems of a [`SystemSet`]. The constraints
+//! only order systems of the same phase, since the order of the phases comes first.
+//!
+//! When the constraints change, the systems of each phase are sorted topologically, keeping the
+//! creation order for systems that aren't constrained, and the builtin pipeline is replaced with
+//! a pipeline that runs the systems in that order. This happens when a system or set with
+//! constraints is created, or, if the world is in readonly or deferred mode then, before the
+//! default pipeline runs or its schedule is computed. Constraints that contain a cycle are
+//! logged as an error, and the systems keep their previous order.
+//!
+//! The constraints order the systems of the builtin pipeline and of the fixed timestep pipeline.
+//! Pipelines that are created with [`World::pipeline()`] run their systems in their own order.
+//!
+//! [`SystemBuilder::before()`]: crate::addons::system::SystemBuilder::before
+//! [`SystemBuilder::after()`]: crate::addons::system::SystemBuilder::after
+
+use core::cmp::Reverse;
+use core::fmt::{Display, Formatter};
+use core::ops::Deref;
+
+use crate::core::*;
+use crate::sys;
+
+extern crate alloc;
+use alloc::{
+    collections::{BTreeMap, BTreeSet, BinaryHeap},
+    format,
+    string::String,
+    vec::Vec,
+};
+use hashbrown::HashMap;
+
+/// A named group of systems, which can be ordered relative to other systems and sets.
+///
+/// Systems are added to a set with [`SystemBuilder::in_set()`]. Ordering a set before or after
+/// something orders each of its systems, which only affects systems of the same phase.
+///
+/// These are typically constructed via [`World::system_set()`].
+///
+/// # Example
+///
+/// ```
+/// use flecs_ecs::prelude::*;
+///
+/// let world = World::new();
+///
+/// let physics = world.system_set("Physics");
+/// let render = world.system_set("Render");
+/// render.after(physics);
+///
+/// world
+///     .system_named::<()>("Draw")
+///     .in_set(render)
+///     .run(|mut it| while it.next() {});
+///
+/// world
+///     .system_named::<()>("Collide")
+///     .in_set(physics)
+///     .run(|mut it| while it.next() {});
+///
+/// world.progress();
+///
+/// let schedule = world.pipeline_schedule(world.get_pipeline());
+/// let names = schedule
+///     .systems()
+///     .map(|system| system.system().name())
+///     .collect::<Vec<_>>();
+/// assert_eq!(names, ["Collide", "Draw"]);
+/// ```
+///
+/// [`SystemBuilder::in_set()`]: crate::addons::system::SystemBuilder::in_set
+#[derive(Clone, Copy)]
+pub struct SystemSet<'a> {
+    entity: EntityView<'a>,
+}
+
+impl<'a> Deref for SystemSet<'a> {
+    type Target = EntityView<'a>;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.entity
+    }
+}
+
+impl<'a> From<SystemSet<'a>> for EntityView<'a> {
+    fn from(set: SystemSet<'a>) -> Self {
+        set.entity
+    }
+}
+
+impl<'a> From<SystemSet<'a>> for Entity {
+    fn from(set: SystemSet<'a>) -> Self {
+        set.entity.id
+    }
+}
+
+impl<'a> SystemSet<'a> {
+    pub(crate) fn new(entity: EntityView<'a>) -> Self {
+        Self { entity }
+    }
+
+    /// Run the systems of this set before a system, or before the systems of a set.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The system or set.
+    pub fn before(&self, other: impl IntoEntity) -> &Self {
+        let world = self.entity.world();
+        add_constraint(world, self.entity.id, other.into_entity(world));
+        apply(&world.real_world());
+        self
+    }
+
+    /// Run the systems of this set after a system, or after the systems of a set.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The system or set.
+    pub fn after(&self, other: impl IntoEntity) -> &Self {
+        let world = self.entity.world();
+        add_constraint(world, other.into_entity(world), self.entity.id);
+        apply(&world.real_world());
+        self
+    }
+
+    pub fn entity(&self) -> EntityView<'a> {
+        self.entity
+    }
+}
+
+/// Error for ordering constraints that contradict each other.
+///
+/// This is returned by [`World::validate_system_order()`]. When the systems are sorted, it is
+/// logged as an error instead, and the systems keep their previous order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SystemOrderError {
+    phase: String,
+    systems: Vec<String>,
+}
+
+impl SystemOrderError {
+    /// The name of the phase of the systems.
+    pub fn phase(&self) -> &str {
+        &self.phase
+    }
+
+    /// The names of the systems in the cycle, where each system has to run before the next one,
+    /// and the last one before the first one.
+    pub fn systems(&self) -> &[String] {
+        &self.systems
+    }
+}
+
+impl Display for SystemOrderError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "cycle in the order of the systems of phase {}: ",
+            self.phase
+        )?;
+        for system in &self.systems {
+            write!(f, "{system} -> ")?;
+        }
+        write!(f, "{}", self.systems.first().map_or("", String::as_str))
+    }
+}
+
+impl core::error::Error for SystemOrderError {}
+
+/// The ordering constraints of the systems of a world.
+#[derive(Default)]
+pub(crate) struct SystemOrder {
+    /// Constraints where the first system or set runs before the second one.
+    constraints: Vec<(Entity, Entity)>,
+    /// Systems and the sets they are in.
+    members: Vec<(Entity, Entity)>,
+    /// Sort keys of the systems that don't run in creation order. Other systems are sorted by
+    /// their id.
+    keys: HashMap<u64, u64>,
+    /// Whether the constraints changed since the systems were sorted.
+    dirty: bool,
+    /// The pipeline that replaced the builtin pipeline.
+    pipeline: Entity,
+}
+
+pub(crate) fn add_constraint(world: WorldRef, first: Entity, second: Entity) {
+    let world = world.real_world();
+    let order = &mut world.world_ctx_mut().system_order;
+    order.constraints.push((first, second));
+    order.dirty = true;
+}
+
+pub(crate) fn add_to_set(world: WorldRef, system: Entity, set: Entity) {
+    let world = world.real_world();
+    let order = &mut world.world_ctx_mut().system_order;
+    order.members.push((system, set));
+    order.dirty = true;
+}
+
+fn system_name(world: &World, system: u64) -> String {
+    world
+        .entity_from_id(system)
+        .get_name()
+        .unwrap_or_else(|| format!("#{system}"))
+}
+
+/// Sorts the systems of each phase that has ordering constraints, and returns the sort keys of
+/// the systems that don't run in creation order.
+fn sort_keys(world: &World) -> Result<HashMap<u64, u64>, SystemOrderError> {
+    let world_ptr = world.world_ptr();
+    let order = &world.world_ctx().system_order;
+    let phase_of =
+        |system: u64| unsafe { sys::ecs_get_target(world_ptr, system, ECS_DEPENDS_ON, 0) };
+    let is_system = |entity: Entity| {
+        world.is_alive(entity) && world.entity_from_id(entity).has(flecs::system::System)
+    };
+    let systems_of = |node: Entity| -> Vec<u64> {
+        if is_system(node) {
+            return alloc::vec![*node];
+        }
+        order
+            .members
+            .iter()
+            .filter(|&&(system, set)| set == node && is_system(system))
+            .map(|&(system, _)| *system)
+            .collect()
+    };
+
+    // constraints between systems, by phase
+    let mut edges: BTreeMap<u64, BTreeSet<(u64, u64)>> = BTreeMap::new();
+    for &(first, second) in &order.constraints {
+        let seconds = systems_of(second);
+        for first in systems_of(first) {
+            for &second in &seconds {
+                let phase = phase_of(first);
+                if phase != 0 && phase == phase_of(second) {
+                    edges.entry(phase).or_default().insert((first, second));
+                }
+            }
+        }
+    }
+
+    // disabled systems keep their place, so they run in order when they are enabled again
+    let mut systems: BTreeMap<u64, BTreeSet<u64>> = BTreeMap::new();
+    world
+        .query::<()>()
+        .with(flecs::system::System)
+        .query_flags(QueryFlags::MatchDisabled)
+        .build()
+        .each_entity(|system, _| {
+            let phase = phase_of(*system.id());
+            if edges.contains_key(&phase) {
+                systems.entry(phase).or_default().insert(*system.id());
+            }
+        });
+
+    let mut keys = HashMap::new();
+    for (phase, edges) in &edges {
+        let mut incoming: BTreeMap<u64, usize> = systems
+            .get(phase)
+            .into_iter()
+            .flatten()
+            .map(|&s| (s, 0))
+            .collect();
+        for &(first, second) in edges {
+            incoming.entry(first).or_default();
+            *incoming.entry(second).or_default() += 1;
+        }
+
+        // Kahn's algorithm, which takes the system that was created first when several systems
+        // can run next
+        let mut ready: BinaryHeap<Reverse<u64>> = incoming
+            .iter()
+            .filter(|&(_, &count)| count == 0)
+            .map(|(&system, _)| Reverse(system))
+            .collect();
+        let mut sorted = Vec::with_capacity(incoming.len());
+        while let Some(Reverse(system)) = ready.pop() {
+            sorted.push(system);
+            for &(_, second) in edges.range((system, 0)..=(system, u64::MAX)) {
+                let count = incoming.get_mut(&second).expect("system of constraint");
+                *count -= 1;
+                if *count == 0 {
+                    ready.push(Reverse(second));
+                }
+            }
+        }
+
+        if sorted.len() < incoming.len() {
+            return Err(SystemOrderError {
+                phase: system_name(world, *phase),
+                systems: find_cycle(edges, &incoming)
+                    .into_iter()
+                    .map(|system| system_name(world, system))
+                    .collect(),
+            });
+        }
+
+        // reuse the ids of the systems as keys, so the keys of the systems of different phases
+        // keep their relative order
+        let ids = incoming.keys();
+        for (&system, &key) in sorted.iter().zip(ids) {
+            if system != key {
+                keys.insert(system, key);
+            }
+        }
+    }
+    Ok(keys)
+}
+
+/// Finds a cycle among the systems that are left after a topological sort, which all have a
+/// constraint with another system that is left.
+fn find_cycle(edges: &BTreeSet<(u64, u64)>, incoming: &BTreeMap<u64, usize>) -> Vec<u64> {
+    let is_left = |system: &u64| incoming.get(system).is_some_and(|&count| count > 0);
+    let mut path: Vec<u64> = Vec::new();
+    let mut system = *incoming.keys().find(|system| is_left(system)).unwrap();
+    loop {
+        if let Some(start) = path.iter().position(|&s| s == system) {
+            // the path follows the constraints backwards
+            let mut cycle = path.split_off(start);
+            cycle.reverse();
+            let first = cycle.iter().enumerate().min_by_key(|&(_, s)| s).unwrap().0;
+            cycle.rotate_left(first);
+            return cycle;
+        }
+        path.push(system);
+        system = edges
+            .iter()
+            .find(|&&(first, second)| second == system && is_left(&first))
+            .map(|&(first, _)| first)
+            .unwrap();
+    }
+}
+
+/// Checks the ordering constraints of the systems of a world.
+pub(crate) fn validate(world: &World) -> Result<(), SystemOrderError> {
+    sort_keys(world).map(|_| ())
+}
+
+/// Sorts the systems when the ordering constraints changed, and runs the pipelines in the new
+/// order. Pipelines can't be replaced while the world is in readonly or deferred mode, so the
+/// systems are sorted the next time this is called.
+///
+/// When the constraints contain a cycle, the error is logged and the previous order is kept.
+pub(crate) fn apply(world: &World) {
+    if !world.world_ctx().system_order.dirty || world.is_readonly() || world.is_deferred() {
+        return;
+    }
+
+    let keys = sort_keys(world);
+    let world_ptr = world.ptr_mut();
+    let order = &mut world.world_ctx_mut().system_order;
+    order.dirty = false;
+    match keys {
+        Ok(keys) => order.keys = keys,
+        Err(err) => {
+            log_error(&err.to_string());
+            return;
+        }
+    }
+
+    // A query only sorts the tables that changed, so the pipelines are created again to sort
+    // all systems with the new keys. Custom pipelines are left alone.
+    let current = unsafe { sys::ecs_get_pipeline(world_ptr) };
+    let builtin = unsafe {
+        sys::ecs_lookup_path_w_sep(
+            world_ptr,
+            0,
+            c"flecs.pipeline.BuiltinPipeline".as_ptr(),
+            c".".as_ptr(),
+            core::ptr::null(),
+            false,
+        )
+    };
+    if current == builtin || current == *order.pipeline {
+        let previous = order.pipeline;
+        let mut phase = [sys::ecs_term_t::default(); 2];
+        phase[0].id = ECS_PHASE;
+        phase[0].src.id = ECS_CASCADE;
+        phase[0].trav = ECS_DEPENDS_ON;
+        phase[1].id = ecs_dependson(ECS_ON_START);
+        phase[1].trav = ECS_DEPENDS_ON;
+        phase[1].oper = OperKind::Not as i16;
+        let pipeline = pipeline_init(world, phase);
+        world.world_ctx_mut().system_order.pipeline = Entity(pipeline);
+        unsafe {
+            sys::ecs_set_pipeline(world_ptr, pipeline);
+            if *previous != 0 {
+                sys::ecs_delete(world_ptr, *previous);
+            }
+        }
+    }
+
+    if let Some(fixed) = world.world_ctx().fixed_timestep {
+        let pipeline = super::fixed_pipeline_init(world);
+        if let Some(state) = world.world_ctx_mut().fixed_timestep.as_mut() {
+            state.pipeline = pipeline;
+        }
+        unsafe { sys::ecs_delete(world_ptr, *fixed.pipeline) };
+    }
+}
+
+/// Sorts the systems before `pipeline` runs or its schedule is computed, if it is the default
+/// pipeline.
+///
+/// # Returns
+///
+/// The pipeline to use instead of `pipeline`, which changes when the default pipeline was
+/// replaced.
+pub(crate) fn apply_to_pipeline(world: &World, pipeline: Entity) -> Entity {
+    let current = unsafe { sys::ecs_get_pipeline(world.world_ptr()) };
+    if *pipeline != 0 && *pipeline != current {
+        return pipeline;
+    }
+    apply(world);
+    if *pipeline == 0 {
+        pipeline
+    } else {
+        Entity(unsafe { sys::ecs_get_pipeline(world.world_ptr()) })
+    }
+}
+
+/// Orders systems by their sort key, which is their id unless their order was changed.
+unsafe extern "C-unwind" fn compare_system(
+    e1: sys::ecs_entity_t,
+    ptr1: *const core::ffi::c_void,
+    e2: sys::ecs_entity_t,
+    _ptr2: *const core::ffi::c_void,
+) -> core::ffi::c_int {
+    // the systems are in the same world, which has the keys in its binding context
+    let poly = unsafe { &*(ptr1 as *const sys::EcsPoly) };
+    let system = poly.poly as *const sys::ecs_system_t;
+    let world = unsafe { (*(*system).query).real_world };
+    let ctx = unsafe { sys::ecs_get_binding_ctx(world) } as *const WorldCtx;
+    let (k1, k2) = match unsafe { ctx.as_ref() } {
+        Some(ctx) => {
+            let keys = &ctx.system_order.keys;
+            (
+                keys.get(&e1).copied().unwrap_or(e1),
+                keys.get(&e2).copied().unwrap_or(e2),
+            )
+        }
+        None => (e1, e2),
+    };
+    (k1 > k2) as core::ffi::c_int - (k1 < k2) as core::ffi::c_int
+}
+
+/// Creates a pipeline with the enabled systems that match `phase`, ordered like the systems
+/// of the default pipeline, with the order of the systems that have ordering constraints.
+///
+/// The second term of `phase` may be empty.
+pub(crate) fn pipeline_init(world: &World, phase: [sys::ecs_term_t; 2]) -> sys::ecs_entity_t {
+    let mut desc = sys::ecs_pipeline_desc_t::default();
+    let system_poly = ecs_pair(ECS_POLY, ECS_SYSTEM);
+    let mut terms = alloc::vec![sys::ecs_term_t {
+        id: ECS_SYSTEM,
+        ..Default::default()
+    }];
+    terms.extend(phase.into_iter().filter(|term| term.id != 0));
+    for trav in [ECS_DEPENDS_ON, ECS_CHILD_OF] {
+        let mut term = sys::ecs_term_t {
+            id: ECS_DISABLED,
+            trav,
+            oper: OperKind::Not as i16,
+            ..Default::default()
+        };
+        term.src.id = ECS_UP;
+        terms.push(term);
+    }
+    // the systems are sorted by their poly component, from which the comparison gets the world
+    terms.push(sys::ecs_term_t {
+        id: system_poly,
+        inout: InOutKind::In as i16,
+        ..Default::default()
+    });
+    desc.query.terms[..terms.len()].copy_from_slice(&terms);
+    desc.query.order_by = system_poly;
+    desc.query.order_by_callback = Some(compare_system);
+    unsafe { sys::ecs_pipeline_init(world.ptr_mut(), &desc) }
+}
@@ -0,0 +1,350 @@
+//! Profiler that records when systems run, which can be exported as a Chrome trace.
+//!
+//! The profiler records the performance traces that flecs emits when the `flecs_perf_trace`
+//! feature is enabled. Flecs traces each system run with the path of the system, and each
+//! merge of commands at a sync point with `flecs.commands.merge`. Each thread records its
+//! spans in its own ring buffer, so worker threads don't contend with each other.
+//!
+//! The trace is written in the [Trace Event Format], which can be opened in `chrome://tracing`
+//! or in [Perfetto](https://ui.perfetto.dev).
+//!
+//! [Trace Event Format]: https://docs.google.com/document/d/1CvAClvFfyA5R-PhYUmn5OOQtYMH4h6I0nSsKchNAySU
+
+extern crate std;
+
+extern crate alloc;
+use alloc::{boxed::Box, collections::VecDeque, ffi::CString, string::String, sync::Arc, vec::Vec};
+use core::ffi::{CStr, c_char};
+use core::fmt::Write;
+use core::sync::atomic::{AtomicBool, AtomicU32, AtomicUsize, Ordering};
+use std::sync::{LazyLock, Mutex, OnceLock, PoisonError};
+use std::time::Instant;
+
+use flecs_ecs_derive::extern_abi;
+use hashbrown::HashMap;
+
+use crate::core::*;
+use crate::sys;
+
+/// Name of the performance trace of a merge of commands at a sync point.
+const MERGE_TRACE: &str = "flecs.commands.merge";
+
+/// Performance traces of operations on single entities and tables, which are too frequent to
+/// record.
+const SKIPPED_TRACES: [&str; 8] = [
+    "flecs.commit",
+    "flecs.emit",
+    "flecs.delete",
+    "flecs.instantiate",
+    "flecs.table.create",
+    "flecs.table.free",
+    "flecs.query.rematch",
+    "flecs.component_monitor.eval",
+];
+
+/// Whether the profiler is recording.
+static RECORDING: AtomicBool = AtomicBool::new(false);
+
+/// Time from which the timestamps of spans are measured.
+static EPOCH: LazyLock<Instant> = LazyLock::new(Instant::now);
+
+/// The handlers that were installed before the handlers of the profiler, which the profiler
+/// calls after recording a trace.
+static NEXT_HANDLERS: OnceLock<(sys::ecs_os_api_perf_trace_t, sys::ecs_os_api_perf_trace_t)> =
+    OnceLock::new();
+
+/// The recordings of the threads that ran a system while the profiler was recording. The
+/// recordings of threads that exited are kept until the profiler starts recording again.
+static THREADS: Mutex<Vec<Arc<Mutex<ThreadRecording>>>> = Mutex::new(Vec::new());
+
+/// Id of the next thread that records spans, which isn't reused when a thread exits.
+static NEXT_THREAD: AtomicU32 = AtomicU32::new(1);
+
+/// Number of spans that each thread keeps, set by [`World::start_profiler()`].
+static CAPACITY: AtomicUsize = AtomicUsize::new(0);
+
+std::thread_local! {
+    static RECORDING_OF_THREAD: Arc<Mutex<ThreadRecording>> = ThreadRecording::register();
+}
+
+/// A span of time in which a performance trace was active.
+#[derive(Clone)]
+struct Span {
+    name: Arc<str>,
+    /// Nanoseconds from the epoch.
+    start: u64,
+    /// Nanoseconds.
+    duration: u64,
+}
+
+/// The spans that were recorded on a thread.
+struct ThreadRecording {
+    thread: u32,
+    thread_name: Option<String>,
+    /// Ring buffer of finished spans, oldest first.
+    spans: VecDeque<Span>,
+    /// Traces that were pushed but not popped yet, innermost last. Skipped traces are `None`.
+    active: Vec<Option<(Arc<str>, u64)>>,
+    /// Names of traces, or `None` for skipped traces.
+    names: HashMap<Box<str>, Option<Arc<str>>>,
+}
+
+impl ThreadRecording {
+    fn register() -> Arc<Mutex<Self>> {
+        let mut threads = THREADS
+            .lock()
+            .expect("profiler thread list lock should not be poisoned");
+        let recording = Arc::new(Mutex::new(ThreadRecording {
+            thread: NEXT_THREAD.fetch_add(1, Ordering::Relaxed),
+            thread_name: std::thread::current().name().map(String::from),
+            spans: VecDeque::new(),
+            active: Vec::new(),
+            names: HashMap::new(),
+        }));
+        threads.push(recording.clone());
+        recording
+    }
+
+    fn clear(&mut self) {
+        self.spans.clear();
+        self.active.clear();
+    }
+
+    fn push(&mut self, name: &str, now: u64) {
+        let name = match self.names.get(name) {
+            Some(name) => name.clone(),
+            None => {
+                let interned = (!SKIPPED_TRACES.contains(&name)).then(|| Arc::<str>::from(name));
+                self.names.insert(name.into(), interned.clone());
+                interned
+            }
+        };
+        self.active.push(name.map(|name| (name, now)));
+    }
+
+    fn pop(&mut self, now: u64, capacity: usize) {
+        let Some(Some((name, start))) = self.active.pop() else {
+            return;
+        };
+        if capacity == 0 {
+            return;
+        }
+        while self.spans.len() >= capacity {
+            self.spans.pop_front();
+        }
+        self.spans.push_back(Span {
+            name,
+            start,
+            duration: now.saturating_sub(start),
+        });
+    }
+}
+
+fn now() -> u64 {
+    EPOCH.elapsed().as_nanos() as u64
+}
+
+#[extern_abi]
+fn perf_trace_push(file: *const c_char, line: usize, name: *const c_char) {
+    if RECORDING.load(Ordering::Relaxed) && !name.is_null() {
+        let name = unsafe { CStr::from_ptr(name) }.to_str().unwrap_or("");
+        let now = now();
+        let _ = RECORDING_OF_THREAD.try_with(|recording| {
+            if let Ok(mut recording) = recording.lock() {
+                recording.push(name, now);
+            }
+        });
+    }
+
+    if let Some((Some(next), _)) = NEXT_HANDLERS.get() {
+        unsafe { next(file, line, name) };
+    }
+}
+
+#[extern_abi]
+fn perf_trace_pop(file: *const c_char, line: usize, name: *const c_char) {
+    if let Some((_, Some(next))) = NEXT_HANDLERS.get() {
+        unsafe { next(file, line, name) };
+    }
+
+    if RECORDING.load(Ordering::Relaxed) {
+        let now = now();
+        let capacity = CAPACITY.load(Ordering::Relaxed);
+        let _ = RECORDING_OF_THREAD.try_with(|recording| {
+            if let Ok(mut recording) = recording.lock() {
+                recording.pop(now, capacity);
+            }
+        });
+    }
+}
+
+/// Installs the performance trace handlers of the profiler in the OS API, which forward to the
+/// handlers that were installed before.
+pub(crate) fn install(api: &mut sys::ecs_os_api_t) {
+    let _ = NEXT_HANDLERS.set((api.perf_trace_push_, api.perf_trace_pop_));
+    api.perf_trace_push_ = Some(perf_trace_push);
+    api.perf_trace_pop_ = Some(perf_trace_pop);
+}
+
+/// Starts recording, and drops the spans that were recorded before.
+pub(crate) fn start(capacity: usize) {
+    LazyLock::force(&EPOCH);
+    CAPACITY.store(capacity, Ordering::Relaxed);
+    let mut threads = THREADS.lock().unwrap_or_else(PoisonError::into_inner);
+    // the thread local of a thread that exited no longer holds on to its recording
+    threads.retain(|recording| Arc::strong_count(recording) > 1);
+    for recording in threads.iter() {
+        recording
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clear();
+    }
+    drop(threads);
+    RECORDING.store(true, Ordering::Relaxed);
+}
+
+/// Stops recording. The spans that were recorded are kept.
+pub(crate) fn stop() {
+    RECORDING.store(false, Ordering::Relaxed);
+}
+
+/// Returns whether the profiler is recording.
+pub(crate) fn is_recording() -> bool {
+    RECORDING.load(Ordering::Relaxed)
+}
+
+/// Category and arguments of the events of spans with the same name.
+struct Classification {
+    category: String,
+    args: String,
+}
+
+/// Classifies the spans of a trace: system runs by the phase of the system, merges as sync
+/// points, and other traces as flecs operations.
+fn classify(world: &World, name: &str) -> Classification {
+    if name == MERGE_TRACE {
+        return Classification {
+            category: "sync".into(),
+            args: String::new(),
+        };
+    }
+
+    let system = CString::new(name).ok().and_then(|path| {
+        let entity = unsafe {
+            sys::ecs_lookup_path_w_sep(
+                world.world_ptr(),
+                0,
+                path.as_ptr(),
+                c".".as_ptr(),
+                core::ptr::null(),
+                false,
+            )
+        };
+        (entity != 0)
+            .then(|| world.entity_from_id(entity))
+            .filter(|entity| entity.has(flecs::system::System))
+    });
+
+    let Some(system) = system else {
+        return Classification {
+            category: "flecs".into(),
+            args: String::new(),
+        };
+    };
+
+    let phase = system.target(flecs::DependsOn, 0);
+    let phase_name = phase.map_or_else(|| String::from("system"), EntityView::name);
+    let mut args = String::new();
+    let _ = write!(args, "\"entity\":{}", *system.id());
+    if let Some(phase) = phase {
+        args.push_str(",\"phase\":");
+        write_json_string(&mut args, &phase.path().unwrap_or_default());
+    }
+    Classification {
+        category: phase_name,
+        args,
+    }
+}
+
+fn write_json_string(out: &mut String, value: &str) {
+    out.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => {
+                let _ = write!(out, "\\u{:04x}", c as u32);
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
+/// Writes the recorded spans as a Chrome trace.
+pub(crate) fn chrome_trace(world: &World) -> String {
+    let threads = THREADS
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .iter()
+        .map(|recording| {
+            let recording = recording.lock().unwrap_or_else(PoisonError::into_inner);
+            (
+                recording.thread,
+                recording.thread_name.clone(),
+                recording.spans.iter().cloned().collect::<Vec<_>>(),
+            )
+        })
+        .collect::<Vec<_>>();
+
+    let mut classifications: HashMap<Arc<str>, Classification> = HashMap::new();
+    let mut out = String::from("{\"traceEvents\":[");
+    let mut first = true;
+    let mut separator = |out: &mut String| {
+        if !core::mem::take(&mut first) {
+            out.push(',');
+        }
+    };
+
+    for (thread, thread_name, spans) in &threads {
+        if spans.is_empty() {
+            continue;
+        }
+
+        separator(&mut out);
+        let _ = write!(
+            out,
+            "{{\"name\":\"thread_name\",\"ph\":\"M\",\"pid\":1,\"tid\":{thread},\"args\":{{\"name\":"
+        );
+        match thread_name {
+            Some(name) => write_json_string(&mut out, name),
+            None => write_json_string(&mut out, &alloc::format!("thread {thread}")),
+        }
+        out.push_str("}}");
+
+        for span in spans {
+            let class = classifications
+                .entry(span.name.clone())
+                .or_insert_with(|| classify(world, &span.name));
+
+            separator(&mut out);
+            out.push_str("{\"name\":");
+            write_json_string(&mut out, &span.name);
+            out.push_str(",\"cat\":");
+            write_json_string(&mut out, &class.category);
+            let _ = write!(
+                out,
+                ",\"ph\":\"X\",\"ts\":{:.3},\"dur\":{:.3},\"pid\":1,\"tid\":{thread},\"args\":{{{}}}}}",
+                span.start as f64 / 1000.0,
+                span.duration as f64 / 1000.0,
+                class.args
+            );
+        }
+    }
+
+    out.push_str("],\"displayTimeUnit\":\"ms\"}");
+    out
+}
@@ -0,0 +1,603 @@
+//! The schedule of a pipeline: the order in which its systems run, and the sync points at which
+//! the pipeline merges the commands that systems enqueued.
+//!
+//! The schedule is the one that flecs builds for a pipeline when it runs. A pipeline inserts a
+//! merge before a system that reads a component that an earlier system may have written to the
+//! command queue, and when the threading or staging mode of the systems changes. The reason of
+//! each merge is found by checking the systems in the same way.
+
+use core::fmt::Write;
+
+use crate::core::*;
+use crate::sys;
+
+extern crate alloc;
+use alloc::{
+    collections::{BTreeMap, BTreeSet},
+    format,
+    string::String,
+    vec::Vec,
+};
+
+/// Why a pipeline merges commands before the systems of a [`ScheduleSegment`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SyncReason<'a> {
+    /// The first system of the segment reads the component, or matches entities with it, after
+    /// an earlier system may have written it to the command queue.
+    Component(IdView<'a>),
+    /// The first system of the segment is multi threaded while the systems before it aren't, or
+    /// the other way around.
+    MultiThreaded,
+    /// The first system of the segment runs in immediate mode while the systems before it don't,
+    /// or the other way around.
+    Immediate,
+    /// The systems before the segment run in immediate mode, which merges after each system.
+    AfterImmediate,
+}
+
+/// A system in a [`PipelineSchedule`].
+#[derive(Debug, Clone)]
+pub struct ScheduledSystem<'a> {
+    system: EntityView<'a>,
+    reads: Vec<IdView<'a>>,
+    writes: Vec<IdView<'a>>,
+    multi_threaded: bool,
+    immediate: bool,
+}
+
+impl<'a> ScheduledSystem<'a> {
+    /// The system entity.
+    pub fn system(&self) -> EntityView<'a> {
+        self.system
+    }
+
+    /// The components that the system reads.
+    pub fn reads(&self) -> &[IdView<'a>] {
+        &self.reads
+    }
+
+    /// The components that the system writes, including components that it adds.
+    pub fn writes(&self) -> &[IdView<'a>] {
+        &self.writes
+    }
+
+    /// Whether the system runs on multiple threads.
+    pub fn is_multi_threaded(&self) -> bool {
+        self.multi_threaded
+    }
+
+    /// Whether the system runs in immediate mode, which doesn't enqueue commands.
+    pub fn is_immediate(&self) -> bool {
+        self.immediate
+    }
+}
+
+/// Systems of a [`PipelineSchedule`] that run between two sync points.
+#[derive(Debug, Clone)]
+pub struct ScheduleSegment<'a> {
+    systems: Vec<ScheduledSystem<'a>>,
+    multi_threaded: bool,
+    immediate: bool,
+    sync_reason: Option<SyncReason<'a>>,
+}
+
+impl<'a> ScheduleSegment<'a> {
+    /// The systems of the segment, in the order in which they run.
+    pub fn systems(&self) -> &[ScheduledSystem<'a>] {
+        &self.systems
+    }
+
+    /// Whether the systems of the segment run on multiple threads.
+    pub fn is_multi_threaded(&self) -> bool {
+        self.multi_threaded
+    }
+
+    /// Whether the systems of the segment run in immediate mode.
+    pub fn is_immediate(&self) -> bool {
+        self.immediate
+    }
+
+    /// Why the pipeline merges commands before the segment, or `None` for the first segment and
+    /// when the reason isn't known.
+    pub fn sync_reason(&self) -> Option<SyncReason<'a>> {
+        self.sync_reason
+    }
+}
+
+/// The systems of a pipeline in the order in which they run, grouped by the sync points at
+/// which the pipeline merges commands.
+///
+/// This is typically obtained via [`World::pipeline_schedule()`].
+#[derive(Debug, Clone)]
+pub struct PipelineSchedule<'a> {
+    pipeline: EntityView<'a>,
+    segments: Vec<ScheduleSegment<'a>>,
+}
+
+impl<'a> PipelineSchedule<'a> {
+    /// The pipeline entity.
+    pub fn pipeline(&self) -> EntityView<'a> {
+        self.pipeline
+    }
+
+    /// The segments of the schedule. Commands are merged after each segment.
+    pub fn segments(&self) -> &[ScheduleSegment<'a>] {
+        &self.segments
+    }
+
+    /// The systems of the schedule, in the order in which they run.
+    pub fn systems(&self) -> impl Iterator<Item = &ScheduledSystem<'a>> {
+        self.segments
+            .iter()
+            .flat_map(|segment| segment.systems.iter())
+    }
+
+    /// Renders the schedule as a Graphviz graph.
+    ///
+    /// Each segment is a cluster with its systems, and the sync points between segments are
+    /// nodes that are labeled with the reason of the merge.
+    pub fn to_dot(&self) -> String {
+        let mut out = String::from("digraph pipeline {\n");
+        out.push_str("    rankdir=TB;\n");
+        out.push_str("    node [shape=box];\n");
+
+        let mut system_index = 0;
+        let mut previous = None;
+        for (index, segment) in self.segments.iter().enumerate() {
+            if let Some(reason) = segment.sync_reason {
+                let merge = format!("merge{index}");
+                let _ = writeln!(
+                    out,
+                    "    {merge} [shape=diamond, label=\"{}\"];",
+                    escape_dot(&sync_reason_label(reason))
+                );
+                if let Some(previous) = previous.replace(merge.clone()) {
+                    let _ = writeln!(out, "    {previous} -> {merge};");
+                }
+            }
+
+            let _ = writeln!(out, "    subgraph cluster_{index} {{");
+            let _ = writeln!(
+                out,
+                "        label=\"{}\";",
+                escape_dot(&segment_label(index, segment))
+            );
+            for system in &segment.systems {
+                let node = format!("system{system_index}");
+                system_index += 1;
+                let _ = writeln!(
+                    out,
+                    "        {node} [label=\"{}\"];",
+                    system_label(system, "\\n", escape_dot)
+                );
+                if let Some(previous) = previous.replace(node.clone()) {
+                    let _ = writeln!(out, "        {previous} -> {node};");
+                }
+            }
+            out.push_str("    }\n");
+        }
+
+        out.push_str("}\n");
+        out
+    }
+
+    /// Renders the schedule as a Mermaid flowchart.
+    ///
+    /// Each segment is a subgraph with its systems, and the sync points between segments are
+    /// nodes that are labeled with the reason of the merge.
+    pub fn to_mermaid(&self) -> String {
+        let mut out = String::from("flowchart TD\n");
+
+        let mut system_index = 0;
+        let mut previous = None;
+        for (index, segment) in self.segments.iter().enumerate() {
+            if let Some(reason) = segment.sync_reason {
+                let merge = format!("merge{index}");
+                let _ = writeln!(
+                    out,
+                    "    {merge}{{{{\"{}\"}}}}",
+                    escape_mermaid(&sync_reason_label(reason))
+                );
+                if let Some(previous) = previous.replace(merge.clone()) {
+                    let _ = writeln!(out, "    {previous} --> {merge}");
+                }
+            }
+
+            let _ = writeln!(
+                out,
+                "    subgraph segment{index}[\"{}\"]",
+                escape_mermaid(&segment_label(index, segment))
+            );
+            for system in &segment.systems {
+                let node = format!("system{system_index}");
+                system_index += 1;
+                let _ = writeln!(
+                    out,
+                    "        {node}[\"{}\"]",
+                    system_label(system, "<br/>", escape_mermaid)
+                );
+                if let Some(previous) = previous.replace(node.clone()) {
+                    let _ = writeln!(out, "        {previous} --> {node}");
+                }
+            }
+            out.push_str("    end\n");
+        }
+
+        out
+    }
+}
+
+fn escape_dot(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+fn escape_mermaid(text: &str) -> String {
+    text.replace('"', "#quot;")
+}
+
+fn id_label(id: IdView) -> String {
+    if id.is_pair() {
+        let name = |entity: Option<EntityView>| entity.map(EntityView::name).unwrap_or_default();
+        format!(
+            "({}, {})",
+            name(id.get_first_id()),
+            name(id.get_second_id())
+        )
+    } else {
+        id.entity_view().name()
+    }
+}
+
+fn ids_label(ids: &[IdView]) -> String {
+    ids.iter()
+        .map(|id| id_label(*id))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn system_label(system: &ScheduledSystem, line_break: &str, escape: fn(&str) -> String) -> String {
+    let mut label = escape(&system.system.name());
+    if !system.reads.is_empty() {
+        let _ = write!(
+            label,
+            "{line_break}reads: {}",
+            escape(&ids_label(&system.reads))
+        );
+    }
+    if !system.writes.is_empty() {
+        let _ = write!(
+            label,
+            "{line_break}writes: {}",
+            escape(&ids_label(&system.writes))
+        );
+    }
+    label
+}
+
+fn segment_label(index: usize, segment: &ScheduleSegment) -> String {
+    format!(
+        "segment {index}: {}, {}",
+        if segment.multi_threaded {
+            "multi threaded"
+        } else {
+            "single threaded"
+        },
+        if segment.immediate {
+            "immediate"
+        } else {
+            "staged"
+        }
+    )
+}
+
+fn sync_reason_label(reason: SyncReason) -> String {
+    match reason {
+        SyncReason::Component(id) => format!("merge: {} was written", id_label(id)),
+        SyncReason::MultiThreaded => String::from("merge: threading changed"),
+        SyncReason::Immediate => String::from("merge: staging changed"),
+        SyncReason::AfterImmediate => String::from("merge: after immediate system"),
+    }
+}
+
+/// The components that were written to the command queue since the last merge.
+#[derive(Default)]
+struct WriteState {
+    ids: BTreeSet<u64>,
+    wildcard_ids: BTreeSet<u64>,
+    /// Whether any component could have been written.
+    write_barrier: bool,
+}
+
+impl WriteState {
+    /// Returns whether `id` could have been written to the command queue.
+    fn is_written(&self, id: u64) -> bool {
+        if self.write_barrier {
+            return true;
+        }
+
+        // a wildcard id is a read barrier
+        if id == ECS_WILDCARD && (!self.ids.is_empty() || !self.wildcard_ids.is_empty()) {
+            return true;
+        }
+
+        let written = if unsafe { sys::ecs_id_is_wildcard(id) } {
+            self.ids
+                .iter()
+                .any(|&written| unsafe { sys::ecs_id_match(written, id) })
+        } else {
+            self.ids.contains(&id)
+        };
+
+        written
+            || self
+                .wildcard_ids
+                .iter()
+                .any(|&written| unsafe { sys::ecs_id_match(id, written) })
+    }
+
+    fn set_written(&mut self, id: u64) {
+        if id == ECS_WILDCARD {
+            self.write_barrier = true;
+        } else if unsafe { sys::ecs_id_is_wildcard(id) } {
+            self.wildcard_ids.insert(id);
+        } else {
+            self.ids.insert(id);
+        }
+    }
+
+    fn reset(&mut self) {
+        *self = Self::default();
+    }
+}
+
+/// Returns the access of a term, where a default access is resolved like flecs does, or `None`
+/// if the term isn't a read or write annotation.
+fn term_access(term: &sys::ecs_term_t) -> Option<InOutKind> {
+    let from_any = unsafe { sys::ecs_term_match_0(term) };
+    let from_this = unsafe { sys::ecs_term_match_this(term) };
+    let is_shared = !from_any && (!from_this || term.src.id & ECS_SELF == 0);
+
+    match term.inout as u32 {
+        x if x == InOutKind::Filter as u32 => None,
+        x if x == InOutKind::None as u32 => None,
+        x if x == InOutKind::In as u32 => Some(InOutKind::In),
+        x if x == InOutKind::Out as u32 => Some(InOutKind::Out),
+        x if x == InOutKind::InOut as u32 => Some(InOutKind::InOut),
+        // a term without source and access is a component id that's passed to the system
+        _ if from_any => None,
+        _ if is_shared => Some(InOutKind::In),
+        _ => Some(InOutKind::InOut),
+    }
+}
+
+/// Checks whether a term requires a merge before the system runs, and records the components
+/// that the term writes to the command queue. Ported from `flecs_pipeline_check_term`.
+fn check_term(term: &sys::ecs_term_t, is_active: bool, write_state: &mut WriteState) -> bool {
+    if term.inout as u32 == InOutKind::Filter as u32 {
+        return false;
+    }
+
+    let from_this = unsafe { sys::ecs_term_match_this(term) };
+    let written = write_state.is_written(term.id);
+
+    // a staged write could have happened for an id that's matched on the main storage
+    if from_this && written {
+        return true;
+    }
+
+    let Some(access) = term_access(term) else {
+        return false;
+    };
+
+    // a Not term with Out access adds a component that the entity doesn't have yet
+    let from_any = unsafe { sys::ecs_term_match_0(term) }
+        || (term.oper as u32 == OperKind::Not as u32 && access == InOutKind::Out);
+    if !from_any {
+        return false;
+    }
+
+    if is_active && matches!(access, InOutKind::Out | InOutKind::InOut) {
+        write_state.set_written(term.id);
+    }
+
+    // a get or ensure reads the component from the main storage
+    matches!(access, InOutKind::In | InOutKind::InOut) && written
+}
+
+fn query_terms(query: &sys::ecs_query_t) -> &[sys::ecs_term_t] {
+    if query.terms.is_null() {
+        return &[];
+    }
+    unsafe { core::slice::from_raw_parts(query.terms, query.term_count as usize) }
+}
+
+/// Checks the terms of a system, and returns the first component that requires a merge before
+/// the system runs. Terms matched on `$this` are checked first, so that a term that writes to
+/// the command queue doesn't cause a merge for the terms before it.
+fn check_terms(
+    query: &sys::ecs_query_t,
+    is_active: bool,
+    write_state: &mut WriteState,
+) -> Option<u64> {
+    let terms = query_terms(query);
+    let (this_terms, other_terms): (Vec<_>, Vec<_>) = terms
+        .iter()
+        .partition(|term| unsafe { sys::ecs_term_match_this(*term) });
+
+    let mut merge = None;
+    for term in this_terms.into_iter().chain(other_terms) {
+        if check_term(term, is_active, write_state) && merge.is_none() {
+            merge = Some(term.id);
+        }
+    }
+    merge
+}
+
+/// Returns the components that a system reads and writes.
+fn system_access<'a>(
+    world: &'a World,
+    query: &sys::ecs_query_t,
+) -> (Vec<IdView<'a>>, Vec<IdView<'a>>) {
+    let mut reads = Vec::new();
+    let mut writes = Vec::new();
+    for term in query_terms(query) {
+        if term.id == 0 {
+            continue;
+        }
+        let Some(access) = term_access(term) else {
+            continue;
+        };
+        let id = world.id_view_from(term.id);
+        if matches!(access, InOutKind::In | InOutKind::InOut) && !reads.contains(&id) {
+            reads.push(id);
+        }
+        if matches!(access, InOutKind::Out | InOutKind::InOut) && !writes.contains(&id) {
+            writes.push(id);
+        }
+    }
+    (reads, writes)
+}
+
+/// Finds why flecs merges before the systems of a pipeline, by checking the systems like
+/// `flecs_pipeline_build` does. Returns the reason of each merge by the first system that runs
+/// after it.
+fn sync_reasons<'a>(
+    world: &'a World,
+    query: *const sys::ecs_query_t,
+) -> BTreeMap<u64, SyncReason<'a>> {
+    let world_ptr = world.world_ptr();
+    let mut reasons = BTreeMap::new();
+    let mut write_state = WriteState::default();
+    let mut multi_threaded = false;
+    let mut immediate = false;
+    let mut first = true;
+    // the reason of the last merge, until the first system after it runs
+    let mut pending = None;
+
+    let mut it = unsafe { sys::ecs_query_iter(world_ptr, query) };
+    while unsafe { sys::ecs_query_next(&mut it) } {
+        let is_active =
+            unsafe { sys::ecs_table_get_type_index(world_ptr, it.table, ECS_EMPTY) } == -1;
+        let entities = unsafe { core::slice::from_raw_parts(it.entities, it.count as usize) };
+
+        for &entity in entities {
+            let Some(system) = (unsafe { sys::ecs_system_get(world_ptr, entity).as_ref() }) else {
+                continue;
+            };
+            let Some(system_query) = (unsafe { system.query.as_ref() }) else {
+                continue;
+            };
+
+            let mut reason = check_terms(system_query, is_active, &mut write_state)
+                .map(|id| SyncReason::Component(world.id_view_from(id)));
+
+            if is_active {
+                if first {
+                    multi_threaded = system.multi_threaded;
+                    immediate = system.immediate;
+                    first = false;
+                }
+                if system.multi_threaded != multi_threaded {
+                    reason.get_or_insert(SyncReason::MultiThreaded);
+                    multi_threaded = system.multi_threaded;
+                }
+                if system.immediate != immediate {
+                    reason.get_or_insert(SyncReason::Immediate);
+                    immediate = system.immediate;
+                }
+            }
+
+            if immediate {
+                reason.get_or_insert(SyncReason::AfterImmediate);
+            }
+
+            if reason.is_some() {
+                write_state.reset();
+                // an inactive system can insert a merge without running, in which case the
+                // merge is shared with the next system that does
+                pending = reason;
+                if is_active {
+                    check_terms(system_query, true, &mut write_state);
+                }
+            }
+
+            if is_active && let Some(reason) = pending.take() {
+                reasons.insert(entity, reason);
+            }
+        }
+    }
+
+    reasons
+}
+
+/// Returns the schedule that flecs built for a pipeline, after building it if the systems of
+/// the pipeline changed.
+pub(crate) fn pipeline_schedule(world: &World, pipeline: Entity) -> PipelineSchedule<'_> {
+    let world_ptr = world.world_ptr_mut();
+    let query = unsafe { sys::ecs_query_get(world_ptr, *pipeline) };
+    ecs_assert!(
+        !query.is_null(),
+        FlecsErrorCode::InvalidParameter,
+        "entity is not a pipeline"
+    );
+
+    let op_count = unsafe { sys::ecs_rust_pipeline_build(world_ptr, *pipeline) };
+    let reasons = sync_reasons(world, query);
+
+    let mut segments: Vec<ScheduleSegment> = Vec::new();
+    for index in 0..op_count {
+        let (mut offset, mut count) = (0, 0);
+        let (mut multi_threaded, mut immediate) = (false, false);
+        let found = unsafe {
+            sys::ecs_rust_pipeline_get_op(
+                world_ptr,
+                *pipeline,
+                index,
+                &mut offset,
+                &mut count,
+                &mut multi_threaded,
+                &mut immediate,
+            )
+        };
+        if !found {
+            break;
+        }
+
+        let systems: Vec<ScheduledSystem> = (offset..offset + count)
+            .filter_map(|index| {
+                let entity =
+                    unsafe { sys::ecs_rust_pipeline_get_system(world_ptr, *pipeline, index) };
+                let system = unsafe { sys::ecs_system_get(world_ptr, entity).as_ref() }?;
+                let system_query = unsafe { system.query.as_ref() }?;
+                let (reads, writes) = system_access(world, system_query);
+                Some(ScheduledSystem {
+                    system: world.entity_from_id(entity),
+                    reads,
+                    writes,
+                    multi_threaded: system.multi_threaded,
+                    immediate: system.immediate,
+                })
+            })
+            .collect();
+
+        let sync_reason = systems
+            .first()
+            .and_then(|system| reasons.get(&*system.system.id()).copied());
+        segments.push(ScheduleSegment {
+            systems,
+            multi_threaded,
+            immediate,
+            sync_reason,
+        });
+    }
+
+    segments.retain(|segment| !segment.systems.is_empty());
+    if let Some(first) = segments.first_mut() {
+        first.sync_reason = None;
+    }
+
+    PipelineSchedule {
+        pipeline: world.entity_from_id(pipeline),
+        segments,
+    }
+}
@@ -0,0 +1,286 @@
+use core::fmt::{Display, Formatter};
+use core::ptr::NonNull;
+
+use flecs_ecs::core::*;
+use flecs_ecs::sys;
+
+extern crate alloc;
+use alloc::{borrow::ToOwned, string::String};
+
+use super::ScriptVars;
+use crate::core::capture_log;
+
+/// The stage of an expression in which an [`ExprError`] occurred.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ExprErrorKind {
+    /// The expression could not be parsed, or refers to unknown identifiers or variables.
+    Parse,
+    /// The expression failed while evaluating, or its result could not be converted.
+    Eval,
+}
+
+/// Error returned when parsing or evaluating an [`Expr`] fails.
+///
+/// The diagnostic that flecs would otherwise log is captured, and split into a message and
+/// the location in the expression.
+///
+/// # Example
+///
+/// ```
+/// use flecs_ecs::prelude::*;
+/// use flecs_ecs::addons::script::*;
+///
+/// let world = World::new();
+/// let vars = ScriptVars::new(&world);
+///
+/// let err = world.eval_expr::<f32>("10 + $speed", &vars).unwrap_err();
+/// assert_eq!(err.kind(), ExprErrorKind::Parse);
+/// assert_eq!(err.line(), Some(1));
+/// assert_eq!(err.column(), Some(6));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExprError {
+    kind: ExprErrorKind,
+    message: String,
+    line: Option<u32>,
+    column: Option<u32>,
+}
+
+impl ExprError {
+    /// Creates an error from a diagnostic captured from the flecs log.
+    fn from_log(kind: ExprErrorKind, log: Option<&str>) -> Self {
+        let diagnostic = Diagnostic::parse(log);
+
+        Self {
+            kind,
+            message: diagnostic
+                .message_or("failed to evaluate expression")
+                .to_owned(),
+            line: diagnostic.line,
+            column: diagnostic.column(),
+        }
+    }
+
+    /// Whether parsing or evaluating the expression failed.
+    pub fn kind(&self) -> ExprErrorKind {
+        self.kind
+    }
+
+    /// Human readable description of what went wrong.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// The line in the expression at which the error occurred, starting at 1, if it is known.
+    pub fn line(&self) -> Option<u32> {
+        self.line
+    }
+
+    /// The column in the line at which the error occurred, starting at 1 and counted in
+    /// characters, if it is known.
+    pub fn column(&self) -> Option<u32> {
+        self.column
+    }
+}
+
+impl Display for ExprError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match (self.line, self.column) {
+            (Some(line), Some(column)) => write!(f, "{line}:{column}: {}", self.message),
+            (Some(line), None) => write!(f, "{line}: {}", self.message),
+            _ => write!(f, "{}", self.message),
+        }
+    }
+}
+
+impl core::error::Error for ExprError {}
+
+/// A parsed expression that can be evaluated multiple times.
+///
+/// Expressions can use the variables of a [`ScriptVars`] object as `$name`, and can read
+/// component values of entities, like `player[Health].value`.
+///
+/// # Example
+///
+/// ```
+/// use flecs_ecs::prelude::*;
+/// use flecs_ecs::addons::script::*;
+///
+/// let world = World::new();
+///
+/// let mut vars = ScriptVars::new(&world);
+/// vars.define("level", 1i32);
+///
+/// let expr = Expr::parse(&world, "100 + $level * 10", &vars).unwrap();
+/// assert_eq!(expr.eval::<i32>(&vars).unwrap(), 110);
+///
+/// *vars.get_mut::<i32>("level").unwrap() = 5;
+/// assert_eq!(expr.eval::<i32>(&vars).unwrap(), 150);
+/// ```
+pub struct Expr<'a> {
+    script: NonNull<sys::ecs_script_t>,
+    world: WorldRef<'a>,
+}
+
+impl Drop for Expr<'_> {
+    fn drop(&mut self) {
+        unsafe { sys::ecs_script_free(self.script.as_ptr()) }
+    }
+}
+
+impl<'a> Expr<'a> {
+    /// Parse an expression.
+    ///
+    /// # Arguments
+    ///
+    /// * `expr` - The expression.
+    /// * `vars` - The variables that the expression can use. The variables that are passed to
+    ///   [`Expr::eval()`] must have the same names and types.
+    ///
+    /// # Returns
+    ///
+    /// The parsed expression, or an error with the location of the problem.
+    ///
+    /// # See also
+    ///
+    /// * [`World::eval_expr()`]
+    /// * C API: `ecs_expr_parse`
+    pub fn parse(
+        world: impl WorldProvider<'a>,
+        expr: &str,
+        vars: &ScriptVars,
+    ) -> Result<Self, ExprError> {
+        Self::parse_id(world, expr, vars, 0)
+    }
+
+    /// Parse an expression that evaluates to a value of type `T`.
+    ///
+    /// Unlike [`Expr::parse()`], this allows the expression to be an initializer without a
+    /// type, such as `{x: 10, y: $y}` for a struct.
+    ///
+    /// # Type Parameters
+    ///
+    /// * `T` - The type of the result.
+    ///
+    /// # Arguments
+    ///
+    /// * `expr` - The expression.
+    /// * `vars` - The variables that the expression can use.
+    ///
+    /// # Returns
+    ///
+    /// The parsed expression, or an error with the location of the problem.
+    pub fn parse_as<T: ComponentId + DataComponent>(
+        world: impl WorldProvider<'a>,
+        expr: &str,
+        vars: &ScriptVars,
+    ) -> Result<Self, ExprError> {
+        let world = world.world();
+        let id = *world.component_id::<T>();
+        Self::parse_id(world, expr, vars, id)
+    }
+
+    fn parse_id(
+        world: impl WorldProvider<'a>,
+        expr: &str,
+        vars: &ScriptVars,
+        type_: sys::ecs_entity_t,
+    ) -> Result<Self, ExprError> {
+        let world = world.world();
+        let expr_c = compact_str::format_compact!("{}\0", expr);
+        let desc = sys::ecs_expr_eval_desc_t {
+            type_,
+            ..Self::desc(vars)
+        };
+
+        // unlike `ecs_expr_parse`, this keeps the expression in the script, so that flecs
+        // reports where in the expression parsing or evaluating failed
+        let (script, log) = capture_log(|| unsafe {
+            sys::ecs_rust_expr_parse(world.world_ptr_mut(), expr_c.as_ptr() as *const _, &desc)
+        });
+
+        match NonNull::new(script) {
+            Some(script) => Ok(Expr { script, world }),
+            None => Err(ExprError::from_log(ExprErrorKind::Parse, log.as_deref())),
+        }
+    }
+
+    /// Evaluate the expression.
+    ///
+    /// # Type Parameters
+    ///
+    /// * `T` - The type of the result. The result of the expression is converted to this type.
+    ///
+    /// # Arguments
+    ///
+    /// * `vars` - The variables that the expression can use.
+    ///
+    /// # Returns
+    ///
+    /// The result of the expression, or an error if it could not be evaluated.
+    ///
+    /// # See also
+    ///
+    /// * [`Expr::eval_into()`]
+    /// * C API: `ecs_expr_eval`
+    pub fn eval<T: ComponentId + DataComponent + Default>(
+        &self,
+        vars: &ScriptVars,
+    ) -> Result<T, ExprError> {
+        let mut value = T::default();
+        self.eval_into(&mut value, vars)?;
+        Ok(value)
+    }
+
+    /// Evaluate the expression, and assign the result to an existing value.
+    ///
+    /// # Type Parameters
+    ///
+    /// * `T` - The type of the result. The result of the expression is converted to this type.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The value to assign the result to.
+    /// * `vars` - The variables that the expression can use.
+    ///
+    /// # See also
+    ///
+    /// * [`Expr::eval()`]
+    /// * C API: `ecs_expr_eval`
+    pub fn eval_into<T: ComponentId + DataComponent>(
+        &self,
+        value: &mut T,
+        vars: &ScriptVars,
+    ) -> Result<(), ExprError> {
+        let mut value = sys::ecs_value_t {
+            type_: *self.world.component_id::<T>(),
+            ptr: value as *mut T as *mut core::ffi::c_void,
+        };
+        let desc = Self::desc(vars);
+
+        let (result, log) =
+            capture_log(|| unsafe { sys::ecs_expr_eval(self.script.as_ptr(), &mut value, &desc) });
+
+        if result == 0 {
+            Ok(())
+        } else {
+            Err(ExprError::from_log(ExprErrorKind::Eval, log.as_deref()))
+        }
+    }
+
+    fn desc(vars: &ScriptVars) -> sys::ecs_expr_eval_desc_t {
+        sys::ecs_expr_eval_desc_t {
+            name: core::ptr::null(),
+            expr: core::ptr::null(),
+            vars: vars.as_ptr(),
+            type_: 0,
+            lookup_action: None,
+            lookup_ctx: core::ptr::null_mut(),
+            disable_folding: false,
+            disable_dynamic_variable_binding: false,
+            allow_unresolved_identifiers: false,
+            runtime: core::ptr::null_mut(),
+            script_visitor: core::ptr::null_mut(),
+        }
+    }
+}
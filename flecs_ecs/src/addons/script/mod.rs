@@ -1,10 +1,18 @@
+mod expr;
 mod script_builder;
 mod script_entity_view;
+mod script_error;
+mod script_vars;
+mod script_watcher;
 mod unmanaged_script;
 mod world;
 
+pub use expr::*;
 pub use script_builder::*;
 pub use script_entity_view::*;
+pub use script_error::*;
+pub use script_vars::*;
+pub use script_watcher::*;
 pub use unmanaged_script::*;
 
 #[cfg(feature = "std")]
@@ -1,7 +1,7 @@
 use flecs_ecs::core::*;
 use flecs_ecs::sys;
 
-use super::ScriptEntityView;
+use super::{ScriptEntityView, ScriptError};
 
 /// [`ScriptBuilder`] is a builder pattern for creating scripts.
 pub struct ScriptBuilder<'a> {
@@ -75,20 +75,28 @@ impl<'a> ScriptBuilder<'a> {
     ///
     /// # Returns
     ///
-    /// Returns the script entity handle of the loaded script.
+    /// Returns the script entity handle of the loaded script, or a [`ScriptError`] if the file
+    /// could not be loaded or the script failed.
+    ///
+    /// If the script entity was created by the builder, it is deleted when the script fails.
     ///
     /// # See also
     ///
     /// * C API: `ecs_script_init`
-    pub fn build_from_file(&mut self, filename: &str) -> ScriptEntityView<'a> {
-        let filename = compact_str::format_compact!("{}\0", filename);
-        let world = self.world.world_ptr_mut();
-
-        self.script.filename = filename.as_ptr() as *const _;
+    pub fn build_from_file(&mut self, filename: &str) -> Result<ScriptEntityView<'a>, ScriptError> {
+        let filename_c = compact_str::format_compact!("{}\0", filename);
+        self.script.filename = filename_c.as_ptr() as *const _;
 
-        let result = unsafe { sys::ecs_script_init(world, &self.script) };
+        let result = self.build();
+        self.script.filename = core::ptr::null();
 
-        ScriptEntityView::new_from(self.world, result)
+        if result == 0 {
+            return Err(ScriptError::from_log(
+                Some(filename),
+                Some("failed to load script file"),
+            ));
+        }
+        self.check(result)
     }
 
     /// Loads a managed script from a code string into the ECS world.
@@ -103,19 +111,37 @@ impl<'a> ScriptBuilder<'a> {
     ///
     /// # Returns
     ///
-    /// Returns the script entity handle of the loaded script.
+    /// Returns the script entity handle of the loaded script, or a [`ScriptError`] if the
+    /// script failed.
+    ///
+    /// If the script entity was created by the builder, it is deleted when the script fails.
     ///
     /// # See also
     ///
     /// * C API: `ecs_script_init`
-    pub fn build_from_code(&mut self, code: &str) -> ScriptEntityView<'a> {
+    pub fn build_from_code(&mut self, code: &str) -> Result<ScriptEntityView<'a>, ScriptError> {
         let code = compact_str::format_compact!("{}\0", code);
-        let world = self.world.world_ptr_mut();
-
         self.script.code = code.as_ptr() as *const _;
 
-        let result = unsafe { sys::ecs_script_init(world, &self.script) };
+        let result = self.build();
+        self.script.code = core::ptr::null();
+        self.check(result)
+    }
+
+    fn build(&self) -> sys::ecs_entity_t {
+        unsafe { sys::ecs_script_init(self.world.world_ptr_mut(), &self.script) }
+    }
 
-        ScriptEntityView::new_from(self.world, result)
+    fn check(&self, entity: sys::ecs_entity_t) -> Result<ScriptEntityView<'a>, ScriptError> {
+        let view = ScriptEntityView::new_from(self.world, entity);
+        match view.error() {
+            None => Ok(view),
+            Some(error) => {
+                if self.script.entity == 0 {
+                    view.destruct();
+                }
+                Err(error)
+            }
+        }
     }
 }
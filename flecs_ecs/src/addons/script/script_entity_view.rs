@@ -10,8 +10,10 @@ extern crate std;
 extern crate alloc;
 use alloc::{borrow::ToOwned, string::String};
 
+use super::ScriptError;
+
 /// [`ScriptEntityView`] is a wrapper around an entity that is associated with a script.
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Debug)]
 pub struct ScriptEntityView<'a> {
     entity: EntityView<'a>,
 }
@@ -56,25 +58,129 @@ impl<'a> ScriptEntityView<'a> {
     ///
     /// # Returns
     ///
-    /// True if success, false if failed.
+    /// Ok if success, or a [`ScriptError`] describing why the script failed.
     ///
     /// # See also
     ///
+    /// * [`ScriptEntityView::error()`]
     /// * C API: `ecs_script_update`
     pub fn update(
         &self,
         world: impl WorldProvider<'a>,
         instance: Option<impl Into<Entity>>,
         code: &str,
-    ) -> bool {
+    ) -> Result<(), ScriptError> {
+        self.check_not_template()?;
+
         let code = compact_str::format_compact!("{}\0", code);
-        unsafe {
+        let success = unsafe {
             sys::ecs_script_update(
                 world.world_ptr_mut(),
                 *self.id,
                 instance.map(|e| *e.into()).unwrap_or(0),
                 code.as_ptr() as *const _,
             ) == 0
+        };
+
+        if success {
+            Ok(())
+        } else {
+            Err(self.last_error())
+        }
+    }
+
+    /// Returns the error of the last update of the script, if it failed.
+    ///
+    /// # Returns
+    ///
+    /// The [`ScriptError`] that was stored in the [`flecs::Script`] component, or `None` if the
+    /// script was loaded successfully.
+    pub fn error(&self) -> Option<ScriptError> {
+        let (filename, error) = self
+            .entity
+            .try_get::<&flecs::Script>(|script| (script.filename, script.error))?;
+        if error.is_null() {
+            return None;
+        }
+
+        let file = if filename.is_null() {
+            self.entity.get_name()
+        } else {
+            Some(
+                unsafe { core::ffi::CStr::from_ptr(filename) }
+                    .to_string_lossy()
+                    .into_owned(),
+            )
+        };
+        let log = unsafe { core::ffi::CStr::from_ptr(error) }.to_string_lossy();
+        Some(ScriptError::from_log(file.as_deref(), Some(&log)))
+    }
+
+    fn last_error(&self) -> ScriptError {
+        self.error()
+            .unwrap_or_else(|| ScriptError::from_log(self.entity.get_name().as_deref(), None))
+    }
+
+    fn check_not_template(&self) -> Result<(), ScriptError> {
+        let is_template = self
+            .entity
+            .try_get::<&flecs::Script>(|script| !script.template_.is_null())
+            .unwrap_or(false);
+        if is_template {
+            return Err(ScriptError::from_log(
+                self.entity.get_name().as_deref(),
+                Some(
+                    "cannot update scripts for individual templates, update parent script instead",
+                ),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Update script with new code, and evaluate it with variables.
+    ///
+    /// # Arguments
+    ///
+    /// * code - The script code.
+    ///
+    /// * instance - An template instance (optional).
+    ///
+    /// * vars - The variables that are available to the script.
+    ///
+    /// # Returns
+    ///
+    /// Ok if success, or a [`ScriptError`] describing why the script failed.
+    ///
+    /// # See also
+    ///
+    /// * [`ScriptEntityView::update()`]
+    /// * [`ScriptVars`][super::ScriptVars]
+    /// * C API: `ecs_script_update`
+    pub fn update_with_vars(
+        &self,
+        world: impl WorldProvider<'a>,
+        instance: Option<impl Into<Entity>>,
+        code: &str,
+        vars: &mut super::ScriptVars,
+    ) -> Result<(), ScriptError> {
+        self.check_not_template()?;
+
+        let code = compact_str::format_compact!("{}\0", code);
+        let desc = vars.eval_desc();
+        let success = unsafe {
+            sys::ecs_rust_script_update_w_desc(
+                world.world_ptr_mut(),
+                *self.id,
+                instance.map(|e| *e.into()).unwrap_or(0),
+                code.as_ptr() as *const _,
+                &desc,
+            ) == 0
+        };
+
+        if success {
+            Ok(())
+        } else {
+            Err(self.last_error())
         }
     }
 
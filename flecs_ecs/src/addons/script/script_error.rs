@@ -0,0 +1,101 @@
+use core::ffi::c_char;
+use core::fmt::{Display, Formatter};
+
+use crate::core::{Diagnostic, take_log};
+
+extern crate alloc;
+use alloc::{borrow::ToOwned, string::String};
+
+/// Error returned when parsing or evaluating a script fails.
+///
+/// The diagnostic that flecs would otherwise log is captured, and split into the location of
+/// the error, the line of code that caused it and a message.
+///
+/// # Example
+///
+/// ```
+/// use flecs_ecs::prelude::*;
+/// use flecs_ecs::addons::script::*;
+///
+/// let world = World::new();
+///
+/// let err = world.run_code("level.flecs", "e {}\nf { ) }").unwrap_err();
+/// assert_eq!(err.file(), Some("level.flecs"));
+/// assert_eq!((err.line(), err.column()), (Some(2), Some(5)));
+/// assert_eq!(err.snippet(), Some("f { ) }"));
+/// assert_eq!(err.message(), "unexpected ')'");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScriptError {
+    file: Option<String>,
+    line: Option<u32>,
+    column: Option<u32>,
+    snippet: Option<String>,
+    message: String,
+}
+
+impl ScriptError {
+    /// Creates an error from a diagnostic captured from the flecs log.
+    pub(super) fn from_log(file: Option<&str>, log: Option<&str>) -> Self {
+        let diagnostic = Diagnostic::parse(log);
+
+        Self {
+            file: file.filter(|file| !file.is_empty()).map(ToOwned::to_owned),
+            line: diagnostic.line,
+            column: diagnostic.column(),
+            snippet: diagnostic.snippet.map(ToOwned::to_owned),
+            message: diagnostic.message_or("failed to run script").to_owned(),
+        }
+    }
+
+    /// Creates an error from a diagnostic that flecs returned, and frees the diagnostic.
+    pub(super) fn from_owned_log(file: Option<&str>, log: *mut c_char) -> Self {
+        Self::from_log(file, take_log(log).as_deref())
+    }
+
+    /// The name of the script, typically a file name, if it has one.
+    pub fn file(&self) -> Option<&str> {
+        self.file.as_deref()
+    }
+
+    /// The line in the script at which the error occurred, starting at 1, if it is known.
+    pub fn line(&self) -> Option<u32> {
+        self.line
+    }
+
+    /// The column in the line at which the error occurred, starting at 1 and counted in
+    /// characters, if it is known.
+    pub fn column(&self) -> Option<u32> {
+        self.column
+    }
+
+    /// The line of code in which the error occurred, if it is known.
+    pub fn snippet(&self) -> Option<&str> {
+        self.snippet.as_deref()
+    }
+
+    /// Human readable description of what went wrong.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl Display for ScriptError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        if let Some(file) = &self.file {
+            write!(f, "{file}:")?;
+        }
+        if let Some(line) = self.line {
+            write!(f, "{line}:")?;
+            if let Some(column) = self.column {
+                write!(f, "{column}:")?;
+            }
+        }
+        if self.file.is_some() || self.line.is_some() {
+            write!(f, " ")?;
+        }
+        write!(f, "{}", self.message)
+    }
+}
+
+impl core::error::Error for ScriptError {}
@@ -0,0 +1,242 @@
+use core::ptr::NonNull;
+
+use flecs_ecs::core::*;
+use flecs_ecs::sys;
+
+/// [`ScriptVars`] is a set of variables that can be passed to a script when it is evaluated.
+///
+/// Variables are organized in scopes. Variables in a child scope, created with [`ScriptVars::push()`],
+/// shadow variables with the same name in the parent scopes. Values are dropped when their
+/// scope is popped or when the [`ScriptVars`] object goes out of scope.
+///
+/// # Example
+///
+/// ```
+/// use flecs_ecs::prelude::*;
+/// use flecs_ecs::addons::script::*;
+///
+/// #[derive(Component)]
+/// #[flecs(meta)]
+/// struct Height {
+///     value: f32,
+/// }
+///
+/// let world = World::new();
+/// world.component_named::<Height>("Height");
+///
+/// let script = Script::parse(&world, "tree", "tree { Height: {value: $height} }", None).unwrap();
+///
+/// let mut vars = ScriptVars::new(&world);
+/// vars.define("height", 2.5f32);
+/// script.eval_with_vars(&mut vars).unwrap();
+///
+/// world.lookup("tree").get::<&Height>(|height| assert_eq!(height.value, 2.5));
+/// ```
+pub struct ScriptVars<'a> {
+    vars: NonNull<sys::ecs_script_vars_t>,
+    depth: usize,
+    world: WorldRef<'a>,
+}
+
+impl Drop for ScriptVars<'_> {
+    fn drop(&mut self) {
+        while self.depth > 0 {
+            self.pop();
+        }
+        unsafe { sys::ecs_script_vars_fini(self.vars.as_ptr()) };
+    }
+}
+
+impl<'a> ScriptVars<'a> {
+    /// Create a new set of script variables with an empty root scope.
+    ///
+    /// # See also
+    ///
+    /// * C API: `ecs_script_vars_init`
+    pub fn new(world: impl WorldProvider<'a>) -> Self {
+        let world = world.world();
+        let vars = unsafe { sys::ecs_script_vars_init(world.world_ptr_mut()) };
+        ScriptVars {
+            vars: NonNull::new(vars).expect("failed to create script variables"),
+            depth: 0,
+            world,
+        }
+    }
+
+    /// Define a variable in the current scope.
+    ///
+    /// # Type Parameters
+    ///
+    /// * `T` - The type of the variable. The script can only use the value if the type is reflected.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the variable, used as `$name` in a script.
+    /// * `value` - The value of the variable.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a variable with the same name already exists in the current scope.
+    ///
+    /// # See also
+    ///
+    /// * [`ScriptVars::get()`]
+    /// * C API: `ecs_script_vars_define_id`
+    pub fn define<T: ComponentId + DataComponent>(&mut self, name: &str, value: T) -> &mut Self {
+        let id = self.world.component_id::<T>();
+        let type_info = unsafe { sys::ecs_get_type_info(self.world.world_ptr(), *id) };
+        ecs_assert!(
+            !type_info.is_null(),
+            FlecsErrorCode::InvalidParameter,
+            "type of variable '{}' is not a component with data",
+            name
+        );
+
+        unsafe {
+            // names are not copied by flecs, so store them in the scope of the variable
+            let stack = self.vars.as_ref().stack;
+            let name_ptr = sys::flecs_stack_alloc(stack, name.len() as i32 + 1, 1) as *mut u8;
+            core::ptr::copy_nonoverlapping(name.as_ptr(), name_ptr, name.len());
+            *name_ptr.add(name.len()) = 0;
+
+            let var = sys::ecs_script_vars_declare(self.vars.as_ptr(), name_ptr as *const _);
+            let Some(var) = var.as_mut() else {
+                panic!("variable '{name}' is already defined in this scope");
+            };
+
+            let ptr = sys::flecs_stack_alloc(stack, (*type_info).size, (*type_info).alignment);
+            core::ptr::write(ptr as *mut T, value);
+            var.value.type_ = *id;
+            var.value.ptr = ptr;
+            var.type_info = type_info;
+        }
+
+        self
+    }
+
+    /// Get the value of a variable in the current scope or one of its parent scopes.
+    ///
+    /// # Type Parameters
+    ///
+    /// * `T` - The type of the variable.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the variable.
+    ///
+    /// # Returns
+    ///
+    /// The value of the variable, or `None` if no variable with that name exists.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the variable is not of type `T`.
+    ///
+    /// # See also
+    ///
+    /// * [`ScriptVars::get_mut()`]
+    /// * C API: `ecs_script_vars_lookup`
+    pub fn get<T: ComponentId + DataComponent>(&self, name: &str) -> Option<&T> {
+        self.lookup::<T>(name).map(|ptr| unsafe { &*ptr })
+    }
+
+    /// Get mutable access to the value of a variable in the current scope or one of its parent
+    /// scopes. This can be used to change the inputs of a script in between evaluations.
+    ///
+    /// # Type Parameters
+    ///
+    /// * `T` - The type of the variable.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the variable.
+    ///
+    /// # Returns
+    ///
+    /// The value of the variable, or `None` if no variable with that name exists.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the variable is not of type `T`.
+    ///
+    /// # See also
+    ///
+    /// * [`ScriptVars::get()`]
+    pub fn get_mut<T: ComponentId + DataComponent>(&mut self, name: &str) -> Option<&mut T> {
+        self.lookup::<T>(name).map(|ptr| unsafe { &mut *ptr })
+    }
+
+    /// Push a new scope. Variables defined after this call shadow variables with the same name
+    /// in the parent scopes, until the scope is popped.
+    ///
+    /// # See also
+    ///
+    /// * [`ScriptVars::pop()`]
+    /// * C API: `ecs_script_vars_push`
+    pub fn push(&mut self) -> &mut Self {
+        let vars = unsafe { sys::ecs_script_vars_push(self.vars.as_ptr()) };
+        self.vars = NonNull::new(vars).expect("failed to push script variable scope");
+        self.depth += 1;
+        self
+    }
+
+    /// Pop the current scope, dropping the variables that were defined in it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the current scope is the root scope.
+    ///
+    /// # See also
+    ///
+    /// * [`ScriptVars::push()`]
+    /// * C API: `ecs_script_vars_pop`
+    pub fn pop(&mut self) -> &mut Self {
+        assert!(
+            self.depth > 0,
+            "cannot pop the root scope of script variables"
+        );
+        let parent = unsafe { sys::ecs_script_vars_pop(self.vars.as_ptr()) };
+        self.vars = NonNull::new(parent).expect("script variable scope has no parent");
+        self.depth -= 1;
+        self
+    }
+
+    /// Returns the descriptor to evaluate a script with these variables.
+    ///
+    /// The descriptor borrows the variables, and must not be used after they are dropped.
+    pub fn eval_desc(&mut self) -> sys::ecs_script_eval_desc_t {
+        sys::ecs_script_eval_desc_t {
+            vars: self.vars.as_ptr(),
+            runtime: core::ptr::null_mut(),
+        }
+    }
+
+    /// Returns the pointer to the current scope.
+    pub fn as_ptr(&self) -> *mut sys::ecs_script_vars_t {
+        self.vars.as_ptr()
+    }
+
+    fn lookup<T: ComponentId + DataComponent>(&self, name: &str) -> Option<*mut T> {
+        let name = compact_str::format_compact!("{}\0", name);
+        let var =
+            unsafe { sys::ecs_script_vars_lookup(self.vars.as_ptr(), name.as_ptr() as *const _) };
+        let var = unsafe { var.as_ref()? };
+        if var.value.ptr.is_null() {
+            return None;
+        }
+
+        let id = self.world.component_id::<T>();
+        if var.value.type_ != *id {
+            panic!(
+                "variable '{}' is of type '{}', not '{}'",
+                name.trim_end_matches('\0'),
+                self.world
+                    .entity_from_id(var.value.type_)
+                    .path()
+                    .unwrap_or_default(),
+                core::any::type_name::<T>()
+            );
+        }
+        Some(var.value.ptr as *mut T)
+    }
+}
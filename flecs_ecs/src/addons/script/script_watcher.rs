@@ -0,0 +1,357 @@
+#[cfg(feature = "std")]
+extern crate std;
+
+extern crate alloc;
+use alloc::{collections::BTreeMap, vec::Vec};
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use flecs_ecs::core::*;
+use flecs_ecs::sys;
+
+use super::{ScriptEntityView, ScriptError};
+use crate::core::take_log;
+
+/// Extension of the script files that are loaded from watched directories.
+const SCRIPT_EXTENSION: &str = "flecs";
+
+/// A change that [`ScriptWatcher::poll()`] applied to the world.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ScriptWatchEvent {
+    /// A script file was found and loaded for the first time.
+    Loaded(PathBuf),
+    /// A script file was changed, and the script was run again.
+    Reloaded(PathBuf),
+    /// A script file was removed, and the entities it created were deleted if it was loaded.
+    Unloaded(PathBuf),
+    /// A script file could not be loaded or run. See [`ScriptWatcher`] for what is kept of the
+    /// previous version of the script.
+    Failed(PathBuf, ScriptError),
+}
+
+struct WatchedScript {
+    entity: Option<Entity>,
+    /// The modification time of the file when it was last polled, or `None` if it couldn't be
+    /// read. The outer `None` means that the file wasn't polled yet.
+    modified: Option<Option<SystemTime>>,
+    error: Option<ScriptError>,
+}
+
+/// [`ScriptWatcher`] reloads script files when they change on disk.
+///
+/// The watcher tracks a set of script files and directories. Every call to
+/// [`ScriptWatcher::poll()`] checks the modification time of the files, and runs the scripts that
+/// changed again. Directories are searched recursively for `.flecs` files, so files that are added
+/// to a watched directory are loaded, and files that are removed are unloaded.
+///
+/// Scripts are run again in place: entities with a name that were created by the previous version
+/// of a script are kept and updated, so that references to them, like instances of a prefab, stay
+/// valid. Anonymous entities and templates are created again. Entities and components that are
+/// removed from a script are not removed from the world until the script file is removed.
+///
+/// When a script fails, the error is reported and the watched file is tried again once it changes.
+/// A script that fails to parse, such as because of a typo, doesn't change the world. A script that
+/// fails while it runs keeps the anonymous entities of the previous version and deletes the ones it
+/// created before the error, but changes it made to named entities before the error are kept, and
+/// its templates are lost until the script runs successfully again.
+///
+/// # Example
+///
+/// ```no_run
+/// use flecs_ecs::prelude::*;
+/// use flecs_ecs::addons::script::*;
+///
+/// let world = World::new();
+///
+/// let mut watcher = ScriptWatcher::new(&world);
+/// watcher.watch("assets/prefabs").watch("assets/level.flecs");
+///
+/// loop {
+///     for event in watcher.poll() {
+///         if let ScriptWatchEvent::Failed(path, err) = event {
+///             eprintln!("{}: {err}", path.display());
+///         }
+///     }
+///
+///     world.progress();
+/// }
+/// ```
+pub struct ScriptWatcher<'a> {
+    world: WorldRef<'a>,
+    paths: Vec<PathBuf>,
+    scripts: BTreeMap<PathBuf, WatchedScript>,
+}
+
+impl<'a> ScriptWatcher<'a> {
+    /// Create a watcher that doesn't watch any files yet.
+    pub fn new(world: impl WorldProvider<'a>) -> Self {
+        ScriptWatcher {
+            world: world.world(),
+            paths: Vec::new(),
+            scripts: BTreeMap::new(),
+        }
+    }
+
+    /// Watch a script file, or a directory with script files.
+    ///
+    /// The scripts are loaded by the next call to [`ScriptWatcher::poll()`].
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The script file, or the directory that is searched for `.flecs` files.
+    pub fn watch(&mut self, path: impl AsRef<Path>) -> &mut Self {
+        let path = path.as_ref().to_path_buf();
+        if !self.paths.contains(&path) {
+            self.paths.push(path);
+        }
+        self
+    }
+
+    /// Load new scripts, and run scripts that changed since the last call again.
+    ///
+    /// Scripts are loaded in the order of their paths. Scripts can't be run again while the
+    /// world is in readonly or deferred mode, such as in a system, so then nothing is polled and
+    /// the changes are applied by the next call outside of it.
+    ///
+    /// # Returns
+    ///
+    /// The scripts that were loaded, reloaded, unloaded or that failed.
+    pub fn poll(&mut self) -> Vec<ScriptWatchEvent> {
+        if self.world.is_readonly() || self.world.is_deferred() {
+            return Vec::new();
+        }
+
+        let mut files = Vec::new();
+        for path in &self.paths {
+            collect_files(path, &mut files);
+        }
+        files.sort();
+        files.dedup();
+
+        let mut events = Vec::new();
+
+        let removed: Vec<PathBuf> = self
+            .scripts
+            .keys()
+            .filter(|path| files.binary_search(path).is_err())
+            .cloned()
+            .collect();
+        for path in removed {
+            if let Some(entity) = self.scripts.remove(&path).and_then(|script| script.entity) {
+                self.unload(entity);
+            }
+            events.push(ScriptWatchEvent::Unloaded(path));
+        }
+
+        for path in files {
+            let modified = fs::metadata(&path).and_then(|m| m.modified()).ok();
+            let script = self.scripts.entry(path.clone()).or_insert(WatchedScript {
+                entity: None,
+                modified: None,
+                error: None,
+            });
+            if script.modified == Some(modified) {
+                continue;
+            }
+            script.modified = Some(modified);
+
+            let was_loaded = script.entity.is_some();
+            let result = match script.entity {
+                Some(entity) => reload(self.world, entity, &path),
+                None => self
+                    .world
+                    .script()
+                    .build_from_file(&path.to_string_lossy())
+                    .map(|view| {
+                        script.entity = Some(view.id());
+                    }),
+            };
+
+            match result {
+                Ok(()) => {
+                    script.error = None;
+                    events.push(if was_loaded {
+                        ScriptWatchEvent::Reloaded(path)
+                    } else {
+                        ScriptWatchEvent::Loaded(path)
+                    });
+                }
+                Err(err) => {
+                    script.error = Some(err.clone());
+                    events.push(ScriptWatchEvent::Failed(path, err));
+                }
+            }
+        }
+
+        events
+    }
+
+    /// Returns the script entity of a watched file.
+    ///
+    /// # Returns
+    ///
+    /// The script entity, or `None` if the file is not watched or hasn't been loaded successfully.
+    pub fn script(&self, path: impl AsRef<Path>) -> Option<ScriptEntityView<'a>> {
+        self.scripts
+            .get(path.as_ref())
+            .and_then(|script| script.entity)
+            .map(|entity| ScriptEntityView::new_from(self.world, entity))
+    }
+
+    /// Returns the watched files for which the last load failed, with their error.
+    pub fn errors(&self) -> impl Iterator<Item = (&Path, &ScriptError)> {
+        self.scripts
+            .iter()
+            .filter_map(|(path, script)| Some((path.as_path(), script.error.as_ref()?)))
+    }
+
+    fn unload(&self, entity: Entity) {
+        let world = self.world.world_ptr_mut();
+        unsafe {
+            sys::ecs_script_clear(world, *entity, 0);
+            sys::ecs_delete(world, *entity);
+        }
+    }
+}
+
+fn collect_files(path: &Path, files: &mut Vec<PathBuf>) {
+    if path.is_file() {
+        files.push(path.to_path_buf());
+        return;
+    }
+
+    let Ok(entries) = fs::read_dir(path) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            collect_files(&path, files);
+        } else if path.extension().is_some_and(|ext| ext == SCRIPT_EXTENSION) {
+            files.push(path);
+        }
+    }
+}
+
+/// Runs the new version of a script file, without deleting the named entities that the previous
+/// version created.
+fn reload(world: WorldRef, entity: Entity, path: &Path) -> Result<(), ScriptError> {
+    let filename = path.to_string_lossy();
+    let code = fs::read_to_string(path)
+        .map_err(|err| ScriptError::from_log(Some(&filename), Some(&alloc::format!("{err}"))))?;
+
+    let world_ptr = world.world_ptr_mut();
+    let filename_c = compact_str::format_compact!("{}\0", filename);
+    let code_c = compact_str::format_compact!("{}\0", code);
+    let script_id = *world.component_id::<flecs::Script>();
+    let tag = ecs_pair(script_id, *entity);
+    let mut result = sys::ecs_script_eval_result_t {
+        error: core::ptr::null_mut(),
+    };
+
+    unsafe {
+        // parse errors don't touch the content of the previous version
+        let script = sys::ecs_script_parse(
+            world_ptr,
+            filename_c.as_ptr() as *const _,
+            code_c.as_ptr() as *const _,
+            core::ptr::null(),
+            &mut result,
+        );
+        if script.is_null() {
+            return Err(ScriptError::from_owned_log(Some(&filename), result.error));
+        }
+
+        // templates hold on to the code of the previous version and can't be defined twice, so
+        // create them again
+        let (templates, anonymous) = recreated_entities(world_ptr, tag, script_id);
+        for e in templates {
+            sys::ecs_delete(world_ptr, e);
+        }
+
+        let prev = sys::ecs_set_with(world_ptr, tag);
+        let failed = sys::ecs_script_eval(script, core::ptr::null(), &mut result) != 0;
+        sys::ecs_set_with(world_ptr, prev);
+
+        // anonymous entities can't be matched with the new version, so only keep the ones of
+        // the version that ran successfully
+        if failed {
+            let (_, created) = recreated_entities(world_ptr, tag, script_id);
+            for e in created {
+                if !anonymous.contains(&e) {
+                    sys::ecs_delete(world_ptr, e);
+                }
+            }
+        } else {
+            for e in anonymous {
+                sys::ecs_delete(world_ptr, e);
+            }
+        }
+
+        let free = sys::ecs_os_api.free_.expect("os api is missing");
+        let strdup = sys::ecs_os_api.strdup_.expect("os api is missing");
+        let component = &mut *(sys::ecs_ensure_id(
+            world_ptr,
+            *entity,
+            script_id,
+            core::mem::size_of::<flecs::Script>(),
+        ) as *mut flecs::Script);
+
+        if !component.error.is_null() {
+            free(component.error as *mut core::ffi::c_void);
+            component.error = core::ptr::null_mut();
+        }
+
+        if failed {
+            sys::ecs_script_free(script);
+            let log = take_log(result.error);
+            if let Some(log) = &log {
+                let log = compact_str::format_compact!("{}\0", log);
+                component.error = strdup(log.as_ptr() as *const _);
+            }
+            return Err(ScriptError::from_log(Some(&filename), log.as_deref()));
+        }
+
+        if !component.script.is_null() {
+            sys::ecs_script_free(component.script);
+        }
+        component.script = script;
+
+        if !component.code.is_null() {
+            free(component.code as *mut core::ffi::c_void);
+        }
+        component.code = strdup(code_c.as_ptr() as *const _);
+    }
+
+    Ok(())
+}
+
+/// Returns the templates and the anonymous entities that a script created.
+fn recreated_entities(
+    world: *mut sys::ecs_world_t,
+    tag: sys::ecs_id_t,
+    script_id: sys::ecs_entity_t,
+) -> (Vec<sys::ecs_entity_t>, Vec<sys::ecs_entity_t>) {
+    let mut templates = Vec::new();
+    let mut anonymous = Vec::new();
+    unsafe {
+        let mut it = sys::ecs_each_id(world, tag);
+        while sys::ecs_each_next(&mut it) {
+            for i in 0..it.count as usize {
+                let e = *it.entities.add(i);
+                let is_template = sys::ecs_get_id(world, e, script_id)
+                    .cast::<flecs::Script>()
+                    .as_ref()
+                    .is_some_and(|script| !script.template_.is_null());
+                if is_template {
+                    templates.push(e);
+                } else if sys::ecs_get_name(world, e).is_null() {
+                    anonymous.push(e);
+                }
+            }
+        }
+    }
+    (templates, anonymous)
+}
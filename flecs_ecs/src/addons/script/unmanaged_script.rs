@@ -9,6 +9,9 @@ extern crate std;
 extern crate alloc;
 use alloc::{borrow::ToOwned, string::String};
 
+use super::ScriptError;
+use crate::core::capture_log;
+
 /// A Script object is not associated to an entity and will be automatically deleted when it goes out of scope.
 /// For scripts that are associated with an entity, use [`ScriptBuilder`][super::ScriptBuilder] alongside [`ScriptEntityView`][super::ScriptEntityView].
 ///
@@ -49,6 +52,10 @@ impl<'a> Script<'a> {
     ///
     /// * code - The script code.
     ///
+    /// # Returns
+    ///
+    /// The parsed script, or a [`ScriptError`] describing the syntax error.
+    ///
     /// # See also
     ///
     /// * C API: `ecs_script_parse`
@@ -57,34 +64,27 @@ impl<'a> Script<'a> {
         name: &str,
         code: &str,
         desc: Option<sys::ecs_script_eval_desc_t>,
-    ) -> Option<Script<'a>> {
-        let name = compact_str::format_compact!("{}\0", name);
+    ) -> Result<Script<'a>, ScriptError> {
+        let name_c = compact_str::format_compact!("{}\0", name);
         let code = compact_str::format_compact!("{}\0", code);
         let world_ptr = world.world_ptr_mut();
+        let mut result = sys::ecs_script_eval_result_t {
+            error: core::ptr::null_mut(),
+        };
 
         let ptr = unsafe {
-            if let Some(desc) = desc {
-                sys::ecs_script_parse(
-                    world_ptr,
-                    name.as_ptr() as *const _,
-                    code.as_ptr() as *const _,
-                    &desc,
-                    core::ptr::null_mut(),
-                )
-            } else {
-                sys::ecs_script_parse(
-                    world_ptr,
-                    name.as_ptr() as *const _,
-                    code.as_ptr() as *const _,
-                    core::ptr::null(),
-                    core::ptr::null_mut(),
-                )
-            }
+            sys::ecs_script_parse(
+                world_ptr,
+                name_c.as_ptr() as *const _,
+                code.as_ptr() as *const _,
+                desc.as_ref().map_or(core::ptr::null(), |desc| desc),
+                &mut result,
+            )
         };
         if ptr.is_null() {
-            None
+            Err(ScriptError::from_owned_log(Some(name), result.error))
         } else {
-            Some(Script {
+            Ok(Script {
                 script: ptr,
                 ast: core::ptr::null_mut(),
                 _phantom: core::marker::PhantomData::<&'a ()>,
@@ -96,19 +96,46 @@ impl<'a> Script<'a> {
     ///
     /// # Returns
     ///
-    /// True if success, false if failed.
+    /// Ok if success, or a [`ScriptError`] describing why the script failed.
     ///
     /// # See also
     ///
     /// * C API: `ecs_script_eval`
-    pub fn eval(&self, desc: Option<sys::ecs_script_eval_desc_t>) -> bool {
-        if let Some(desc) = desc {
-            unsafe { sys::ecs_script_eval(self.script, &desc, core::ptr::null_mut()) == 0 }
-        } else {
-            unsafe {
-                sys::ecs_script_eval(self.script, core::ptr::null(), core::ptr::null_mut()) == 0
-            }
+    pub fn eval(&self, desc: Option<sys::ecs_script_eval_desc_t>) -> Result<(), ScriptError> {
+        self.eval_desc(desc.as_ref().map_or(core::ptr::null(), |desc| desc))
+    }
+
+    /// Evaluate script with variables.
+    /// This operation evaluates (runs) a parsed script, where the script can use the variables as `$name`.
+    ///
+    /// # Arguments
+    ///
+    /// * vars - The variables that are available to the script.
+    ///
+    /// # Returns
+    ///
+    /// Ok if success, or a [`ScriptError`] describing why the script failed.
+    ///
+    /// # See also
+    ///
+    /// * [`ScriptVars`][super::ScriptVars]
+    /// * C API: `ecs_script_eval`
+    pub fn eval_with_vars(&self, vars: &mut super::ScriptVars) -> Result<(), ScriptError> {
+        self.eval_desc(&vars.eval_desc())
+    }
+
+    fn eval_desc(&self, desc: *const sys::ecs_script_eval_desc_t) -> Result<(), ScriptError> {
+        let mut result = sys::ecs_script_eval_result_t {
+            error: core::ptr::null_mut(),
+        };
+
+        if unsafe { sys::ecs_script_eval(self.script, desc, &mut result) } == 0 {
+            return Ok(());
         }
+
+        let name = unsafe { (*self.script).name };
+        let name = (!name.is_null()).then(|| unsafe { CStr::from_ptr(name) }.to_string_lossy());
+        Err(ScriptError::from_owned_log(name.as_deref(), result.error))
     }
 
     pub fn destroy(self) {
@@ -126,23 +153,36 @@ impl<'a> Script<'a> {
     ///
     /// # Returns
     ///
-    /// True if success, false otherwise.
+    /// Ok if success, or a [`ScriptError`] describing why the script failed.
     ///
     /// # See also
     ///
     /// * C API: `ecs_script_run`
-    pub fn run_code(world: impl WorldProvider<'a>, name: &str, code: &str) -> bool {
-        let name = compact_str::format_compact!("{}\0", name);
+    pub fn run_code(
+        world: impl WorldProvider<'a>,
+        name: &str,
+        code: &str,
+    ) -> Result<(), ScriptError> {
+        let name_c = compact_str::format_compact!("{}\0", name);
         let code = compact_str::format_compact!("{}\0", code);
         let world_ptr = world.world_ptr_mut();
+        let mut result = sys::ecs_script_eval_result_t {
+            error: core::ptr::null_mut(),
+        };
 
-        unsafe {
+        let success = unsafe {
             sys::ecs_script_run(
                 world_ptr,
-                name.as_ptr() as *const _,
+                name_c.as_ptr() as *const _,
                 code.as_ptr() as *const _,
-                core::ptr::null_mut(),
+                &mut result,
             ) == 0
+        };
+
+        if success {
+            Ok(())
+        } else {
+            Err(ScriptError::from_owned_log(Some(name), result.error))
         }
     }
 
@@ -155,12 +195,24 @@ impl<'a> Script<'a> {
     ///
     /// # Returns
     ///
-    /// True if success, false if failed.
-    pub fn run_file(world: impl WorldProvider<'a>, filename: &str) -> bool {
-        let filename = compact_str::format_compact!("{}\0", filename);
+    /// Ok if success, or a [`ScriptError`] describing why the file could not be loaded or run.
+    ///
+    /// # See also
+    ///
+    /// * C API: `ecs_script_run_file`
+    pub fn run_file(world: impl WorldProvider<'a>, filename: &str) -> Result<(), ScriptError> {
+        let filename_c = compact_str::format_compact!("{}\0", filename);
         let world_ptr = world.world_ptr_mut();
 
-        unsafe { sys::ecs_script_run_file(world_ptr, filename.as_ptr() as *const _) == 0 }
+        let (result, log) = capture_log(|| unsafe {
+            sys::ecs_script_run_file(world_ptr, filename_c.as_ptr() as *const _)
+        });
+
+        if result == 0 {
+            Ok(())
+        } else {
+            Err(ScriptError::from_log(Some(filename), log.as_deref()))
+        }
     }
 
     /// Convert script AST to string.
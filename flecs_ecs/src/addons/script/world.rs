@@ -40,12 +40,12 @@ impl World {
     ///
     /// # Returns
     ///
-    /// True if success, false otherwise.
+    /// Ok if success, or a [`ScriptError`] describing why the script failed.
     ///
     /// # See also
     ///
     /// * C API: `ecs_script_run`
-    pub fn run_code(&self, name: &str, code: &str) -> bool {
+    pub fn run_code(&self, name: &str, code: &str) -> Result<(), ScriptError> {
         Script::run_code(self, name, code)
     }
 
@@ -58,8 +58,12 @@ impl World {
     ///
     /// # Returns
     ///
-    /// True if success, false if failed.
-    pub fn run_file(&self, filename: &str) -> bool {
+    /// Ok if success, or a [`ScriptError`] describing why the file could not be loaded or run.
+    ///
+    /// # See also
+    ///
+    /// * C API: `ecs_script_run_file`
+    pub fn run_file(&self, filename: &str) -> Result<(), ScriptError> {
         Script::run_file(self, filename)
     }
 
@@ -96,4 +100,58 @@ impl World {
     pub fn script_entity_from(&self, id: impl IntoEntity) -> ScriptEntityView<'_> {
         ScriptEntityView::new_from(self, id)
     }
+
+    /// Parse and evaluate an expression.
+    ///
+    /// To evaluate an initializer without a type, like `{x: 10, y: 20}`, or to evaluate the
+    /// same expression more than once, use an [`Expr`] instead.
+    ///
+    /// # Type Parameters
+    ///
+    /// * `T` - The type of the result. The result of the expression is converted to this type.
+    ///
+    /// # Arguments
+    ///
+    /// * `expr` - The expression.
+    /// * `vars` - The variables that the expression can use.
+    ///
+    /// # Returns
+    ///
+    /// The result of the expression, or an error with the location of the problem.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use flecs_ecs::prelude::*;
+    /// use flecs_ecs::addons::script::*;
+    ///
+    /// #[derive(Component)]
+    /// #[flecs(meta)]
+    /// struct Position {
+    ///     x: f32,
+    ///     y: f32,
+    /// }
+    ///
+    /// let world = World::new();
+    /// world.component_named::<Position>("Position");
+    /// world.entity_named("player").set(Position { x: 10.0, y: 0.0 });
+    ///
+    /// let mut vars = ScriptVars::new(&world);
+    /// vars.define("speed", 1.5f32);
+    ///
+    /// let value = world.eval_expr::<f32>("player[Position].x + $speed * 2", &vars);
+    /// assert_eq!(value, Ok(13.0));
+    /// ```
+    ///
+    /// # See also
+    ///
+    /// * [`Expr::parse()`]
+    /// * C API: `ecs_expr_parse`, `ecs_expr_eval`
+    pub fn eval_expr<T: ComponentId + DataComponent + Default>(
+        &self,
+        expr: &str,
+        vars: &ScriptVars,
+    ) -> Result<T, ExprError> {
+        Expr::parse(self, expr, vars)?.eval::<T>(vars)
+    }
 }
@@ -1,2 +1,5 @@
 mod stats;
 pub use stats::*;
+mod stats_view;
+pub use stats_view::*;
+mod world;
@@ -0,0 +1,240 @@
+use flecs_ecs::core::*;
+use flecs_ecs::sys;
+
+extern crate alloc;
+use alloc::{boxed::Box, vec::Vec};
+
+/// A metric that is measured over the window of samples that the stats addon retains.
+///
+/// The stats addon records a sample of each metric roughly 60 times per second, and keeps the
+/// samples of the last second. Metrics that count events or time, like the frame time, report
+/// the amount per sample.
+///
+/// The window starts out with zeroed samples, which the minimum and average include until the
+/// window has filled up. Use [`StatsMetric::latest()`] for metrics of a world that has been
+/// collecting statistics for less than a second.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct StatsMetric {
+    latest: f32,
+    min: f32,
+    max: f32,
+    average: f32,
+}
+
+impl StatsMetric {
+    /// Reduces the window of a metric into its latest, minimum, maximum and average value.
+    ///
+    /// # Arguments
+    ///
+    /// * `metric` - The metric with the retained window.
+    /// * `t` - The position of the latest sample in the window.
+    fn from_window(metric: &sys::ecs_metric_t, t: i32) -> Self {
+        let mut reduced: sys::ecs_metric_t = unsafe { core::mem::zeroed() };
+        unsafe {
+            sys::ecs_metric_reduce(&mut reduced, metric, 0, t);
+            Self {
+                latest: metric.gauge.avg[t as usize],
+                min: reduced.gauge.min[0],
+                max: reduced.gauge.max[0],
+                average: reduced.gauge.avg[0],
+            }
+        }
+    }
+
+    /// The value of the latest sample.
+    pub fn latest(&self) -> f32 {
+        self.latest
+    }
+
+    /// The lowest value in the window, which includes the zeroed samples until the window has
+    /// filled up.
+    pub fn min(&self) -> f32 {
+        self.min
+    }
+
+    /// The highest value in the window.
+    pub fn max(&self) -> f32 {
+        self.max
+    }
+
+    /// The average value over the window, which counts the samples that weren't recorded yet
+    /// as `0`.
+    pub fn average(&self) -> f32 {
+        self.average
+    }
+}
+
+/// Statistics of a world, as collected by the stats addon.
+///
+/// These are typically obtained via [`World::stats()`].
+pub struct WorldStatsView {
+    stats: Box<sys::ecs_world_stats_t>,
+}
+
+impl WorldStatsView {
+    /// Copies the statistics out of the component, so they don't change as the world
+    /// progresses.
+    pub(crate) fn new(stats: &sys::ecs_world_stats_t) -> Self {
+        let mut copy = Box::<sys::ecs_world_stats_t>::new_uninit();
+        unsafe {
+            copy.as_mut_ptr().copy_from_nonoverlapping(stats, 1);
+            Self {
+                stats: copy.assume_init(),
+            }
+        }
+    }
+
+    fn metric(&self, metric: &sys::ecs_metric_t) -> StatsMetric {
+        StatsMetric::from_window(metric, self.stats.t)
+    }
+
+    /// Number of alive entities.
+    pub fn entity_count(&self) -> StatsMetric {
+        self.metric(&self.stats.entities.count)
+    }
+
+    /// Number of components, which are ids with data.
+    pub fn component_count(&self) -> StatsMetric {
+        self.metric(&self.stats.components.component_count)
+    }
+
+    /// Number of tables.
+    pub fn table_count(&self) -> StatsMetric {
+        self.metric(&self.stats.tables.count)
+    }
+
+    /// Number of queries, including the queries of systems and observers.
+    pub fn query_count(&self) -> StatsMetric {
+        self.metric(&self.stats.queries.query_count)
+    }
+
+    /// Number of systems.
+    pub fn system_count(&self) -> StatsMetric {
+        self.metric(&self.stats.queries.system_count)
+    }
+
+    /// Number of observers.
+    pub fn observer_count(&self) -> StatsMetric {
+        self.metric(&self.stats.queries.observer_count)
+    }
+
+    /// Seconds spent processing frames.
+    pub fn frame_time(&self) -> StatsMetric {
+        self.metric(&self.stats.performance.frame_time)
+    }
+
+    /// Seconds spent running systems.
+    pub fn system_time(&self) -> StatsMetric {
+        self.metric(&self.stats.performance.system_time)
+    }
+
+    /// Seconds spent merging commands.
+    pub fn merge_time(&self) -> StatsMetric {
+        self.metric(&self.stats.performance.merge_time)
+    }
+
+    /// Number of frames per second.
+    pub fn fps(&self) -> StatsMetric {
+        self.metric(&self.stats.performance.fps)
+    }
+
+    /// The delta time passed to systems, in seconds.
+    pub fn delta_time(&self) -> StatsMetric {
+        self.metric(&self.stats.performance.delta_time)
+    }
+
+    /// Number of frames that ran per sample. Use [`WorldStatsView::fps()`] for the frame rate.
+    pub fn frame_count(&self) -> StatsMetric {
+        self.metric(&self.stats.frame.frame_count)
+    }
+
+    /// Returns the underlying statistics, for metrics that don't have an accessor.
+    pub fn raw(&self) -> &sys::ecs_world_stats_t {
+        &self.stats
+    }
+}
+
+/// Statistics of a system, as collected by the stats addon.
+///
+/// These are typically obtained via [`System::stats()`](crate::addons::system::System::stats).
+pub struct SystemStatsView {
+    stats: Box<sys::ecs_system_stats_t>,
+}
+
+impl SystemStatsView {
+    pub(crate) fn new(stats: &sys::ecs_system_stats_t) -> Self {
+        Self {
+            stats: Box::new(*stats),
+        }
+    }
+
+    /// Seconds spent running the system.
+    pub fn time_spent(&self) -> StatsMetric {
+        StatsMetric::from_window(&self.stats.time_spent, self.stats.query.t)
+    }
+
+    /// Number of entities matched by the query of the system.
+    pub fn matched_entity_count(&self) -> StatsMetric {
+        StatsMetric::from_window(&self.stats.query.matched_entity_count, self.stats.query.t)
+    }
+
+    /// Number of tables matched by the query of the system.
+    pub fn matched_table_count(&self) -> StatsMetric {
+        StatsMetric::from_window(&self.stats.query.matched_table_count, self.stats.query.t)
+    }
+
+    /// Returns whether the system is a task, which is a system that doesn't match entities.
+    pub fn is_task(&self) -> bool {
+        self.stats.task
+    }
+
+    /// Returns the underlying statistics, for metrics that don't have an accessor.
+    pub fn raw(&self) -> &sys::ecs_system_stats_t {
+        &self.stats
+    }
+}
+
+/// Statistics of a pipeline, as collected by the stats addon.
+///
+/// These are typically obtained via [`World::pipeline_stats()`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PipelineStatsView {
+    systems: Vec<Entity>,
+    sync_point_count: usize,
+}
+
+impl PipelineStatsView {
+    pub(crate) fn new(stats: &sys::ecs_pipeline_stats_t) -> Self {
+        let ids = if stats.systems.array.is_null() {
+            &[][..]
+        } else {
+            unsafe {
+                core::slice::from_raw_parts(
+                    stats.systems.array as *const sys::ecs_entity_t,
+                    stats.systems.count as usize,
+                )
+            }
+        };
+
+        Self {
+            // merges are stored as 0
+            systems: ids
+                .iter()
+                .filter(|&&id| id != 0)
+                .map(|&id| Entity(id))
+                .collect(),
+            sync_point_count: stats.sync_points.count as usize,
+        }
+    }
+
+    /// The active systems of the pipeline, in the order in which they run. Systems are active
+    /// when they match entities, or when they don't match entities at all.
+    pub fn systems(&self) -> &[Entity] {
+        &self.systems
+    }
+
+    /// Number of points in the pipeline at which commands are merged.
+    pub fn sync_point_count(&self) -> usize {
+        self.sync_point_count
+    }
+}
@@ -0,0 +1,126 @@
+use super::{PipelineStatsView, SystemStatsView, WorldStatsView};
+use crate::addons::system::System;
+use crate::core::*;
+use crate::sys;
+
+/// Returns the statistics component of the world entity for the last second, or `None` if the
+/// stats addon, which registers the component, isn't imported.
+fn stats_component<T: ComponentId>(world: &World) -> Option<*const T> {
+    if !T::is_registered_with_world(world) {
+        return None;
+    }
+    let id = ecs_pair(*world.component_id::<T>(), unsafe { sys::EcsPeriod1s });
+    let ptr = unsafe { sys::ecs_get_id(world.world_ptr(), ECS_WORLD, id) } as *const T;
+    (!ptr.is_null()).then_some(ptr)
+}
+
+/// Stats mixin implementation
+impl World {
+    /// Get the statistics of the world over the last second.
+    ///
+    /// Statistics are collected by the stats addon while the world progresses, so the addon
+    /// must be imported with `world.import::<Stats>()` first. Until the world progresses, the
+    /// statistics are empty, and during the first second the window of each metric is only
+    /// partly recorded, see [`StatsMetric`](super::StatsMetric).
+    ///
+    /// # Returns
+    ///
+    /// The statistics, or `None` if the stats addon isn't imported.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use flecs_ecs::addons::stats::Stats;
+    /// use flecs_ecs::prelude::*;
+    ///
+    /// let world = World::new();
+    /// assert!(world.stats().is_none());
+    /// world.import::<Stats>();
+    ///
+    /// world.entity();
+    /// world.progress();
+    ///
+    /// let stats = world.stats().unwrap();
+    /// assert!(stats.entity_count().latest() > 0.0);
+    /// let frame_time = stats.frame_time().average();
+    /// ```
+    ///
+    /// # See also
+    ///
+    /// * [`World::pipeline_stats()`]
+    /// * [`System::stats()`]
+    /// * C API: `ecs_world_stats_get`
+    pub fn stats(&self) -> Option<WorldStatsView> {
+        let component = stats_component::<sys::EcsWorldStats>(self)?;
+        Some(WorldStatsView::new(unsafe { &(*component).stats }))
+    }
+
+    /// Get the statistics of the pipeline that the world runs.
+    ///
+    /// Like [`World::stats()`], this requires the stats addon to be imported.
+    ///
+    /// # Returns
+    ///
+    /// The statistics, or `None` if the stats addon isn't imported or the pipeline hasn't run
+    /// since it was imported.
+    ///
+    /// # See also
+    ///
+    /// * [`World::stats()`]
+    /// * C API: `ecs_pipeline_stats_get`
+    pub fn pipeline_stats(&self) -> Option<PipelineStatsView> {
+        let component = stats_component::<sys::EcsPipelineStats>(self)?;
+        let pipeline = unsafe { sys::ecs_get_pipeline(self.world_ptr()) };
+        let stats = unsafe { sys::ecs_map_get_deref_(&(*component).stats, pipeline) }
+            as *const sys::ecs_pipeline_stats_t;
+        unsafe { stats.as_ref() }.map(PipelineStatsView::new)
+    }
+}
+
+impl System<'_> {
+    /// Get the statistics of the system over the last second.
+    ///
+    /// Like [`World::stats()`], this requires the stats addon to be imported.
+    ///
+    /// # Returns
+    ///
+    /// The statistics, or `None` if the stats addon isn't imported or the world hasn't progressed
+    /// since it was imported.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use flecs_ecs::addons::stats::Stats;
+    /// use flecs_ecs::prelude::*;
+    ///
+    /// #[derive(Component)]
+    /// struct Position {
+    ///     x: f32,
+    ///     y: f32,
+    /// }
+    ///
+    /// let world = World::new();
+    /// world.import::<Stats>();
+    /// world.entity().set(Position { x: 0.0, y: 0.0 });
+    ///
+    /// let system = world.system::<&mut Position>().each(|pos| pos.x += 1.0);
+    /// assert!(system.stats().is_none());
+    ///
+    /// world.progress();
+    ///
+    /// let stats = system.stats().unwrap();
+    /// assert_eq!(stats.matched_entity_count().latest(), 1.0);
+    /// ```
+    ///
+    /// # See also
+    ///
+    /// * [`World::stats()`]
+    /// * C API: `ecs_system_stats_get`
+    pub fn stats(&self) -> Option<SystemStatsView> {
+        let world = self.world();
+        let component = stats_component::<sys::EcsSystemStats>(&world)?;
+        let stats = unsafe { sys::ecs_map_get_deref_(&(*component).stats, *self.id()) }
+            as *const sys::ecs_system_stats_t;
+        unsafe { stats.as_ref() }.map(SystemStatsView::new)
+    }
+}
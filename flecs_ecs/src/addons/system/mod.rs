@@ -77,6 +77,14 @@ impl<'a> System<'a> {
     ///
     /// * `context` - The context to set.
     pub fn set_context(&mut self, context: *mut c_void) {
+        // a typed context is dropped here, as its free function must not be used for `context`
+        let system = unsafe {
+            sys::ecs_system_get(self.world.world_ptr_mut(), *self.id()) as *mut sys::ecs_system_t
+        };
+        if let Some(system) = unsafe { system.as_mut() } {
+            TypedCtx::clear(&mut system.ctx, &mut system.ctx_free);
+        }
+
         let desc: sys::ecs_system_desc_t = sys::ecs_system_desc_t {
             entity: *self.id(),
             ctx: context,
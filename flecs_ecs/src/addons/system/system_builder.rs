@@ -147,6 +147,89 @@ where
         self.kind(enum_id)
     }
 
+    /// Run the system before another system of the same phase, or before the systems of a
+    /// [`SystemSet`](crate::addons::pipeline::SystemSet).
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The system or set.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use flecs_ecs::prelude::*;
+    ///
+    /// let world = World::new();
+    ///
+    /// let render = world
+    ///     .system_named::<()>("Render")
+    ///     .run(|mut it| while it.next() {});
+    ///
+    /// world
+    ///     .system_named::<()>("Move")
+    ///     .before(render)
+    ///     .run(|mut it| while it.next() {});
+    ///
+    /// world.progress();
+    ///
+    /// let schedule = world.pipeline_schedule(world.get_pipeline());
+    /// let names = schedule
+    ///     .systems()
+    ///     .map(|system| system.system().name())
+    ///     .collect::<Vec<_>>();
+    /// assert_eq!(names, ["Move", "Render"]);
+    /// ```
+    ///
+    /// # See also
+    ///
+    /// * [`SystemBuilder::after()`]
+    /// * [`World::validate_system_order()`]
+    #[cfg(feature = "flecs_pipeline")]
+    pub fn before(&mut self, other: impl IntoEntity) -> &mut Self {
+        let other = other.into_entity(self.world);
+        crate::addons::pipeline::ordering::add_constraint(
+            self.world,
+            Entity(self.desc.entity),
+            other,
+        );
+        self
+    }
+
+    /// Run the system after another system of the same phase, or after the systems of a
+    /// [`SystemSet`](crate::addons::pipeline::SystemSet).
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The system or set.
+    ///
+    /// # See also
+    ///
+    /// * [`SystemBuilder::before()`]
+    /// * [`World::validate_system_order()`]
+    #[cfg(feature = "flecs_pipeline")]
+    pub fn after(&mut self, other: impl IntoEntity) -> &mut Self {
+        let other = other.into_entity(self.world);
+        crate::addons::pipeline::ordering::add_constraint(
+            self.world,
+            other,
+            Entity(self.desc.entity),
+        );
+        self
+    }
+
+    /// Add the system to a [`SystemSet`](crate::addons::pipeline::SystemSet), which orders it
+    /// with the other systems of the set.
+    ///
+    /// # Arguments
+    ///
+    /// * `set` - The set, created with [`World::system_set()`].
+    #[cfg(feature = "flecs_pipeline")]
+    pub fn in_set(&mut self, set: impl IntoEntity) -> &mut Self {
+        let set = set.into_entity(self.world);
+        crate::addons::pipeline::ordering::add_to_set(self.world, Entity(self.desc.entity), set);
+        self
+    }
+
     /// Specify whether system should be ran in staged context.
     ///
     /// # Arguments
@@ -202,6 +285,8 @@ where
             panic!("you should not call this fn manually. Use `.each` , `.run` instead")
         }
         let system = System::new(self.world(), self.desc);
+        #[cfg(feature = "flecs_pipeline")]
+        crate::addons::pipeline::ordering::apply(&self.world.real_world());
         for s in self.term_builder.str_ptrs_to_free.iter_mut() {
             unsafe { core::mem::ManuallyDrop::drop(s) };
         }
@@ -0,0 +1,96 @@
+use core::time::Duration;
+
+use super::*;
+use crate::core::*;
+
+extern crate alloc;
+use alloc::boxed::Box;
+
+impl EntityView<'_> {
+    /// Invoke a callback once, after a delay. The timer is cancelled when the entity is
+    /// deleted.
+    ///
+    /// # Arguments
+    ///
+    /// * `delay` - The delay.
+    /// * `callback` - The callback, which is passed the entity.
+    ///
+    /// # Returns
+    ///
+    /// A handle to cancel the timer.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use core::time::Duration;
+    /// use flecs_ecs::prelude::*;
+    ///
+    /// #[derive(Component)]
+    /// struct Stunned;
+    ///
+    /// let world = World::new();
+    /// let e = world.entity().add(Stunned);
+    ///
+    /// e.after(Duration::from_millis(500), |e| {
+    ///     e.remove(Stunned);
+    /// });
+    ///
+    /// world.progress_time(0.5);
+    /// assert!(!e.has(Stunned));
+    /// ```
+    ///
+    /// # See also
+    ///
+    /// * [`EntityView::every()`]
+    /// * [`World::after()`]
+    pub fn after(
+        &self,
+        delay: Duration,
+        callback: impl FnOnce(EntityView) + 'static,
+    ) -> TimerHandle {
+        let entity = self.id();
+        let mut callback = Some(callback);
+        timer_callback_init(
+            &self.world(),
+            Some(entity),
+            delay,
+            false,
+            Box::new(move |world| {
+                if let Some(callback) = callback.take() {
+                    callback(world.entity_from_id(entity));
+                }
+            }),
+        )
+    }
+
+    /// Invoke a callback each time an interval passes, until the timer is cancelled or the
+    /// entity is deleted.
+    ///
+    /// # Arguments
+    ///
+    /// * `interval` - The interval.
+    /// * `callback` - The callback, which is passed the entity.
+    ///
+    /// # Returns
+    ///
+    /// A handle to cancel the timer.
+    ///
+    /// # See also
+    ///
+    /// * [`EntityView::after()`]
+    /// * [`World::every()`]
+    pub fn every(
+        &self,
+        interval: Duration,
+        mut callback: impl FnMut(EntityView) + 'static,
+    ) -> TimerHandle {
+        let entity = self.id();
+        timer_callback_init(
+            &self.world(),
+            Some(entity),
+            interval,
+            true,
+            Box::new(move |world| callback(world.entity_from_id(entity))),
+        )
+    }
+}
@@ -1,5 +1,9 @@
 mod timer;
 pub use timer::*;
+mod timer_callback;
+pub use timer_callback::TimerHandle;
+pub(crate) use timer_callback::{TimerCallbacks, timer_callback_init, timer_callbacks_init};
+mod entity_view;
 mod system;
 mod system_builder;
 mod world;
@@ -0,0 +1,186 @@
+//! Timers that invoke a Rust closure when they expire, instead of ticking systems.
+
+use core::time::Duration;
+
+use flecs_ecs_derive::Component;
+
+use crate::core::*;
+
+extern crate alloc;
+use alloc::{boxed::Box, collections::BTreeMap, vec::Vec};
+
+use super::TimerAPI;
+
+/// Handle to a timer created with [`World::after()`], [`World::every()`],
+/// [`EntityView::after()`] or [`EntityView::every()`].
+///
+/// The timer is an entity, which is deleted when the timer is cancelled, when a one-shot timer
+/// expires, or when the entity that the timer belongs to is deleted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TimerHandle {
+    timer: Entity,
+}
+
+impl TimerHandle {
+    /// The timer entity.
+    pub fn entity(&self) -> Entity {
+        self.timer
+    }
+
+    /// Returns whether the timer hasn't been cancelled or, for a one-shot timer, hasn't
+    /// expired yet.
+    ///
+    /// # Arguments
+    ///
+    /// * `world` - The world of the timer.
+    pub fn is_active<'a>(&self, world: impl WorldProvider<'a>) -> bool {
+        world.world().is_alive(self.timer)
+    }
+
+    /// Cancel the timer, so its callback is no longer invoked. Cancelling a timer that is no
+    /// longer active does nothing.
+    ///
+    /// # Arguments
+    ///
+    /// * `world` - The world of the timer.
+    pub fn cancel<'a>(&self, world: impl WorldProvider<'a>) {
+        let world = world.world();
+        if world.is_alive(self.timer) {
+            world.entity_from_id(self.timer).destruct();
+        }
+    }
+}
+
+impl From<TimerHandle> for Entity {
+    #[inline]
+    fn from(handle: TimerHandle) -> Self {
+        handle.timer
+    }
+}
+
+/// Tag for timer entities with a callback.
+#[derive(Debug, Clone, Copy, Component, Default)]
+struct TimerCallbackTag;
+
+/// Relationship from a timer to the entity it belongs to, which deletes the timer together
+/// with the entity.
+#[derive(Debug, Clone, Copy, Component, Default)]
+struct TimerOf;
+
+struct TimerCallback {
+    callback: Box<dyn FnMut(&World)>,
+    repeat: bool,
+}
+
+/// The callbacks of the timers of a world, by timer entity.
+#[derive(Default)]
+pub(crate) struct TimerCallbacks {
+    callbacks: BTreeMap<u64, TimerCallback>,
+}
+
+/// Creates the system that invokes the callbacks of expired timers, and the observer that
+/// drops the callbacks of deleted timers.
+///
+/// This runs when the world is created, because timers can be created from systems, where the
+/// world is readonly.
+pub(crate) fn timer_callbacks_init(world: &World) {
+    world.world_ctx_mut().timer_callbacks = Some(TimerCallbacks::default());
+
+    world
+        .component::<TimerOf>()
+        .add_trait::<(flecs::OnDeleteTarget, flecs::Delete)>();
+
+    world
+        .system_named::<&flecs::system::TickSource>("flecs::rust::TimerCallbacks")
+        .with(TimerCallbackTag)
+        .kind(flecs::pipeline::PreUpdate)
+        .run(|mut it| {
+            let mut expired = Vec::new();
+            while it.next() {
+                let ticks = it.field::<flecs::system::TickSource>(0);
+                for i in it.iter() {
+                    if ticks[i].tick {
+                        expired.push(*it.entity(i).id());
+                    }
+                }
+            }
+
+            let world = it.world();
+            for timer in expired {
+                // the callback is taken out while it runs, so it can create or cancel timers
+                let Some(mut entry) = world
+                    .real_world()
+                    .world_ctx_mut()
+                    .timer_callbacks
+                    .as_mut()
+                    .and_then(|timers| timers.callbacks.remove(&timer))
+                else {
+                    continue;
+                };
+
+                (entry.callback)(&world);
+
+                if entry.repeat {
+                    if let Some(timers) =
+                        world.real_world().world_ctx_mut().timer_callbacks.as_mut()
+                    {
+                        timers.callbacks.insert(timer, entry);
+                    }
+                } else {
+                    world.entity_from_id(timer).destruct();
+                }
+            }
+        });
+
+    world
+        .observer_named::<flecs::OnRemove, ()>("flecs::rust::TimerCallbackCleanup")
+        .with(TimerCallbackTag)
+        .each_entity(|e, _| {
+            if let Some(timers) = e
+                .world()
+                .real_world()
+                .world_ctx_mut()
+                .timer_callbacks
+                .as_mut()
+            {
+                timers.callbacks.remove(&*e.id());
+            }
+        });
+}
+
+/// Creates a timer entity with a callback.
+///
+/// # Arguments
+///
+/// * `world` - The world to create the timer in.
+/// * `owner` - The entity that the timer belongs to, or `None` if the timer belongs to the world.
+/// * `duration` - The timeout or interval of the timer.
+/// * `repeat` - Whether the timer is an interval timer, instead of a one-shot timer.
+/// * `callback` - The callback.
+pub(crate) fn timer_callback_init(
+    world: &World,
+    owner: Option<Entity>,
+    duration: Duration,
+    repeat: bool,
+    callback: Box<dyn FnMut(&World)>,
+) -> TimerHandle {
+    let real_world = world.real_world();
+    let timer = world.timer();
+    timer.add(TimerCallbackTag);
+    if let Some(owner) = owner {
+        timer.add((TimerOf, owner));
+    }
+    let seconds = duration.as_secs_f32();
+    let timer = if repeat {
+        timer.set_interval(seconds)
+    } else {
+        timer.set_timeout(seconds)
+    };
+
+    if let Some(timers) = real_world.world_ctx_mut().timer_callbacks.as_mut() {
+        timers
+            .callbacks
+            .insert(*timer.id(), TimerCallback { callback, repeat });
+    }
+    TimerHandle { timer: timer.id() }
+}
@@ -1,7 +1,12 @@
+use core::time::Duration;
+
 use super::*;
 use crate::core::*;
 use crate::sys;
 
+extern crate alloc;
+use alloc::boxed::Box;
+
 impl World {
     /// Find or register a singleton Timer
     pub fn timer(&self) -> Timer<'_> {
@@ -19,4 +24,82 @@ impl World {
     pub fn randomize_timers(&self) {
         unsafe { sys::ecs_randomize_timers(self.ptr_mut()) }
     }
+
+    /// Invoke a callback once, after a delay.
+    ///
+    /// The timer is synchronous, and is incremented each frame by the delta time of the frame,
+    /// without time scale. The callback is invoked in the `PreUpdate` phase of the frame in which
+    /// the timer expires, and operations on the world passed to it are deferred.
+    ///
+    /// # Arguments
+    ///
+    /// * `delay` - The delay.
+    /// * `callback` - The callback.
+    ///
+    /// # Returns
+    ///
+    /// A handle to cancel the timer.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use core::time::Duration;
+    /// use flecs_ecs::prelude::*;
+    ///
+    /// #[derive(Component)]
+    /// struct Explosion;
+    ///
+    /// let world = World::new();
+    ///
+    /// world.after(Duration::from_secs(2), |world| {
+    ///     world.entity().add(Explosion);
+    /// });
+    ///
+    /// world.progress_time(1.0);
+    /// assert_eq!(world.count(Explosion), 0);
+    /// world.progress_time(1.0);
+    /// assert_eq!(world.count(Explosion), 1);
+    /// ```
+    ///
+    /// # See also
+    ///
+    /// * [`World::every()`]
+    /// * [`EntityView::after()`]
+    pub fn after(&self, delay: Duration, callback: impl FnOnce(&World) + 'static) -> TimerHandle {
+        let mut callback = Some(callback);
+        timer_callback_init(
+            self,
+            None,
+            delay,
+            false,
+            Box::new(move |world| {
+                if let Some(callback) = callback.take() {
+                    callback(world);
+                }
+            }),
+        )
+    }
+
+    /// Invoke a callback each time an interval passes, until the timer is cancelled.
+    ///
+    /// The timer is synchronous, and is incremented each frame by the delta time of the frame,
+    /// without time scale. The callback is invoked in the `PreUpdate` phase of the frames in
+    /// which the interval passes, and operations on the world passed to it are deferred.
+    ///
+    /// # Arguments
+    ///
+    /// * `interval` - The interval.
+    /// * `callback` - The callback.
+    ///
+    /// # Returns
+    ///
+    /// A handle to cancel the timer.
+    ///
+    /// # See also
+    ///
+    /// * [`World::after()`]
+    /// * [`EntityView::every()`]
+    pub fn every(&self, interval: Duration, callback: impl FnMut(&World) + 'static) -> TimerHandle {
+        timer_callback_init(self, None, interval, true, Box::new(callback))
+    }
 }
@@ -254,6 +254,11 @@ pub(crate) const ECS_TOGGLE: u64 = 1 << 61;
 /// Include all components from entity to which AND is applied
 pub(crate) const ECS_AND: u64 = 1 << 60;
 
+// Type hook flags
+
+/// Set by flecs for components that cannot be copied.
+pub(crate) const ECS_TYPE_HOOK_COPY_ILLEGAL: u32 = 1 << 13;
+
 // Builtin component ids
 pub(crate) const ECS_COMPONENT: u64 = 1;
 pub(crate) const ECS_IDENTIFIER: u64 = 2;
@@ -97,9 +97,18 @@ pub fn register_copy_lifecycle_action<T: Clone>(type_hooks: &mut sys::ecs_type_h
     type_hooks.copy_ctor = Some(copy_ctor::<T>); //same implementation as copy
 }
 
+// `T` is kept so that registration code reads the same for all hooks, the panic message
+// takes the name from the type info.
+#[allow(clippy::extra_unused_type_parameters)]
 pub fn register_copy_panic_lifecycle_action<T>(type_hooks: &mut sys::ecs_type_hooks_t) {
-    type_hooks.copy = Some(panic_copy::<T>);
-    type_hooks.copy_ctor = Some(panic_copy::<T>); //same implementation as copy
+    type_hooks.copy = Some(panic_copy);
+    type_hooks.copy_ctor = Some(panic_copy); //same implementation as copy
+}
+
+/// Returns whether `copy` is the hook registered for components that don't implement `Clone`.
+pub(crate) fn is_panic_copy_hook(copy: sys::ecs_copy_t) -> bool {
+    let panic: sys::ecs_copy_t = Some(panic_copy);
+    matches!((copy, panic), (Some(copy), Some(panic)) if ptr::fn_addr_eq(copy, panic))
 }
 
 pub fn register_partial_ord_lifecycle_action<T: core::cmp::PartialOrd>(
@@ -245,15 +254,19 @@ fn panic_ctor<T>(_dst_ptr: *mut c_void, _count: i32, _type_info: *const sys::ecs
 }
 
 #[extern_abi]
-fn panic_copy<T>(
+fn panic_copy(
     _dst_ptr: *mut c_void,
     _src_ptr: *const c_void,
     _count: i32,
-    _type_info: *const sys::ecs_type_info_t,
+    type_info: *const sys::ecs_type_info_t,
 ) {
+    let name = unsafe { type_info.as_ref() }
+        .filter(|type_info| !type_info.name.is_null())
+        .map_or("<unknown>".into(), |type_info| unsafe {
+            core::ffi::CStr::from_ptr(type_info.name).to_string_lossy()
+        });
     panic!(
-        "Clone is not implemented for type {} and it's being used in a copy / duplicate operation such as component overriding or duplicating entities / components or prefab copying",
-        core::any::type_name::<T>()
+        "Clone is not implemented for type {name} and it's being used in a copy / duplicate operation such as component overriding or duplicating entities / components or prefab copying",
     );
 }
 
@@ -23,7 +23,19 @@ unsafe impl Send for OsApiHook {}
 /// Run automatically, once and only once, when the first [`super::World`]
 /// is created, or [`ensure_initialized`] is called directly.
 static OS_API_HOOKS: LazyLock<Mutex<Option<Vec<OsApiHook>>>> =
-    LazyLock::new(|| Mutex::new(Some(Default::default())));
+    LazyLock::new(|| Mutex::new(Some(default_hooks())));
+
+/// Hooks installed by crate features, which run before hooks added with [`add_init_hook`].
+fn default_hooks() -> Vec<OsApiHook> {
+    [
+        #[cfg(feature = "flecs_tracing")]
+        OsApiHook(Box::new(super::log_tracing::install)),
+        #[cfg(all(feature = "flecs_perf_trace", feature = "flecs_pipeline"))]
+        OsApiHook(Box::new(crate::addons::pipeline::profiler::install)),
+    ]
+    .into_iter()
+    .collect()
+}
 
 /// Initialize the Flecs OS API if not initialized already.
 ///
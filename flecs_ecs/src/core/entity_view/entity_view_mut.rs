@@ -65,6 +65,74 @@ impl<'a> EntityView<'a> {
         self
     }
 
+    /// Adds an ID to the entity, returning an error where [`EntityView::add`] would panic.
+    ///
+    /// Fails when the entity is not alive, when the `id` is not a valid component, pair or entity,
+    /// or when the `id` is a non ZST type without a constructor hook.
+    ///
+    /// Typed components are still registered on use. With the `flecs_manual_registration` feature,
+    /// use [`EntityView::try_set`] to also have the registration checked.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use flecs_ecs::prelude::*;
+    ///
+    /// #[derive(Component)]
+    /// struct Tag;
+    ///
+    /// let world = World::new();
+    ///
+    /// let e = world.entity();
+    /// assert!(e.try_add(Tag).is_ok());
+    ///
+    /// e.destruct();
+    /// assert!(e.try_add(Tag).is_err());
+    /// ```
+    pub fn try_add<T: IntoId>(self, id: T) -> Result<Self, FlecsError> {
+        let world = self.world.world_ptr_mut();
+        check_entity_alive(world, *self.id, "add")?;
+
+        let id = *id.into_id(self.world);
+
+        const {
+            if T::IS_PAIR
+                && T::IS_TYPED
+                && !T::IF_ID_IS_DEFAULT
+                && T::IS_TYPED_SECOND
+                && !T::IF_ID_IS_DEFAULT_SECOND
+                && !<T as IntoId>::IS_TYPE_TAG
+            {
+                panic!("none implement default, use `set_pair` instead to ensure valid data");
+            }
+        }
+
+        const {
+            if !T::IS_PAIR && T::IS_TYPED && !T::IF_ID_IS_DEFAULT && !<T as IntoId>::IS_TYPE_TAG {
+                panic!("Default hook not implemented for non ZST type");
+            }
+        }
+
+        if !T::IS_PAIR {
+            if !T::IS_TYPED {
+                try_check_add_id_validity(world, id)?;
+            }
+        } else if T::IS_TYPED {
+            if !T::IF_ID_IS_DEFAULT && !T::IS_TYPED_SECOND {
+                try_check_add_id_validity(world, id)?;
+            }
+        } else if T::IS_TYPED_SECOND {
+            if !T::IF_ID_IS_DEFAULT_SECOND {
+                try_check_add_id_validity(world, id)?;
+            }
+        } else {
+            try_check_add_id_validity(world, id)?;
+        }
+
+        unsafe { sys::ecs_add_id(world, *self.id, id) }
+        Ok(self)
+    }
+
     /// Adds an ID to the entity unchecked. Useful for run-time components.
     ///
     /// The provided `id` can represent various types, including a component, a pair, a tag, or another entity.
@@ -237,6 +305,28 @@ impl<'a> EntityView<'a> {
         self
     }
 
+    /// Remove an id from an entity, returning an error instead of panicking when the
+    /// entity is not alive or the id is not valid.
+    ///
+    /// # Arguments
+    ///
+    /// * `component_id`: The entity to remove.
+    pub fn try_remove<T: IntoId>(self, id: T) -> Result<Self, FlecsError> {
+        let world = self.world.world_ptr_mut();
+        check_entity_alive(world, *self.id, "remove")?;
+
+        let id = *id.into_id(self.world);
+        let id = if <T as IntoId>::IS_ENUM {
+            ecs_pair(id, ECS_WILDCARD)
+        } else {
+            id
+        };
+        check_id_valid(world, id, true, "remove")?;
+
+        unsafe { sys::ecs_remove_id(world, *self.id, id) }
+        Ok(self)
+    }
+
     /// Shortcut for `add((flecs::IsA, id))`.
     ///
     /// # Arguments
@@ -246,6 +336,21 @@ impl<'a> EntityView<'a> {
         unsafe { self.add_id_unchecked((ECS_IS_A, second.into_entity(self.world))) }
     }
 
+    /// Shortcut for `try_add((flecs::IsA, id))` that also fails when the base
+    /// inherits from this entity, which would create a cycle.
+    ///
+    /// # Arguments
+    ///
+    /// * `second`: The second element of the pair.
+    pub fn try_is_a(self, second: impl IntoEntity) -> Result<Self, FlecsError> {
+        let world = self.world.world_ptr_mut();
+        let second = *second.into_entity(self.world);
+        check_entity_alive(world, *self.id, "is_a")?;
+        check_entity_alive(world, second, "is_a")?;
+        check_no_cycle(world, ECS_IS_A, *self.id, second, "is_a")?;
+        Ok(unsafe { self.add_id_unchecked((ECS_IS_A, second)) })
+    }
+
     /// Shortcut for `add_id((flecs::ChildOf::ID, entity))`.
     ///
     /// # Arguments
@@ -256,6 +361,35 @@ impl<'a> EntityView<'a> {
         unsafe { self.add_id_unchecked((ECS_CHILD_OF, parent.into_entity(self.world))) }
     }
 
+    /// Shortcut for `try_add((flecs::ChildOf::ID, entity))` that also fails when the parent
+    /// is this entity or one of its descendants, which would create a cycle.
+    ///
+    /// # Arguments
+    ///
+    /// * `parent`: The parent entity to establish the relationship with.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use flecs_ecs::prelude::*;
+    ///
+    /// let world = World::new();
+    ///
+    /// let parent = world.entity();
+    /// let child = world.entity().child_of(parent);
+    ///
+    /// let err = parent.try_child_of(child).unwrap_err();
+    /// assert_eq!(err.code(), FlecsErrorCode::CycleDetected);
+    /// ```
+    pub fn try_child_of(self, parent: impl IntoEntity) -> Result<Self, FlecsError> {
+        let world = self.world.world_ptr_mut();
+        let parent = *parent.into_entity(self.world);
+        check_entity_alive(world, *self.id, "child_of")?;
+        check_entity_alive(world, parent, "child_of")?;
+        check_no_cycle(world, ECS_CHILD_OF, *self.id, parent, "child_of")?;
+        Ok(unsafe { self.add_id_unchecked((ECS_CHILD_OF, parent)) })
+    }
+
     /// Shortcut for `add_id((flecs::DependsOn::ID, entity))`.
     ///
     /// # Arguments
@@ -396,6 +530,35 @@ impl<'a> EntityView<'a> {
         self
     }
 
+    /// Sets a component of type `T` on the entity, returning an error instead of
+    /// panicking when the entity is not alive or the component is not registered.
+    ///
+    /// # Arguments
+    ///
+    /// * `component` - The component to set on the entity.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use flecs_ecs::prelude::*;
+    ///
+    /// #[derive(Component, Debug, PartialEq)]
+    /// struct Position {
+    ///     x: f32,
+    ///     y: f32,
+    /// }
+    ///
+    /// let world = World::new();
+    ///
+    /// let e = world.entity().try_set(Position { x: 1.0, y: 2.0 }).unwrap();
+    /// e.get::<&Position>(|pos| assert_eq!(pos, &Position { x: 1.0, y: 2.0 }));
+    /// ```
+    pub fn try_set<T: ComponentId>(self, component: T) -> Result<Self, FlecsError> {
+        check_entity_alive(self.world.world_ptr(), *self.id, "set")?;
+        check_component_registered::<T>(self.world)?;
+        Ok(self.set(component))
+    }
+
     /// Sets the data of the specified id. Can be a pair or Component.
     ///
     /// # Safety
@@ -455,6 +618,37 @@ impl<'a> EntityView<'a> {
         self
     }
 
+    /// Sets the data of the specified id, returning an error instead of panicking when the
+    /// entity is not alive or the data type does not match the id type.
+    ///
+    /// # See also
+    ///
+    /// * [`EntityView::set_id`]
+    pub fn try_set_id<T>(self, data: T, id: impl IntoId) -> Result<Self, FlecsError>
+    where
+        T: ComponentId + DataComponent,
+    {
+        let world = self.world.world_ptr_mut();
+        check_entity_alive(world, *self.id, "set")?;
+        check_component_registered::<T>(self.world)?;
+
+        let id = *id.into_id(self.world);
+        check_id_valid(world, id, false, "set")?;
+
+        let data_id = T::entity_id(self.world);
+        let id_data_id = unsafe { sys::ecs_get_typeid(world, id) };
+
+        if data_id != id_data_id {
+            return Err(FlecsError::new(
+                FlecsErrorCode::InvalidParameter,
+                "data type does not match id type. For pairs this is the first element occurrence that is not a zero-sized type (ZST)",
+            ));
+        }
+
+        set_helper(world, *self.id, data, id);
+        Ok(self)
+    }
+
     /// Set a pair for an entity.
     /// This operation sets the pair value, and uses the first non tag / ZST as type.
     /// If the data is an flecs enum (Repr(C)), it will use the enum variant id.
@@ -656,6 +850,30 @@ impl<'a> EntityView<'a> {
         self
     }
 
+    /// assign a component for an entity.
+    /// This operation sets the component value. Returns an error if the entity is not
+    /// alive or did not yet have the component.
+    pub fn try_assign<T: ComponentId + DataComponent>(self, value: T) -> Result<Self, FlecsError> {
+        let world = self.world.world_ptr_mut();
+        check_entity_alive(world, *self.id, "assign")?;
+        check_component_registered::<T>(self.world)?;
+
+        let id = T::entity_id(self.world);
+        if !unsafe { sys::ecs_has_id(world, *self.id, id) } {
+            return Err(FlecsError::new(
+                FlecsErrorCode::InvalidOperation,
+                format!(
+                    "entity #{} does not have component {}",
+                    *self.id,
+                    <T as ComponentOrPairId>::name()
+                ),
+            ));
+        }
+
+        assign_helper(world, *self.id, value, id);
+        Ok(self)
+    }
+
     /// assign a component for an entity.
     /// This operation sets the component value. If the entity did not yet have
     /// the component the operation will panic.
@@ -781,6 +999,49 @@ impl<'a> EntityView<'a> {
         self
     }
 
+    /// Sets the name of the entity, returning an error instead of aborting when the
+    /// entity is not alive or another entity in the same scope already has the name.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - A string slice that holds the name to be set.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use flecs_ecs::prelude::*;
+    ///
+    /// let world = World::new();
+    ///
+    /// world.entity_named("foo");
+    ///
+    /// let err = world.entity().try_set_name("foo").unwrap_err();
+    /// assert_eq!(err.code(), FlecsErrorCode::NameInUse);
+    /// ```
+    pub fn try_set_name(self, name: &str) -> Result<Self, FlecsError> {
+        let world = self.world.world_ptr_mut();
+        check_entity_alive(world, *self.id, "set_name")?;
+
+        let name = compact_str::format_compact!("{}\0", name);
+        let parent = unsafe { sys::ecs_get_target(world, *self.id, ECS_CHILD_OF, 0) };
+        // the same index that `ecs_set_name` adds the name to, so the name is matched literally
+        let existing = unsafe { sys::ecs_lookup_child(world, parent, name.as_ptr() as *const _) };
+
+        if existing != 0 && existing != *self.id {
+            return Err(FlecsError::new(
+                FlecsErrorCode::NameInUse,
+                format!(
+                    "cannot assign name '{}' to entity #{}, name already used by entity #{existing}",
+                    name.trim_end_matches('\0'),
+                    *self.id
+                ),
+            ));
+        }
+
+        unsafe { sys::ecs_set_name(world, *self.id, name.as_ptr() as *const _) };
+        Ok(self)
+    }
+
     /// Removes the name of the entity.
     pub fn remove_name(self) -> Self {
         unsafe {
@@ -1016,6 +1277,13 @@ impl<'a> EntityView<'a> {
         unsafe { sys::ecs_delete(self.world.world_ptr_mut(), *self.id) }
     }
 
+    /// Delete an entity, returning an error instead of panicking when the entity is not alive.
+    pub fn try_destruct(self) -> Result<(), FlecsError> {
+        check_entity_alive(self.world.world_ptr(), *self.id, "delete")?;
+        self.destruct();
+        Ok(())
+    }
+
     /// Set child order.
     /// Changes the order of children as returned by [`EntityView::each_child()`].
     /// Only applicable to entities with the [`flecs::OrderedChildren`] trait.
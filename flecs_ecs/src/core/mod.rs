@@ -15,8 +15,13 @@ pub mod id_view;
 pub mod observer;
 pub mod observer_builder;
 pub mod query;
+mod query_binding;
 pub mod query_builder;
+pub mod query_chunks;
+pub mod query_expr;
 pub mod query_iter;
+pub mod query_par;
+pub mod query_plan;
 pub(crate) mod query_tuple;
 #[cfg(feature = "flecs_safety_locks")]
 mod safety_map;
@@ -46,9 +51,14 @@ pub use id_view::IdView;
 pub use observer::Observer;
 pub use observer_builder::ObserverBuilder;
 pub use query::Query;
+pub(crate) use query_binding::*;
 #[doc(hidden)]
 pub use query_builder::*;
+pub use query_chunks::*;
+pub use query_expr::QueryParseError;
 pub use query_iter::QueryIter;
+pub use query_par::{OsApiThreads, QueryThreadPool};
+pub use query_plan::*;
 #[doc(hidden)]
 pub use query_tuple::*;
 #[cfg(feature = "flecs_safety_locks")]
@@ -62,4 +72,5 @@ pub use utility::*;
 pub(crate) use world::FlecsArray;
 pub use world::World;
 pub use world::WorldGet;
+pub use world::WorldSnapshot;
 pub(crate) use world_ctx::*;
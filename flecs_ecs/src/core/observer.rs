@@ -102,6 +102,14 @@ impl<'a> Observer<'a> {
 
     /// Set the context for the observer
     pub fn set_context(&mut self, context: *mut c_void) {
+        // a typed context is dropped here, as its free function must not be used for `context`
+        let observer = unsafe {
+            sys::ecs_observer_get(self.world.world_ptr_mut(), *self.id) as *mut sys::ecs_observer_t
+        };
+        if let Some(observer) = unsafe { observer.as_mut() } {
+            TypedCtx::clear(&mut observer.ctx, &mut observer.ctx_free);
+        }
+
         let desc: sys::ecs_observer_desc_t = sys::ecs_observer_desc_t {
             entity: *self.id,
             ctx: context,
@@ -618,6 +618,18 @@ where
         world: impl WorldProvider<'a>,
         desc: &mut sys::ecs_query_desc_t,
     ) -> Self {
+        Self::try_new_from_desc(world, desc).unwrap_or_else(|| {
+            panic!(
+                "Failed to create query, this is due to the user creating an invalid query. Most likely by using `expr` with a wrong expression."
+            )
+        })
+    }
+
+    /// Create a new query from a query descriptor, or `None` if flecs rejects the descriptor.
+    pub(crate) fn try_new_from_desc<'a>(
+        world: impl WorldProvider<'a>,
+        desc: &mut sys::ecs_query_desc_t,
+    ) -> Option<Self> {
         if desc.entity != 0 && desc.terms[0].id == 0 {
             let world_ptr = world.world_ptr();
             let query_poly = unsafe {
@@ -636,11 +648,11 @@ where
                     (*world_ctx).inc_query_ref_count();
                     let world_ctx = NonNull::new_unchecked(world_ctx);
 
-                    return Self {
+                    return Some(Self {
                         query,
                         world_ctx,
                         _phantom: PhantomData,
-                    };
+                    });
                 }
             }
         }
@@ -649,9 +661,7 @@ where
         let query_ptr = unsafe { sys::ecs_query_init(world_ptr, desc) };
 
         if query_ptr.is_null() {
-            panic!(
-                "Failed to create query, this is due to the user creating an invalid query. Most likely by using `expr` with a wrong expression."
-            );
+            return None;
         }
 
         unsafe {
@@ -661,11 +671,11 @@ where
 
             let query = NonNull::new_unchecked(query_ptr);
 
-            Self {
+            Some(Self {
                 query,
                 world_ctx,
                 _phantom: PhantomData,
-            }
+            })
         }
     }
 
@@ -787,6 +797,24 @@ where
             core::ptr::null_mut()
         }
     }
+
+    /// Get the typed context for a group
+    ///
+    /// # Type Parameters
+    ///
+    /// * `C` - The type of the group context
+    ///
+    /// # Arguments
+    ///
+    /// * `group_id` - The group id to get context for
+    ///
+    /// # Returns
+    ///
+    /// The context created by [`QueryBuilderImpl::on_group_create_with()`], or `None` if the group
+    /// doesn't exist or the context is not of type `C`.
+    pub fn group_ctx<C: 'static>(&self, group_id: impl Into<Entity>) -> Option<&C> {
+        QueryBindingCtx::group_ctx::<C>(self.query.as_ptr(), *group_id.into())
+    }
 }
 
 impl<T: QueryTuple> From<&Query<T>> for NonNull<sys::ecs_query_t> {
@@ -0,0 +1,269 @@
+#![doc(hidden)]
+//! Contexts that connect Rust closures to the callbacks of a query.
+
+use core::any::Any;
+use core::ffi::c_void;
+use core::ptr::NonNull;
+use core::sync::atomic::{AtomicIsize, Ordering};
+
+use flecs_ecs_derive::extern_abi;
+
+use crate::core::*;
+use crate::sys;
+
+extern crate alloc;
+use alloc::boxed::Box;
+
+type GroupByFn = Box<dyn FnMut(WorldRef, Table, Entity) -> u64>;
+type GroupCreateFn = Box<dyn FnMut(WorldRef, u64) -> Box<dyn Any>>;
+type GroupDeleteFn = Box<dyn FnMut(WorldRef, u64, Box<dyn Any>)>;
+
+/// Binding context of a query, stored in `ecs_query_t::binding_ctx`.
+#[derive(Default)]
+pub(crate) struct QueryBindingCtx {
+    /// Whether group contexts are created by [`GroupByBindingCtx`].
+    pub(crate) typed_group_ctx: bool,
+    /// Whether the query context is a [`TypedCtx`].
+    pub(crate) typed_ctx: bool,
+}
+
+impl QueryBindingCtx {
+    /// Returns the binding context of a query that is being built, creating it if it doesn't exist yet.
+    pub(crate) fn ensure(desc: &mut sys::ecs_query_desc_t) -> &mut Self {
+        if desc.binding_ctx.is_null() {
+            desc.binding_ctx = Box::into_raw(Box::<Self>::default()) as *mut c_void;
+            desc.binding_ctx_free = Some(Self::free);
+        }
+        unsafe { &mut *(desc.binding_ctx as *mut Self) }
+    }
+
+    /// Returns the binding context of a query.
+    pub(crate) fn get<'a>(query: *const sys::ecs_query_t) -> Option<&'a Self> {
+        let query = unsafe { query.as_ref()? };
+        unsafe { (query.binding_ctx as *const Self).as_ref() }
+    }
+
+    /// Returns the group context of a group, if it was created by [`GroupByBindingCtx`]
+    /// and is of type `T`.
+    pub(crate) fn group_ctx<'a, T: 'static>(
+        query: *const sys::ecs_query_t,
+        group_id: u64,
+    ) -> Option<&'a T> {
+        if !Self::get(query)?.typed_group_ctx {
+            return None;
+        }
+        let ctx = unsafe { sys::ecs_query_get_group_ctx(query, group_id) as *const Box<dyn Any> };
+        unsafe { ctx.as_ref()? }.downcast_ref::<T>()
+    }
+
+    /// Returns the typed context of a query, if it was set with [`QueryBuilder::ctx()`].
+    pub(crate) fn typed_ctx(query: *const sys::ecs_query_t) -> Option<*mut TypedCtx> {
+        if !Self::get(query)?.typed_ctx {
+            return None;
+        }
+        let ctx = unsafe { (*query).ctx as *mut TypedCtx };
+        (!ctx.is_null()).then_some(ctx)
+    }
+
+    #[extern_abi]
+    fn free(ctx: *mut c_void) {
+        unsafe { drop(Box::from_raw(ctx as *mut Self)) };
+    }
+}
+
+/// Owned context of a query, system or observer, stored in the `ctx` field of its descriptor.
+///
+/// The same context can be accessed by nested or concurrent iterations of its object, so
+/// borrows are tracked at runtime like a `RefCell`.
+pub(crate) struct TypedCtx {
+    value: Box<dyn Any + Send + Sync>,
+    type_name: &'static str,
+    /// Number of shared borrows, or -1 while the context is mutably borrowed.
+    borrow: AtomicIsize,
+}
+
+impl TypedCtx {
+    /// Free function of a typed context, used to recognize contexts that are a [`TypedCtx`].
+    pub(crate) const FREE: sys::ecs_ctx_free_t = Some(Self::free);
+
+    /// Replaces the context of a descriptor with a typed context, dropping the previous
+    /// typed context if there was one.
+    pub(crate) fn set<T: Send + Sync + 'static>(
+        ctx: &mut *mut c_void,
+        ctx_free: &mut sys::ecs_ctx_free_t,
+        value: T,
+    ) {
+        Self::clear(ctx, ctx_free);
+        *ctx = Box::into_raw(Box::new(Self {
+            value: Box::new(value),
+            type_name: core::any::type_name::<T>(),
+            borrow: AtomicIsize::new(0),
+        })) as *mut c_void;
+        *ctx_free = Self::FREE;
+    }
+
+    /// Drops the context of a descriptor or object if it is a typed context.
+    ///
+    /// # Panics
+    ///
+    /// Panics when the context is borrowed, such as when it is replaced from a callback that
+    /// is accessing it.
+    pub(crate) fn clear(ctx: &mut *mut c_void, ctx_free: &mut sys::ecs_ctx_free_t) {
+        if Self::is_typed(*ctx_free) {
+            if !ctx.is_null() {
+                let borrow = unsafe { &(*(*ctx as *const Self)).borrow };
+                assert!(
+                    borrow.load(Ordering::Acquire) == 0,
+                    "cannot replace a context while it is borrowed"
+                );
+                Self::free(*ctx);
+            }
+            *ctx = core::ptr::null_mut();
+            *ctx_free = None;
+        }
+    }
+
+    /// Returns whether a context with this free function is a [`TypedCtx`].
+    pub(crate) fn is_typed(ctx_free: sys::ecs_ctx_free_t) -> bool {
+        ctx_free.map(|f| f as usize) == Self::FREE.map(|f| f as usize)
+    }
+
+    /// Returns the value of the context if it is of type `T`.
+    pub(crate) fn downcast_ref<T: 'static>(&self) -> Option<&T> {
+        self.value.downcast_ref::<T>()
+    }
+
+    /// Returns a pointer to the value of the context if it is of type `T`.
+    ///
+    /// # Safety
+    ///
+    /// `ctx` must point to a live typed context.
+    pub(crate) unsafe fn downcast_ptr<T: 'static>(ctx: *mut Self) -> Option<NonNull<T>> {
+        let value = unsafe { &mut (*ctx).value };
+        value.downcast_mut::<T>().map(NonNull::from)
+    }
+
+    /// Adds a shared borrow, returns `false` if the context is mutably borrowed.
+    pub(crate) fn try_borrow(&self) -> bool {
+        self.borrow
+            .fetch_update(Ordering::Acquire, Ordering::Relaxed, |borrow| {
+                (borrow >= 0).then_some(borrow + 1)
+            })
+            .is_ok()
+    }
+
+    /// Releases a shared borrow.
+    pub(crate) fn release(&self) {
+        self.borrow.fetch_sub(1, Ordering::Release);
+    }
+
+    /// Adds a mutable borrow, returns `false` if the context is borrowed.
+    pub(crate) fn try_borrow_mut(&self) -> bool {
+        self.borrow
+            .compare_exchange(0, -1, Ordering::Acquire, Ordering::Relaxed)
+            .is_ok()
+    }
+
+    /// Releases a mutable borrow.
+    pub(crate) fn release_mut(&self) {
+        self.borrow.store(0, Ordering::Release);
+    }
+
+    /// Returns the name of the type of the value, used in error messages.
+    pub(crate) fn type_name(&self) -> &'static str {
+        self.type_name
+    }
+
+    #[extern_abi]
+    fn free(ctx: *mut c_void) {
+        unsafe { drop(Box::from_raw(ctx as *mut Self)) };
+    }
+}
+
+/// Closures for grouping matched tables, stored in `ecs_query_desc_t::group_by_ctx`.
+#[derive(Default)]
+pub(crate) struct GroupByBindingCtx {
+    pub(crate) group_by: Option<GroupByFn>,
+    pub(crate) on_create: Option<GroupCreateFn>,
+    pub(crate) on_delete: Option<GroupDeleteFn>,
+}
+
+impl GroupByBindingCtx {
+    /// Returns the group by context of a query that is being built, creating it if it doesn't
+    /// exist yet. This replaces a context that was set with `group_by_ctx`.
+    pub(crate) fn ensure(desc: &mut sys::ecs_query_desc_t) -> &mut Self {
+        let free: sys::ecs_ctx_free_t = Some(Self::free);
+        let is_binding_ctx = desc.group_by_ctx_free.map(|f| f as usize) == free.map(|f| f as usize);
+
+        if !is_binding_ctx {
+            if let Some(ctx_free) = desc.group_by_ctx_free
+                && !desc.group_by_ctx.is_null()
+            {
+                unsafe { ctx_free(desc.group_by_ctx) };
+            }
+            desc.group_by_ctx = Box::into_raw(Box::<Self>::default()) as *mut c_void;
+            desc.group_by_ctx_free = Some(Self::free);
+        }
+
+        unsafe { &mut *(desc.group_by_ctx as *mut Self) }
+    }
+
+    /// Returns whether a group delete action is the one that drops typed group contexts.
+    pub(crate) fn is_on_group_delete(action: sys::ecs_group_delete_action_t) -> bool {
+        let on_group_delete: sys::ecs_group_delete_action_t = Some(Self::on_group_delete);
+        action.map(|f| f as usize) == on_group_delete.map(|f| f as usize)
+    }
+
+    #[extern_abi]
+    pub(crate) fn group_by(
+        world: *mut sys::ecs_world_t,
+        table: *mut sys::ecs_table_t,
+        id: sys::ecs_id_t,
+        ctx: *mut c_void,
+    ) -> u64 {
+        let ctx = unsafe { &mut *(ctx as *mut Self) };
+        let world = unsafe { WorldRef::from_ptr(world) };
+        let table = Table::new(world, NonNull::new(table).expect("table is null"));
+        let group_by = ctx.group_by.as_mut().expect("group_by callback is not set");
+        group_by(world, table, Entity::new(id))
+    }
+
+    #[extern_abi]
+    pub(crate) fn on_group_create(
+        world: *mut sys::ecs_world_t,
+        group_id: u64,
+        ctx: *mut c_void,
+    ) -> *mut c_void {
+        let ctx = unsafe { &mut *(ctx as *mut Self) };
+        let world = unsafe { WorldRef::from_ptr(world) };
+        let on_create = ctx
+            .on_create
+            .as_mut()
+            .expect("on_group_create callback is not set");
+        Box::into_raw(Box::new(on_create(world, group_id))) as *mut c_void
+    }
+
+    #[extern_abi]
+    pub(crate) fn on_group_delete(
+        world: *mut sys::ecs_world_t,
+        group_id: u64,
+        group_ctx: *mut c_void,
+        ctx: *mut c_void,
+    ) {
+        let ctx = unsafe { &mut *(ctx as *mut Self) };
+        if group_ctx.is_null() {
+            return;
+        }
+
+        let group_ctx = unsafe { *Box::from_raw(group_ctx as *mut Box<dyn Any>) };
+        if let Some(on_delete) = ctx.on_delete.as_mut() {
+            let world = unsafe { WorldRef::from_ptr(world) };
+            on_delete(world, group_id, group_ctx);
+        }
+    }
+
+    #[extern_abi]
+    fn free(ctx: *mut c_void) {
+        unsafe { drop(Box::from_raw(ctx as *mut Self)) };
+    }
+}
@@ -301,6 +301,7 @@
 //! [`QueryCacheKind::All`]
 //! [`QueryCacheKind::None`]
 
+use core::any::Any;
 use core::ffi::c_void;
 use core::mem::ManuallyDrop;
 
@@ -312,7 +313,7 @@ use crate::sys;
 extern crate std;
 
 extern crate alloc;
-use alloc::{format, vec::Vec};
+use alloc::{boxed::Box, format, vec::Vec};
 use flecs_ecs_derive::extern_abi;
 
 /// Builder for constructing complex [`Query`] objects.
@@ -539,6 +540,61 @@ where
         T::populate(&mut obj);
         obj
     }
+
+    /// Set a typed context that is owned by the query.
+    ///
+    /// The context can be accessed while iterating with [`TableIter::ctx()`] and
+    /// [`TableIter::ctx_mut()`], and is dropped when the query is deleted.
+    ///
+    /// # Type Parameters
+    ///
+    /// * `C` - The type of the context. It must be `Send + Sync`, as the query can be
+    ///   iterated from several threads at once.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The context value
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use flecs_ecs::prelude::*;
+    ///
+    /// #[derive(Component)]
+    /// struct Position {
+    ///     x: f32,
+    ///     y: f32,
+    /// }
+    ///
+    /// struct Scale(f32);
+    ///
+    /// let world = World::new();
+    ///
+    /// let query = world
+    ///     .query::<&mut Position>()
+    ///     .ctx(Scale(2.0))
+    ///     .build();
+    ///
+    /// let e = world.entity().set(Position { x: 1.0, y: 2.0 });
+    ///
+    /// query.run(|mut it| {
+    ///     while it.next() {
+    ///         let scale = it.ctx::<Scale>().0;
+    ///         let mut pos = it.field_mut::<Position>(0);
+    ///         for i in it.iter() {
+    ///             pos[i].x *= scale;
+    ///             pos[i].y *= scale;
+    ///         }
+    ///     }
+    /// });
+    ///
+    /// e.get::<&Position>(|pos| assert_eq!((pos.x, pos.y), (2.0, 4.0)));
+    /// ```
+    pub fn ctx<C: Send + Sync + 'static>(&mut self, value: C) -> &mut Self {
+        TypedCtx::set(&mut self.desc.ctx, &mut self.desc.ctx_free, value);
+        QueryBindingCtx::ensure(&mut self.desc).typed_ctx = true;
+        self
+    }
 }
 
 #[doc(hidden)]
@@ -1123,6 +1179,14 @@ pub trait QueryBuilderImpl<'a>: TermBuilderImpl<'a> {
     fn on_group_create(&mut self, action: sys::ecs_group_create_action_t) -> &mut Self {
         let desc = self.query_desc_mut();
         desc.on_group_create = action;
+        if !desc.binding_ctx.is_null() {
+            let binding_ctx = QueryBindingCtx::ensure(desc);
+            let was_typed = core::mem::replace(&mut binding_ctx.typed_group_ctx, false);
+            // group contexts are no longer created by Rust, so they can't be dropped by it
+            if was_typed {
+                desc.on_group_delete = None;
+            }
+        }
         self
     }
 
@@ -1131,11 +1195,237 @@ pub trait QueryBuilderImpl<'a>: TermBuilderImpl<'a> {
     /// # Arguments
     ///
     /// * `action`: The action to execute when a group is deleted.
+    ///
+    /// # Panics
+    ///
+    /// Panics when the group contexts are created by [`QueryBuilderImpl::on_group_create_with()`],
+    /// as they are dropped by the query. Use [`QueryBuilderImpl::on_group_create_delete_with()`]
+    /// to be notified when those are deleted.
     fn on_group_delete(&mut self, action: sys::ecs_group_delete_action_t) -> &mut Self {
         let desc = self.query_desc_mut();
+        ecs_assert!(
+            !GroupByBindingCtx::is_on_group_delete(desc.on_group_delete),
+            FlecsErrorCode::InvalidParameter,
+            "on_group_delete can't be combined with on_group_create_with, use on_group_create_delete_with instead"
+        );
         desc.on_group_delete = action;
         self
     }
+
+    /// Group and sort matched tables with a closure.
+    ///
+    /// This is the closure based version of [`QueryBuilderImpl::group_by_fn()`]. The closure
+    /// is invoked for every table matched by the query and returns the id of the group the
+    /// table belongs to. Tables are iterated in ascending order of group id.
+    ///
+    /// The closure is stored in the `group_by` context of the query, which means it can't be
+    /// combined with [`QueryBuilderImpl::group_by_ctx()`] or the raw group callbacks.
+    ///
+    /// # Arguments
+    ///
+    /// * `component`: The component passed to the closure.
+    /// * `group_by`: Closure that receives the world, the matched table and `component`
+    ///   and returns the group id for the table.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use flecs_ecs::prelude::*;
+    ///
+    /// #[derive(Component)]
+    /// struct Position {
+    ///     x: f32,
+    ///     y: f32,
+    /// }
+    ///
+    /// #[derive(Component)]
+    /// struct Red;
+    ///
+    /// #[derive(Component)]
+    /// struct Blue;
+    ///
+    /// let world = World::new();
+    ///
+    /// world.entity().set(Position { x: 0.0, y: 0.0 }).add(Blue);
+    /// world.entity().set(Position { x: 0.0, y: 0.0 }).add(Red);
+    ///
+    /// let query = world
+    ///     .query::<&Position>()
+    ///     .group_by_with(Red, |_world, table, red| if table.has(red) { 1 } else { 2 })
+    ///     .build();
+    ///
+    /// let mut groups = Vec::new();
+    /// query.run(|mut it| {
+    ///     while it.next() {
+    ///         groups.push(it.group_id());
+    ///     }
+    /// });
+    /// assert_eq!(groups, [1, 2]);
+    /// ```
+    ///
+    /// # See also
+    ///
+    /// * [`QueryBuilderImpl::on_group_create_with()`]
+    /// * [`QueryBuilderImpl::on_group_create_delete_with()`]
+    fn group_by_with(
+        &mut self,
+        component: impl IntoEntity,
+        group_by: impl FnMut(WorldRef, Table, Entity) -> u64 + 'static,
+    ) -> &mut Self {
+        let world = self.world();
+        let desc = self.query_desc_mut();
+        GroupByBindingCtx::ensure(desc).group_by = Some(Box::new(group_by));
+        desc.group_by_callback = Some(GroupByBindingCtx::group_by);
+        desc.group_by = *component.into_entity(world);
+        self
+    }
+
+    /// Create a typed context for every group of the query.
+    ///
+    /// The closure is invoked when the query creates a new group and returns the context for
+    /// that group. The context can be retrieved while iterating with [`TableIter::group_ctx()`]
+    /// or with [`Query::group_ctx()`], and is dropped when the group or the query is deleted.
+    ///
+    /// # Type Parameters
+    ///
+    /// * `T`: The type of the group context.
+    ///
+    /// # Arguments
+    ///
+    /// * `on_create`: Closure that receives the world and the group id, and returns the group context.
+    ///
+    /// # Panics
+    ///
+    /// Panics when a raw [`QueryBuilderImpl::on_group_delete()`] action is set, as it would be
+    /// replaced by the action that drops the group contexts.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use flecs_ecs::prelude::*;
+    ///
+    /// #[derive(Component)]
+    /// struct Position {
+    ///     x: f32,
+    ///     y: f32,
+    /// }
+    ///
+    /// struct CellInfo {
+    ///     name: String,
+    /// }
+    ///
+    /// let world = World::new();
+    ///
+    /// world.entity().set(Position { x: 5.0, y: 0.0 });
+    ///
+    /// let query = world
+    ///     .query::<&Position>()
+    ///     .group_by_with(Position::id(), |_world, table, _| table.count() as u64)
+    ///     .on_group_create_with(|_world, group_id| CellInfo {
+    ///         name: format!("cell {group_id}"),
+    ///     })
+    ///     .build();
+    ///
+    /// query.run(|mut it| {
+    ///     while it.next() {
+    ///         let info = it.group_ctx::<CellInfo>().unwrap();
+    ///         assert_eq!(info.name, format!("cell {}", it.group_id()));
+    ///     }
+    /// });
+    /// ```
+    ///
+    /// # See also
+    ///
+    /// * [`QueryBuilderImpl::group_by_with()`]
+    /// * [`QueryBuilderImpl::on_group_create_delete_with()`]
+    fn on_group_create_with<T: 'static>(
+        &mut self,
+        on_create: impl FnMut(WorldRef, u64) -> T + 'static,
+    ) -> &mut Self {
+        self.on_group_create_delete_with(on_create, |_, _, _: T| {})
+    }
+
+    /// Create a typed context for every group of the query, and take it back when the group
+    /// is deleted.
+    ///
+    /// This is [`QueryBuilderImpl::on_group_create_with()`] with a second closure that
+    /// receives ownership of the context when the group or the query is deleted. Both
+    /// closures are set together so that they always agree on the type of the context.
+    ///
+    /// # Type Parameters
+    ///
+    /// * `T`: The type of the group context.
+    ///
+    /// # Arguments
+    ///
+    /// * `on_create`: Closure that receives the world and the group id, and returns the group context.
+    /// * `on_delete`: Closure that receives the world, the group id and the group context.
+    ///
+    /// # Panics
+    ///
+    /// Panics when a raw [`QueryBuilderImpl::on_group_delete()`] action is set, as it would be
+    /// replaced by the action that drops the group contexts.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use flecs_ecs::prelude::*;
+    ///
+    /// #[derive(Component)]
+    /// struct Position {
+    ///     x: f32,
+    ///     y: f32,
+    /// }
+    ///
+    /// let world = World::new();
+    ///
+    /// world.entity().set(Position { x: 5.0, y: 0.0 });
+    ///
+    /// let query = world
+    ///     .query::<&Position>()
+    ///     .group_by_with(Position::id(), |_world, _table, _| 1)
+    ///     .on_group_create_delete_with(
+    ///         |_world, group_id| format!("cell {group_id}"),
+    ///         |_world, _group_id, name: String| assert_eq!(name, "cell 1"),
+    ///     )
+    ///     .build();
+    ///
+    /// assert_eq!(query.group_ctx::<String>(1).unwrap(), "cell 1");
+    /// query.destruct();
+    /// ```
+    ///
+    /// # See also
+    ///
+    /// * [`QueryBuilderImpl::group_by_with()`]
+    /// * [`QueryBuilderImpl::on_group_create_with()`]
+    fn on_group_create_delete_with<T: 'static>(
+        &mut self,
+        mut on_create: impl FnMut(WorldRef, u64) -> T + 'static,
+        mut on_delete: impl FnMut(WorldRef, u64, T) + 'static,
+    ) -> &mut Self {
+        let desc = self.query_desc_mut();
+        ecs_assert!(
+            desc.on_group_delete.is_none()
+                || GroupByBindingCtx::is_on_group_delete(desc.on_group_delete),
+            FlecsErrorCode::InvalidParameter,
+            "on_group_create_with would replace the on_group_delete action of the query"
+        );
+
+        let ctx = GroupByBindingCtx::ensure(desc);
+        ctx.on_create = Some(Box::new(move |world, group_id| {
+            Box::new(on_create(world, group_id))
+        }));
+        ctx.on_delete = Some(Box::new(move |world, group_id, ctx: Box<dyn Any>| {
+            // both closures are always replaced together, so the type matches
+            if let Ok(ctx) = ctx.downcast::<T>() {
+                on_delete(world, group_id, *ctx);
+            }
+        }));
+        desc.on_group_create = Some(GroupByBindingCtx::on_group_create);
+        desc.on_group_delete = Some(GroupByBindingCtx::on_group_delete);
+        QueryBindingCtx::ensure(desc).typed_group_ctx = true;
+        self
+    }
 }
 
 pub trait OrderByFn<T>
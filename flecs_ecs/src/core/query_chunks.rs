@@ -0,0 +1,402 @@
+//! Iterators over the results of a query, which yield the tables that the query matched, or the
+//! matched entities one by one.
+//!
+//! Unlike the callbacks of [`QueryAPI::each()`] and [`QueryAPI::run()`], the iterators work
+//! with iterator adapters such as `map`, `filter`, `zip` and `collect`, and can be stopped early
+//! with `break`.
+//!
+//! Each item keeps the table it was matched in locked until it's dropped, so that the table can't
+//! be changed while the item gives access to its components. When the `flecs_safety_locks`
+//! feature is enabled, the components are also locked for reading or writing, so that other
+//! queries can't access them in conflicting ways.
+
+use core::cell::UnsafeCell;
+use core::marker::PhantomData;
+
+use crate::core::*;
+use crate::sys;
+
+extern crate alloc;
+use alloc::{rc::Rc, vec::Vec};
+
+/// Whether the current result of the iterator writes a component that all its entities share,
+/// such as a singleton.
+pub(crate) fn writes_shared_fields(iter: &sys::ecs_iter_t) -> bool {
+    let query = unsafe { &*iter.query };
+    let shared_fields = (iter.ref_fields | iter.up_fields) & !iter.row_fields;
+    (shared_fields as u64) & (query.write_fields as u64) != 0
+}
+
+/// The components of a table that a query matched, which keeps the table locked until dropped.
+struct ChunkData<'q, T: QueryTuple> {
+    world: WorldRef<'q>,
+    table: *mut sys::ecs_table_t,
+    entities: *const sys::ecs_entity_t,
+    count: usize,
+    is_any_array: IsAnyArray,
+    pointers: UnsafeCell<T::Pointers>,
+    /// Pointers for each row, when the query has fields that aren't stored in table columns,
+    /// such as sparse components.
+    rows: Vec<UnsafeCell<T::Pointers>>,
+    #[cfg(feature = "flecs_safety_locks")]
+    any_sparse_terms: bool,
+}
+
+impl<'q, T: QueryTuple> ChunkData<'q, T> {
+    fn new(iter: &mut sys::ecs_iter_t, world: WorldRef<'q>) -> Self {
+        if writes_shared_fields(iter) {
+            panic!(
+                "query iterators can't give mutable access to components that all entities share, such as singletons. Use `each` or `run` instead."
+            );
+        }
+
+        iter.flags |= sys::EcsIterCppEach;
+        let (is_any_array, mut pointers) = T::create_ptrs(iter);
+        let count = if iter.count == 0 && iter.table.is_null() {
+            1_usize
+        } else {
+            iter.count as usize
+        };
+
+        // the pointers of fields that aren't stored in columns are looked up by the iterator,
+        // which moves on to the next table before the chunk is dropped
+        let rows = if is_any_array.a_row {
+            (0..count)
+                .map(|row| {
+                    let _ = pointers.get_tuple_with_row(iter, row);
+                    UnsafeCell::new(pointers.clone())
+                })
+                .collect()
+        } else {
+            Vec::new()
+        };
+
+        #[cfg(feature = "flecs_safety_locks")]
+        let any_sparse_terms = iter.row_fields != 0;
+        #[cfg(feature = "flecs_safety_locks")]
+        if any_sparse_terms {
+            do_read_write_locks::<INCREMENT, true, T>(&world, pointers.safety_table_records());
+        } else {
+            do_read_write_locks::<INCREMENT, false, T>(&world, pointers.safety_table_records());
+        }
+        table_lock(iter.world, iter.table);
+
+        Self {
+            world,
+            table: iter.table,
+            entities: iter.entities,
+            count,
+            is_any_array,
+            pointers: UnsafeCell::new(pointers),
+            rows,
+            #[cfg(feature = "flecs_safety_locks")]
+            any_sparse_terms,
+        }
+    }
+
+    fn entity(&self, row: usize) -> EntityView<'q> {
+        ecs_assert!(
+            !self.entities.is_null(),
+            FlecsErrorCode::InvalidOperation,
+            "query does not return entities ($this variable is not populated)"
+        );
+        EntityView::new_from(self.world, unsafe { *self.entities.add(row) })
+    }
+
+    /// # Safety
+    ///
+    /// The components must not be accessed through another tuple of the same row while the
+    /// returned tuple is alive.
+    unsafe fn tuple(&self, row: usize) -> T::TupleType<'_> {
+        assert!(
+            row < self.count,
+            "row {row} is out of range for a chunk of {} rows",
+            self.count
+        );
+        unsafe {
+            if self.is_any_array.a_row {
+                (*self.rows[row].get()).get_tuple_with_ref(row)
+            } else if self.is_any_array.a_ref {
+                (*self.pointers.get()).get_tuple_with_ref(row)
+            } else {
+                (*self.pointers.get()).get_tuple(row)
+            }
+        }
+    }
+}
+
+impl<T: QueryTuple> Drop for ChunkData<'_, T> {
+    fn drop(&mut self) {
+        table_unlock(self.world.world_ptr_mut(), self.table);
+
+        #[cfg(feature = "flecs_safety_locks")]
+        {
+            let pointers = self.pointers.get_mut();
+            if self.any_sparse_terms {
+                do_read_write_locks::<DECREMENT, true, T>(
+                    &self.world,
+                    pointers.safety_table_records(),
+                );
+            } else {
+                do_read_write_locks::<DECREMENT, false, T>(
+                    &self.world,
+                    pointers.safety_table_records(),
+                );
+            }
+        }
+    }
+}
+
+/// Iterator over the tables that a query matched, which is created with [`QueryAPI::iter()`].
+///
+/// Dropping the iterator before it's exhausted finishes the underlying query iterator.
+pub struct QueryChunks<'q, T: QueryTuple> {
+    iter: sys::ecs_iter_t,
+    iter_next: ExternIterNextFn,
+    world: WorldRef<'q>,
+    done: bool,
+    _phantom: PhantomData<T>,
+}
+
+impl<'q, T: QueryTuple> QueryChunks<'q, T> {
+    pub(crate) fn new(
+        iter: sys::ecs_iter_t,
+        iter_next: ExternIterNextFn,
+        world: WorldRef<'q>,
+    ) -> Self {
+        Self {
+            iter,
+            iter_next,
+            world,
+            done: false,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Iterate the matched entities one by one instead of by table.
+    pub fn rows(self) -> QueryRows<'q, T> {
+        QueryRows {
+            chunks: self,
+            chunk: None,
+            row: 0,
+        }
+    }
+}
+
+impl<'q, T: QueryTuple> Iterator for QueryChunks<'q, T> {
+    type Item = QueryChunk<'q, T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        // the query iterator finishes itself when it returns false
+        if !unsafe { (self.iter_next)(&mut self.iter) } {
+            self.done = true;
+            return None;
+        }
+        Some(QueryChunk {
+            data: Rc::new(ChunkData::new(&mut self.iter, self.world)),
+        })
+    }
+}
+
+impl<T: QueryTuple> Drop for QueryChunks<'_, T> {
+    fn drop(&mut self) {
+        if !self.done {
+            unsafe { sys::ecs_iter_fini(&mut self.iter) };
+        }
+    }
+}
+
+/// The entities of a table that a query matched, with their components.
+///
+/// The table stays locked until the chunk is dropped.
+///
+/// # Example
+///
+/// ```
+/// use flecs_ecs::prelude::*;
+///
+/// #[derive(Component)]
+/// struct Position {
+///     x: f32,
+/// }
+///
+/// #[derive(Component)]
+/// struct Velocity {
+///     x: f32,
+/// }
+///
+/// let world = World::new();
+/// for i in 0..3 {
+///     world
+///         .entity()
+///         .set(Position { x: 0.0 })
+///         .set(Velocity { x: i as f32 });
+/// }
+///
+/// let query = world.new_query::<(&mut Position, &Velocity)>();
+/// for mut chunk in query.iter() {
+///     let (positions, velocities) = chunk.columns();
+///     for (pos, vel) in positions.iter_mut().zip(velocities) {
+///         pos.x += vel.x;
+///     }
+/// }
+///
+/// let xs = query
+///     .iter()
+///     .flat_map(|mut chunk| chunk.columns().0.iter().map(|pos| pos.x).collect::<Vec<_>>())
+///     .collect::<Vec<_>>();
+/// assert_eq!(xs, [0.0, 1.0, 2.0]);
+/// ```
+pub struct QueryChunk<'q, T: QueryTuple> {
+    data: Rc<ChunkData<'q, T>>,
+}
+
+impl<'q, T: QueryTuple> QueryChunk<'q, T> {
+    /// Number of entities in the chunk.
+    pub fn count(&self) -> usize {
+        self.data.count
+    }
+
+    /// Whether the chunk has no entities.
+    pub fn is_empty(&self) -> bool {
+        self.data.count == 0
+    }
+
+    /// The ids of the entities in the chunk, or an empty slice if the query doesn't return
+    /// entities.
+    pub fn entities(&self) -> &[Entity] {
+        if self.data.entities.is_null() {
+            return &[];
+        }
+        unsafe { core::slice::from_raw_parts(self.data.entities as *const Entity, self.data.count) }
+    }
+
+    /// The entity in a row of the chunk.
+    ///
+    /// # Arguments
+    ///
+    /// * `row` - The row, smaller than [`QueryChunk::count()`].
+    pub fn entity(&self, row: usize) -> EntityView<'q> {
+        assert!(
+            row < self.data.count,
+            "row {row} is out of range for a chunk of {} rows",
+            self.data.count
+        );
+        self.data.entity(row)
+    }
+
+    /// The table of the chunk, or `None` if the query doesn't match a table.
+    pub fn table(&self) -> Option<Table<'q>> {
+        core::ptr::NonNull::new(self.data.table).map(|table| Table::new(self.data.world, table))
+    }
+
+    /// The components of the entity in a row of the chunk.
+    ///
+    /// # Arguments
+    ///
+    /// * `row` - The row, smaller than [`QueryChunk::count()`].
+    pub fn get(&mut self, row: usize) -> T::TupleType<'_> {
+        unsafe { self.data.tuple(row) }
+    }
+
+    /// Iterate the components of the entities in the chunk.
+    pub fn iter_mut(&mut self) -> impl ExactSizeIterator<Item = T::TupleType<'_>> + '_ {
+        let data = &self.data;
+        (0..data.count).map(|row| unsafe { data.tuple(row) })
+    }
+
+    /// The columns of the components of the chunk, as slices with an element for each entity.
+    /// Components that all entities share, such as singletons, are slices with one element.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the query has fields that aren't stored in table columns, such as sparse
+    /// components. Use [`QueryChunk::iter_mut()`] for those.
+    pub fn columns(&mut self) -> T::TupleSliceType<'_> {
+        assert!(
+            !self.data.is_any_array.a_row,
+            "the query has fields that aren't stored in table columns, such as sparse components"
+        );
+        unsafe { (*self.data.pointers.get()).get_slices(self.data.count) }
+    }
+}
+
+/// Iterator over the entities that a query matched, which is created with
+/// [`QueryAPI::iter_rows()`].
+pub struct QueryRows<'q, T: QueryTuple> {
+    chunks: QueryChunks<'q, T>,
+    chunk: Option<Rc<ChunkData<'q, T>>>,
+    row: usize,
+}
+
+impl<'q, T: QueryTuple> Iterator for QueryRows<'q, T> {
+    type Item = QueryRow<'q, T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(chunk) = &self.chunk
+                && self.row < chunk.count
+            {
+                let row = self.row;
+                self.row += 1;
+                return Some(QueryRow {
+                    data: chunk.clone(),
+                    row,
+                });
+            }
+            self.chunk = Some(self.chunks.next()?.data);
+            self.row = 0;
+        }
+    }
+}
+
+/// An entity that a query matched, with its components.
+///
+/// The table of the entity stays locked until the row is dropped.
+///
+/// # Example
+///
+/// ```
+/// use flecs_ecs::prelude::*;
+///
+/// #[derive(Component)]
+/// struct Health(u32);
+///
+/// let world = World::new();
+/// world.entity_named("a").set(Health(0));
+/// world.entity_named("b").set(Health(5));
+///
+/// let query = world.new_query::<&mut Health>();
+/// let alive = query
+///     .iter_rows()
+///     .filter_map(|mut row| (row.get().0 > 0).then(|| row.entity().name()))
+///     .collect::<Vec<_>>();
+/// assert_eq!(alive, ["b"]);
+///
+/// for mut row in query.iter_rows() {
+///     let health = row.get();
+///     if health.0 == 0 {
+///         health.0 = 10;
+///         break;
+///     }
+/// }
+/// ```
+pub struct QueryRow<'q, T: QueryTuple> {
+    data: Rc<ChunkData<'q, T>>,
+    row: usize,
+}
+
+impl<'q, T: QueryTuple> QueryRow<'q, T> {
+    /// The entity.
+    pub fn entity(&self) -> EntityView<'q> {
+        self.data.entity(self.row)
+    }
+
+    /// The components of the entity.
+    pub fn get(&mut self) -> T::TupleType<'_> {
+        unsafe { self.data.tuple(self.row) }
+    }
+}
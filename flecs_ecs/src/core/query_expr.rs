@@ -0,0 +1,269 @@
+//! Checked creation of queries from the flecs query DSL.
+//!
+//! [`QueryBuilder::expr()`](crate::core::QueryBuilder::expr) passes a query expression such as
+//! `"Position, [in] Velocity(up ChildOf), !Dead"` to flecs as is, so a mistake in the expression
+//! only shows up as a panic in `build()`. [`World::try_query_from_expr()`] and
+//! [`World::validate_query_expr()`] return a [`QueryParseError`] instead, which points at the
+//! term that flecs rejected.
+
+use core::ffi::c_char;
+use core::fmt::{Display, Formatter};
+
+use crate::core::*;
+use crate::sys;
+
+extern crate alloc;
+use alloc::{borrow::ToOwned, ffi::CString, string::String, vec::Vec};
+
+/// Error returned when flecs rejects a query expression.
+///
+/// The diagnostic that flecs would otherwise log is captured and split into a message, the
+/// location of the offending token and the term that contains it.
+///
+/// # Example
+///
+/// ```
+/// use flecs_ecs::prelude::*;
+///
+/// let world = World::new();
+///
+/// let err = world.validate_query_expr("ChildOf, (IsA, ").unwrap_err();
+/// assert_eq!(err.message(), "unexpected end of script");
+/// assert_eq!(err.column(), Some(15));
+/// assert_eq!(err.expected(), Some("an identifier"));
+/// assert_eq!(err.term(), Some("(IsA,"));
+/// assert_eq!(err.to_string(), "1:15: unexpected end of script, expected an identifier");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QueryParseError {
+    expr: String,
+    message: String,
+    line: Option<u32>,
+    column: Option<u32>,
+    expected: Option<&'static str>,
+    term: Option<(usize, usize)>,
+}
+
+impl QueryParseError {
+    /// Creates an error from a diagnostic captured from the flecs log.
+    ///
+    /// The `^` of syntax errors is under the end of the offending token. Errors about names that
+    /// don't resolve only contain a message with the name in quotes.
+    pub(crate) fn from_log(expr: &str, log: Option<&str>) -> Self {
+        let diagnostic = Diagnostic::parse(log);
+        let message = diagnostic.message_or("invalid query expression");
+
+        let caret = diagnostic
+            .line
+            .zip(diagnostic.caret)
+            .map(|(line, column)| line_offset(expr, line) + column);
+
+        let offset = if message.starts_with("unexpected end of") {
+            Some(expr.trim_end().len())
+        } else if let Some(caret) = caret {
+            Some(token_start(expr, caret.min(expr.len())))
+        } else {
+            quoted_name(message).and_then(|name| find_name(expr, name))
+        };
+
+        Self::at(expr, message, offset)
+    }
+
+    /// Creates an error for the token of `expr` at byte `offset`, if it is known.
+    fn at(expr: &str, message: &str, offset: Option<usize>) -> Self {
+        let mut err = Self {
+            expr: expr.to_owned(),
+            message: message.to_owned(),
+            line: None,
+            column: None,
+            expected: None,
+            term: None,
+        };
+
+        if let Some(offset) = offset {
+            let line_start = expr[..offset].rfind('\n').map_or(0, |index| index + 1);
+            err.line = Some(expr[..offset].matches('\n').count() as u32 + 1);
+            err.column = Some((expr[line_start..offset].chars().count() + 1) as u32);
+            err.term = term_at(expr, offset);
+            if message.starts_with("unexpected") {
+                err.expected = Some(expected_at(expr, offset));
+            }
+        }
+
+        err
+    }
+
+    /// Human readable description of what went wrong.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// The line in the expression at which the error occurred, starting at 1, if it is known.
+    pub fn line(&self) -> Option<u32> {
+        self.line
+    }
+
+    /// The column in the line at which the offending token starts, starting at 1, if it is known.
+    ///
+    /// When the expression ended too early, this is the column after its last character.
+    pub fn column(&self) -> Option<u32> {
+        self.column
+    }
+
+    /// What the parser expected instead of the offending token, such as `"')'"` or
+    /// `"an identifier"`, if the error is a syntax error.
+    ///
+    /// flecs doesn't report this, so it is a guess based on the tokens before the offending
+    /// token and the delimiters that are still open. It can be wrong for unusual expressions.
+    pub fn expected(&self) -> Option<&str> {
+        self.expected
+    }
+
+    /// The term of the expression that contains the offending token, if it is known and not
+    /// empty.
+    pub fn term(&self) -> Option<&str> {
+        self.term.map(|(start, end)| &self.expr[start..end])
+    }
+
+    /// The expression that failed to parse.
+    pub fn expr(&self) -> &str {
+        &self.expr
+    }
+}
+
+impl Display for QueryParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        if let (Some(line), Some(column)) = (self.line, self.column) {
+            write!(f, "{line}:{column}: ")?;
+        }
+        write!(f, "{}", self.message)?;
+        if let Some(expected) = self.expected {
+            write!(f, ", expected {expected}")?;
+        }
+        Ok(())
+    }
+}
+
+impl core::error::Error for QueryParseError {}
+
+fn is_name_char(c: char) -> bool {
+    c.is_alphanumeric() || matches!(c, '_' | '.' | '$' | ':' | '*' | '#')
+}
+
+/// Byte offset at which `line` of `expr` starts.
+fn line_offset(expr: &str, line: u32) -> usize {
+    if line <= 1 {
+        return 0;
+    }
+    expr.match_indices('\n')
+        .nth(line as usize - 2)
+        .map_or(0, |(index, _)| index + 1)
+}
+
+/// The caret of flecs points at the last character of a name, so walk back to its start.
+fn token_start(expr: &str, offset: usize) -> usize {
+    let is_name = |index: usize| expr[index..].chars().next().is_some_and(is_name_char);
+    if !expr.is_char_boundary(offset) || !is_name(offset) {
+        return offset;
+    }
+    expr[..offset]
+        .char_indices()
+        .rev()
+        .take_while(|&(_, c)| is_name_char(c))
+        .last()
+        .map_or(offset, |(index, _)| index)
+}
+
+/// The name in quotes in messages like `unresolved identifier 'Foo'`.
+fn quoted_name(message: &str) -> Option<&str> {
+    let (_, rest) = message.split_once('\'')?;
+    let (name, _) = rest.split_once('\'')?;
+    Some(name)
+}
+
+/// Byte offset of the first occurrence of `name` in `expr` that isn't part of a longer name.
+fn find_name(expr: &str, name: &str) -> Option<usize> {
+    expr.match_indices(name)
+        .map(|(index, _)| index)
+        .find(|&index| {
+            let before = expr[..index].chars().next_back();
+            let after = expr[index + name.len()..].chars().next();
+            !before.is_some_and(is_name_char) && !after.is_some_and(is_name_char)
+        })
+}
+
+/// Byte range of the trimmed term that contains `offset`. Terms are separated by commas that
+/// aren't nested in parentheses, brackets or braces.
+fn term_at(expr: &str, offset: usize) -> Option<(usize, usize)> {
+    let mut depth = 0i32;
+    let mut start = 0;
+    let mut end = expr.len();
+    for (index, c) in expr.char_indices() {
+        match c {
+            '(' | '[' | '{' => depth += 1,
+            ')' | ']' | '}' => depth -= 1,
+            ',' if depth <= 0 => {
+                if index >= offset {
+                    end = index;
+                    break;
+                }
+                start = index + 1;
+            }
+            _ => {}
+        }
+    }
+
+    let term = &expr[start..end];
+    let trimmed_start = start + (term.len() - term.trim_start().len());
+    let trimmed_end = start + term.trim_end().len();
+    (trimmed_start < trimmed_end).then_some((trimmed_start, trimmed_end))
+}
+
+/// Describes what the parser expected at `offset`, based on the last token before it and the
+/// delimiters that are still open.
+fn expected_at(expr: &str, offset: usize) -> &'static str {
+    let before = &expr[..offset];
+    let mut open = Vec::new();
+    for c in before.chars() {
+        match c {
+            '(' | '[' | '{' => open.push(c),
+            ')' | ']' | '}' => {
+                open.pop();
+            }
+            _ => {}
+        }
+    }
+
+    // an inout annotation like `[in]` only holds a single keyword
+    let at_name = expr[offset..].chars().next().is_some_and(is_name_char);
+    if at_name && open.last() == Some(&'[') {
+        return "']'";
+    }
+
+    match before.trim_end().chars().next_back() {
+        None | Some(',' | '(' | '[' | '{' | '!' | '?' | '|' | '=' | '~') => "an identifier",
+        _ => match open.last() {
+            Some('(') => "')'",
+            Some('[') => "']'",
+            Some('{') => "'}'",
+            _ => "',' or the end of the expression",
+        },
+    }
+}
+
+/// Creates a query from `expr`, capturing the diagnostic of flecs if the expression is invalid.
+pub(crate) fn query_from_expr<'a>(
+    world: impl WorldProvider<'a>,
+    expr: &str,
+) -> Result<Query<()>, QueryParseError> {
+    let c_expr = CString::new(expr).map_err(|err| {
+        QueryParseError::at(expr, "unexpected nul byte", Some(err.nul_position()))
+    })?;
+
+    let mut desc = sys::ecs_query_desc_t {
+        expr: c_expr.as_ptr() as *const c_char,
+        ..Default::default()
+    };
+    let (query, log) = capture_log(|| Query::<()>::try_new_from_desc(world, &mut desc));
+    query.ok_or_else(|| QueryParseError::from_log(expr, log.as_deref()))
+}
@@ -0,0 +1,304 @@
+//! Parallel iteration of queries outside of systems.
+//!
+//! [`Query::par_each()`] divides the entities that a query matched between the stages of the
+//! world, which are configured with [`World::set_threads()`] or [`World::set_task_threads()`],
+//! and iterates the entities of each stage on a separate thread. Every table is split into a
+//! range of rows for each stage, so large tables are shared between threads as well.
+
+#[cfg(feature = "std")]
+extern crate std;
+
+extern crate alloc;
+use alloc::{boxed::Box, vec::Vec};
+
+use core::any::Any;
+use core::cell::UnsafeCell;
+use core::ffi::c_void;
+use core::panic::AssertUnwindSafe;
+use std::panic::{catch_unwind, resume_unwind};
+use std::sync::{Mutex, PoisonError};
+
+use crate::core::*;
+use crate::sys;
+use flecs_ecs_derive::extern_abi;
+
+/// Runs the jobs of a parallel query iteration, such as [`Query::par_each_with()`].
+///
+/// Implement this trait to run parallel queries on the thread pool of an application instead
+/// of the threads of the flecs OS API.
+///
+/// # Example
+///
+/// ```
+/// use flecs_ecs::prelude::*;
+///
+/// struct ScopedThreads;
+///
+/// impl QueryThreadPool for ScopedThreads {
+///     fn run_jobs(&self, jobs: usize, job: &(dyn Fn(usize) + Sync)) {
+///         std::thread::scope(|scope| {
+///             for index in 1..jobs {
+///                 scope.spawn(move || job(index));
+///             }
+///             job(0);
+///         });
+///     }
+/// }
+///
+/// #[derive(Component)]
+/// struct Position {
+///     x: f32,
+/// }
+///
+/// let world = World::new();
+/// world.set_stage_count(4);
+/// for _ in 0..100 {
+///     world.entity().set(Position { x: 0.0 });
+/// }
+///
+/// let query = world.new_query::<&mut Position>();
+/// query.par_each_with(&ScopedThreads, |pos| pos.x += 1.0);
+/// query.each(|pos| assert_eq!(pos.x, 1.0));
+/// ```
+pub trait QueryThreadPool {
+    /// Run `job` once for every index in `0..jobs` and return when all jobs finished.
+    ///
+    /// The jobs may run in any order, and don't have to run at the same time. If a job panics,
+    /// the panic should be resumed on the calling thread once the other jobs finished.
+    fn run_jobs(&self, jobs: usize, job: &(dyn Fn(usize) + Sync));
+}
+
+/// Runs the jobs of a parallel query iteration on threads created with the flecs OS API, or on
+/// tasks when the world uses task threads.
+///
+/// This is the thread pool that [`Query::par_each()`] uses.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct OsApiThreads;
+
+type JobPanic = Mutex<Option<Box<dyn Any + Send>>>;
+
+struct OsApiJob<'j> {
+    job: &'j (dyn Fn(usize) + Sync),
+    index: usize,
+    panic: &'j JobPanic,
+}
+
+impl OsApiJob<'_> {
+    fn run(&self) {
+        if let Err(payload) = catch_unwind(AssertUnwindSafe(|| (self.job)(self.index))) {
+            let mut panic = self.panic.lock().unwrap_or_else(PoisonError::into_inner);
+            panic.get_or_insert(payload);
+        }
+    }
+}
+
+#[extern_abi]
+unsafe fn run_os_api_job(param: *mut c_void) -> *mut c_void {
+    let job = unsafe { &*(param as *const OsApiJob) };
+    job.run();
+    core::ptr::null_mut()
+}
+
+impl QueryThreadPool for OsApiThreads {
+    fn run_jobs(&self, jobs: usize, job: &(dyn Fn(usize) + Sync)) {
+        let panic = JobPanic::default();
+        let os_jobs = (0..jobs)
+            .map(|index| OsApiJob {
+                job,
+                index,
+                panic: &panic,
+            })
+            .collect::<Vec<_>>();
+
+        // tasks are used when the application hands the threads of flecs to a job system
+        let (spawn, join) = unsafe {
+            match (sys::ecs_os_api.task_new_, sys::ecs_os_api.task_join_) {
+                (Some(spawn), Some(join)) => (Some(spawn), Some(join)),
+                _ => (sys::ecs_os_api.thread_new_, sys::ecs_os_api.thread_join_),
+            }
+        };
+        let (Some(spawn), Some(join)) = (spawn, join) else {
+            panic!("the flecs OS API has no threading support, use `par_each_with` instead");
+        };
+
+        let threads = os_jobs[1..]
+            .iter()
+            .map(|job| unsafe {
+                spawn(Some(run_os_api_job), job as *const OsApiJob as *mut c_void)
+            })
+            .collect::<Vec<_>>();
+        os_jobs[0].run();
+        for thread in threads {
+            unsafe { join(thread) };
+        }
+
+        if let Some(payload) = panic.into_inner().unwrap_or_else(PoisonError::into_inner) {
+            resume_unwind(payload);
+        }
+    }
+}
+
+/// The query iterators of the stages, which are each used by one job.
+struct StageIters(Vec<(*mut sys::ecs_world_t, UnsafeCell<sys::ecs_iter_t>)>);
+
+// SAFETY: every job only accesses the iterator and stage of its own index
+unsafe impl Sync for StageIters {}
+
+/// Leaves readonly mode when dropped, also when a job panicked.
+struct ReadonlyScope(*mut sys::ecs_world_t);
+
+impl ReadonlyScope {
+    fn begin(world: *mut sys::ecs_world_t) -> Self {
+        unsafe { sys::ecs_readonly_begin(world, true) };
+        Self(world)
+    }
+}
+
+impl Drop for ReadonlyScope {
+    fn drop(&mut self) {
+        unsafe { sys::ecs_readonly_end(self.0) };
+    }
+}
+
+impl<T> Query<T>
+where
+    T: QueryTuple,
+{
+    /// Variant of [`QueryAPI::each()`] which divides the matched entities between the stages of
+    /// the world and iterates them on threads of the flecs OS API.
+    ///
+    /// The number of threads is the stage count of the world, which is configured with
+    /// [`World::set_threads()`], [`World::set_task_threads()`] or [`World::set_stage_count()`].
+    /// With a single stage, the entities are iterated on the calling thread.
+    ///
+    /// When the `flecs_safety_locks` feature is enabled, the components are locked for the
+    /// stage of each thread, so that conflicting access from the calling thread panics.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the world is readonly or deferred, such as in a system, or if the query writes
+    /// a component that all entities share, such as a singleton. Panics of `func` are resumed on
+    /// the calling thread.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use flecs_ecs::prelude::*;
+    ///
+    /// #[derive(Component)]
+    /// struct Position {
+    ///     x: f32,
+    /// }
+    ///
+    /// #[derive(Component)]
+    /// struct Velocity {
+    ///     x: f32,
+    /// }
+    ///
+    /// let world = World::new();
+    /// world.set_threads(4);
+    /// for i in 0..1000 {
+    ///     world
+    ///         .entity()
+    ///         .set(Position { x: 0.0 })
+    ///         .set(Velocity { x: i as f32 });
+    /// }
+    ///
+    /// let query = world.new_query::<(&mut Position, &Velocity)>();
+    /// query.par_each(|(pos, vel)| {
+    ///     pos.x += vel.x;
+    /// });
+    /// ```
+    ///
+    /// # See also
+    ///
+    /// * [`Query::par_each_with()`]
+    /// * [`ParSystemAPI::par_each()`]
+    pub fn par_each<Func>(&self, func: Func)
+    where
+        Func: Fn(T::TupleType<'_>) + Send + Sync,
+    {
+        self.par_each_with(&OsApiThreads, func);
+    }
+
+    /// Variant of [`Query::par_each()`] which runs the jobs of the stages on a thread pool of the
+    /// caller.
+    ///
+    /// # Arguments
+    ///
+    /// * `pool` - The thread pool, which runs a job for every stage of the world.
+    /// * `func` - The function that is invoked for each matched entity.
+    ///
+    /// # See also
+    ///
+    /// * [`QueryThreadPool`]
+    pub fn par_each_with<Func>(&self, pool: &impl QueryThreadPool, func: Func)
+    where
+        Func: Fn(T::TupleType<'_>) + Send + Sync,
+    {
+        let world = self.world().real_world();
+        let world_ptr = world.world_ptr_mut();
+        let readonly = unsafe { sys::ecs_world_get_flags(world_ptr) } & sys::EcsWorldReadonly != 0;
+        if readonly || world.is_deferred() {
+            panic!(
+                "`par_each` can't be used while the world is readonly or deferred, such as in a system. Use a multi threaded system instead."
+            );
+        }
+
+        let stage_count = world.get_stage_count().max(1) as usize;
+        if stage_count == 1 {
+            self.each(func);
+            return;
+        }
+
+        // the iterators are created before entering readonly mode, so that pending table
+        // events are still processed
+        let iters = StageIters(
+            (0..stage_count)
+                .map(|index| {
+                    let stage = unsafe { sys::ecs_get_stage(world_ptr, index as i32) };
+                    let stage_world = unsafe { WorldRef::from_ptr(stage) };
+                    let iter = self.retrieve_iter_stage(stage_world);
+                    (stage, UnsafeCell::new(iter))
+                })
+                .collect(),
+        );
+
+        let iters = &iters;
+        let _readonly = ReadonlyScope::begin(world_ptr);
+        pool.run_jobs(stage_count, &|index| {
+            let (stage, iter) = &iters.0[index];
+            let stage_world = unsafe { WorldRef::from_ptr(*stage) };
+            let mut func = |components: T::TupleType<'_>| func(components);
+            let mut worker =
+                unsafe { sys::ecs_worker_iter(iter.get(), index as i32, stage_count as i32) };
+
+            while unsafe { sys::ecs_worker_next(&mut worker) } {
+                if writes_shared_fields(&worker) {
+                    unsafe { sys::ecs_iter_fini(&mut worker) };
+                    panic!(
+                        "`par_each` can't give mutable access to components that all entities share, such as singletons"
+                    );
+                }
+
+                #[cfg(not(feature = "flecs_safety_locks"))]
+                internal_each_iter_next::<T, false, false>(&mut worker, &stage_world, &mut func);
+
+                #[cfg(feature = "flecs_safety_locks")]
+                if worker.row_fields == 0 {
+                    internal_each_iter_next::<T, false, false>(
+                        &mut worker,
+                        &stage_world,
+                        &mut func,
+                    );
+                } else {
+                    internal_each_iter_next::<T, false, true>(
+                        &mut worker,
+                        &stage_world,
+                        &mut func,
+                    );
+                }
+            }
+        });
+    }
+}
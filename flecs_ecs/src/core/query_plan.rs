@@ -0,0 +1,615 @@
+//! Structured access to the plan that flecs compiles for a query.
+//!
+//! [`QueryAPI::plan()`] returns the plan as text, which is meant to be read by a person.
+//! [`QueryAPI::explain()`] returns the same plan as a [`QueryPlan`], a list of [`QueryPlanOp`]
+//! operations with the term, source, traversal and variables of each operation.
+//! [`QueryAPI::explain_profiled()`] also evaluates the query, and counts how often each
+//! operation was entered, which shows where a query spends its time.
+
+use core::ffi::c_void;
+use core::fmt::{Display, Formatter};
+
+use crate::core::*;
+use crate::sys;
+use flecs_ecs_derive::extern_abi;
+
+extern crate alloc;
+use alloc::{
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+
+macro_rules! query_op_kinds {
+    ($($(#[$doc:meta])* $variant:ident => $name:literal,)+) => {
+        /// The kind of a [`QueryPlanOp`], which is the instruction the query engine runs.
+        #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+        #[non_exhaustive]
+        pub enum QueryOpKind {
+            $($(#[$doc])* $variant,)+
+        }
+
+        impl QueryOpKind {
+            /// The name that flecs uses for the kind in the text of [`QueryAPI::plan()`].
+            pub fn name(self) -> &'static str {
+                match self {
+                    $(QueryOpKind::$variant => $name,)+
+                }
+            }
+
+            /// The kind with the name that flecs uses for it, or `Nothing` if the kind is
+            /// unknown.
+            fn from_name(name: &str) -> Self {
+                match name {
+                    $($name => QueryOpKind::$variant,)+
+                    _ => QueryOpKind::Nothing,
+                }
+            }
+        }
+    };
+}
+
+query_op_kinds! {
+    /// Yield all tables.
+    All => "all",
+    /// Find or match an id against a variable source.
+    And => "and",
+    /// Find or match an id against a variable source, with support for `_` (any).
+    AndAny => "and_any",
+    /// Find or match a `(*, Target)` id.
+    AndWcTgt => "and_wct",
+    /// Search several trivial terms at once.
+    Triv => "triv",
+    /// Iterate the cached terms of a query that is partially cached.
+    Cache => "cache",
+    /// Iterate the cache of a query that is entirely cached.
+    IsCache => "xcache",
+    /// Match an id on the source by traversing a relationship upwards.
+    Up => "up",
+    /// Match an id on the source itself, or by traversing a relationship upwards.
+    SelfUp => "selfup",
+    /// Match an id against a fixed or variable source.
+    With => "with",
+    /// Match a `(*, Target)` id against a fixed or variable source.
+    WithWcTgt => "with_wct",
+    /// Traverse a transitive or reflexive relationship.
+    Trav => "trav",
+    /// The `AndFrom` operator.
+    AndFrom => "andfrom",
+    /// The `OrFrom` operator.
+    OrFrom => "orfrom",
+    /// The `NotFrom` operator.
+    NotFrom => "notfrom",
+    /// Test whether ids that match a wildcard exist.
+    Ids => "ids",
+    /// Find the ids in use that match a `(Relationship, *)` wildcard.
+    IdsRight => "idsr",
+    /// Find the ids in use that match a `(*, Target)` wildcard.
+    IdsLeft => "idsl",
+    /// Iterate the entities of a table to populate an entity variable.
+    Each => "each",
+    /// Store a table or entity in a variable.
+    Store => "store",
+    /// Reset a variable to a wildcard.
+    Reset => "reset",
+    /// The `Or` operator.
+    Or => "or",
+    /// The `Optional` operator.
+    Optional => "option",
+    /// Run a block if a variable is set.
+    IfVar => "ifvar",
+    /// Run a block if a term is set.
+    IfSet => "ifset",
+    /// The `Not` operator.
+    Not => "not",
+    /// End of a block.
+    End => "end",
+    /// Test whether a variable is equal to a value, or assign the value if it isn't set.
+    PredEq => "eq",
+    /// Test whether a variable is not equal to a value.
+    PredNeq => "neq",
+    /// Test whether the name of a variable is equal to a value.
+    PredEqName => "eq_nm",
+    /// Test whether the name of a variable is not equal to a value.
+    PredNeqName => "neq_nm",
+    /// Test whether the name of a variable matches a value.
+    PredEqMatch => "eq_m",
+    /// Test whether the name of a variable doesn't match a value.
+    PredNeqMatch => "neq_m",
+    /// Test whether a member is equal to a value.
+    MemberEq => "membereq",
+    /// Test whether a member is not equal to a value.
+    MemberNeq => "memberneq",
+    /// Evaluate the bitset of a toggleable component.
+    Toggle => "toggle",
+    /// Evaluate the bitset of an optional toggleable component.
+    ToggleOption => "togglopt",
+    /// Match a sparse component.
+    Sparse => "spars",
+    /// Match a sparse component with the `Not` operator.
+    SparseNot => "spars_not",
+    /// Match a sparse component on the source itself, or by traversing upwards.
+    SparseSelfUp => "spars_sup",
+    /// Match a sparse component by traversing upwards.
+    SparseUp => "spars_up",
+    /// Match a sparse component against a fixed or variable source.
+    SparseWith => "spars_w",
+    /// Look up an entity relative to a variable.
+    Lookup => "lookup",
+    /// Populate the sources of the iterator from variables.
+    SetVars => "setvars",
+    /// Populate the `$this` variable.
+    SetThis => "setthis",
+    /// Set the fixed sources of the iterator.
+    SetFixed => "setfix",
+    /// Set the fixed ids of the iterator.
+    SetIds => "setids",
+    /// Set an id if it isn't set.
+    SetId => "setid",
+    /// Test whether a table contains an entity.
+    Contain => "contain",
+    /// Test whether both elements of a pair are the same.
+    PairEq => "pair_eq",
+    /// Yield a result to the application.
+    Yield => "yield",
+    /// Match nothing, used for queries without terms.
+    Nothing => "nothing",
+}
+
+impl QueryOpKind {
+    /// Whether the operation traverses a relationship upwards.
+    pub fn is_traversal(self) -> bool {
+        matches!(
+            self,
+            Self::Up | Self::SelfUp | Self::SparseUp | Self::SparseSelfUp | Self::Trav
+        )
+    }
+
+    /// Whether the operation gets its results from the query cache.
+    pub fn is_cache(self) -> bool {
+        matches!(self, Self::Cache | Self::IsCache)
+    }
+}
+
+impl Display for QueryOpKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// The source, first or second element that an operation of a [`QueryPlan`] evaluates.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum QueryOpRef {
+    /// The operation doesn't use this element.
+    None,
+    /// A query variable. Anonymous variables are named after their index.
+    Var(String),
+    /// A fixed entity, such as a component.
+    Entity {
+        /// The entity.
+        entity: Entity,
+        /// The path of the entity.
+        path: String,
+    },
+}
+
+impl QueryOpRef {
+    /// The name of the variable, if this is a variable.
+    pub fn var(&self) -> Option<&str> {
+        match self {
+            Self::Var(name) => Some(name),
+            _ => None,
+        }
+    }
+
+    /// The entity, if this is a fixed entity.
+    pub fn entity(&self) -> Option<Entity> {
+        match self {
+            Self::Entity { entity, .. } => Some(*entity),
+            _ => None,
+        }
+    }
+}
+
+impl Display for QueryOpRef {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::None => Ok(()),
+            Self::Var(name) => write!(f, "${name}"),
+            Self::Entity { path, .. } => f.write_str(path),
+        }
+    }
+}
+
+/// How the term of an operation traverses a relationship to find its source.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QueryOpTraversal {
+    relationship: Entity,
+    relationship_path: String,
+    self_: bool,
+    cascade: bool,
+    desc: bool,
+}
+
+impl QueryOpTraversal {
+    /// The relationship that is traversed, such as `ChildOf`.
+    pub fn relationship(&self) -> Entity {
+        self.relationship
+    }
+
+    /// Whether the source itself is matched before traversing (`self|up`).
+    pub fn includes_self(&self) -> bool {
+        self.self_
+    }
+
+    /// Whether results are ordered by the depth of the source (`cascade`).
+    pub fn is_cascade(&self) -> bool {
+        self.cascade
+    }
+
+    /// Whether a `cascade` is ordered from the deepest source first (`desc`).
+    pub fn is_desc(&self) -> bool {
+        self.desc
+    }
+}
+
+/// How often an operation ran while a query was evaluated by [`QueryAPI::explain_profiled()`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub struct QueryOpProfile {
+    /// How often the operation was entered to find its first result.
+    pub enter: u32,
+    /// How often the operation was entered again to find its next result, after an operation
+    /// behind it ran out of results.
+    pub redo: u32,
+}
+
+/// An operation of a [`QueryPlan`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QueryPlanOp {
+    index: usize,
+    kind: QueryOpKind,
+    term_index: Option<usize>,
+    source: QueryOpRef,
+    first: QueryOpRef,
+    second: QueryOpRef,
+    traversal: Option<QueryOpTraversal>,
+    writes: Vec<String>,
+    reads: Vec<String>,
+    profile: Option<QueryOpProfile>,
+}
+
+impl QueryPlanOp {
+    /// The index of the operation in the plan.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// The kind of the operation.
+    pub fn kind(&self) -> QueryOpKind {
+        self.kind
+    }
+
+    /// The index of the query term that the operation evaluates, if it evaluates a single term.
+    pub fn term_index(&self) -> Option<usize> {
+        self.term_index
+    }
+
+    /// The source that the operation matches, such as `$this`.
+    pub fn source(&self) -> &QueryOpRef {
+        &self.source
+    }
+
+    /// The first element of the id that the operation matches, such as a component or the
+    /// relationship of a pair.
+    pub fn first(&self) -> &QueryOpRef {
+        &self.first
+    }
+
+    /// The second element of the pair that the operation matches, if it matches a pair.
+    pub fn second(&self) -> &QueryOpRef {
+        &self.second
+    }
+
+    /// How the term of the operation traverses a relationship, if it does.
+    pub fn traversal(&self) -> Option<&QueryOpTraversal> {
+        self.traversal.as_ref()
+    }
+
+    /// The names of the variables that the operation writes.
+    pub fn writes(&self) -> &[String] {
+        &self.writes
+    }
+
+    /// The names of the variables that the operation reads, which were written by an earlier
+    /// operation.
+    pub fn reads(&self) -> &[String] {
+        &self.reads
+    }
+
+    /// Whether the operation gets its results from the query cache.
+    pub fn is_cached(&self) -> bool {
+        self.kind.is_cache()
+    }
+
+    /// How often the operation ran, if the plan was created with
+    /// [`QueryAPI::explain_profiled()`] and profiling is supported.
+    pub fn profile(&self) -> Option<QueryOpProfile> {
+        self.profile
+    }
+}
+
+impl Display for QueryPlanOp {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        if let Some(profile) = self.profile {
+            write!(f, "{:>6} -> {:>6} <-  | ", profile.enter, profile.redo)?;
+        }
+        write!(f, "{:>2}. {:<9}", self.index, self.kind.name())?;
+        if self.source != QueryOpRef::None {
+            write!(f, " {}", self.source)?;
+        }
+        if self.first != QueryOpRef::None {
+            write!(f, " ({}", self.first)?;
+            if self.second != QueryOpRef::None {
+                write!(f, ", {}", self.second)?;
+            }
+            write!(f, ")")?;
+        }
+        if let Some(term) = self.term_index {
+            write!(f, " term={term}")?;
+        }
+        if let Some(traversal) = &self.traversal {
+            write!(f, " trav={}", traversal.relationship_path)?;
+        }
+        if !self.writes.is_empty() {
+            write!(f, " writes=${}", self.writes.join(",$"))?;
+        }
+        if !self.reads.is_empty() {
+            write!(f, " reads=${}", self.reads.join(",$"))?;
+        }
+        if self.is_cached() {
+            write!(f, " cached")?;
+        }
+        Ok(())
+    }
+}
+
+/// The operations that the query engine runs to evaluate a query, which is created with
+/// [`QueryAPI::explain()`].
+///
+/// Queries that only match components on `$this`, and cached queries of which all terms are
+/// cached, are evaluated by a specialized iterator instead of a plan. The plan of those queries
+/// has no operations.
+///
+/// # Example
+///
+/// ```
+/// use flecs_ecs::prelude::*;
+///
+/// #[derive(Component)]
+/// struct Position {
+///     x: f32,
+///     y: f32,
+/// }
+///
+/// let world = World::new();
+///
+/// let query = world
+///     .query::<&Position>()
+///     .term_at(0)
+///     .parent()
+///     .build();
+///
+/// let plan = query.explain();
+/// let up = plan
+///     .ops()
+///     .iter()
+///     .find(|op| op.kind() == QueryOpKind::Up)
+///     .unwrap();
+/// assert_eq!(up.term_index(), Some(0));
+/// assert_eq!(up.source().var(), Some("this"));
+/// assert_eq!(up.first().entity(), Some(world.component_id::<Position>()));
+/// assert_eq!(
+///     up.traversal().map(|trav| trav.relationship()),
+///     Some(flecs::ChildOf::ID.into())
+/// );
+/// assert!(!up.is_cached());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct QueryPlan {
+    ops: Vec<QueryPlanOp>,
+}
+
+impl QueryPlan {
+    /// The operations of the plan, in the order of the plan.
+    pub fn ops(&self) -> &[QueryPlanOp] {
+        &self.ops
+    }
+
+    /// Whether the query is evaluated by a specialized iterator instead of a plan.
+    pub fn is_trivial(&self) -> bool {
+        self.ops.is_empty()
+    }
+
+    /// Create the plan of `query`, and evaluate the query to profile the plan if `profile` is
+    /// set.
+    pub(crate) fn new(query: *const sys::ecs_query_t, profile: bool) -> Self {
+        let query = unsafe { &*query };
+        let raw_ops = raw_ops(query);
+        let mut it = unsafe { sys::ecs_query_iter(query.world, query) };
+
+        let mut counts = vec![[0; 2]; raw_ops.len()];
+        let mut capture = ProfileCapture {
+            fini: it.fini,
+            counts: &mut counts,
+        };
+        let profiled =
+            profile && !raw_ops.is_empty() && unsafe { !it.priv_.iter.query.profile.is_null() };
+
+        if profiled {
+            it.callback_ctx = &mut capture as *mut ProfileCapture as *mut c_void;
+            it.fini = Some(capture_profile_fini);
+            // the counters are copied when the last `ecs_query_next` finishes the iterator
+            while unsafe { sys::ecs_query_next(&mut it) } {
+                // results aren't used, so they must not be marked as changed
+                it.flags |= sys::EcsIterSkip;
+            }
+        } else {
+            unsafe { sys::ecs_iter_fini(&mut it) };
+        }
+
+        let world = unsafe { WorldRef::from_ptr(query.real_world) };
+        let mut written = 0;
+        let ops = raw_ops
+            .iter()
+            .enumerate()
+            .map(|(index, op)| {
+                let profile = profiled.then(|| QueryOpProfile {
+                    enter: counts[index][0] as u32,
+                    redo: counts[index][1] as u32,
+                });
+                QueryPlanOp::new(world, query, index, op, &mut written, profile)
+            })
+            .collect();
+
+        Self { ops }
+    }
+}
+
+impl Display for QueryPlan {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        for op in &self.ops {
+            writeln!(f, "{op}")?;
+        }
+        Ok(())
+    }
+}
+
+/// The operations of the plan of `query`.
+fn raw_ops(query: &sys::ecs_query_t) -> Vec<sys::ecs_rust_query_op_t> {
+    let count = unsafe { sys::ecs_rust_query_op_count(query) };
+    (0..count)
+        .map(|index| {
+            let mut op = sys::ecs_rust_query_op_t::default();
+            unsafe { sys::ecs_rust_query_get_op(query, index, &mut op) };
+            op
+        })
+        .collect()
+}
+
+impl QueryPlanOp {
+    fn new(
+        world: WorldRef,
+        query: &sys::ecs_query_t,
+        index: usize,
+        op: &sys::ecs_rust_query_op_t,
+        written: &mut u64,
+        profile: Option<QueryOpProfile>,
+    ) -> Self {
+        let name = unsafe { core::ffi::CStr::from_ptr(op.kind) }.to_string_lossy();
+        let kind = QueryOpKind::from_name(name.trim_end());
+        // flecs marks every variable that an op uses as written by it, so whether the op reads
+        // or writes a variable depends on whether an earlier op wrote it
+        let mut used = op.written;
+        let mut ref_at = |var: i16, entity: sys::ecs_entity_t| {
+            if let Ok(var) = u8::try_from(var) {
+                if var < 64 {
+                    used |= 1 << var;
+                }
+                QueryOpRef::Var(var_name(query, var))
+            } else if entity != 0 {
+                let entity = Entity::new(entity);
+                QueryOpRef::Entity {
+                    entity,
+                    path: entity_path(world, entity),
+                }
+            } else {
+                QueryOpRef::None
+            }
+        };
+
+        let source = ref_at(op.src_var, op.src);
+        let first = ref_at(op.first_var, op.first);
+        let second = ref_at(op.second_var, op.second);
+
+        let term_index = (first != QueryOpRef::None && op.term_index >= 0)
+            .then_some(op.term_index as usize)
+            .filter(|&term| term < query.term_count as usize);
+        let traversal = term_index.and_then(|term| {
+            let term = unsafe { &*query.terms.add(term) };
+            (term.src.id & sys::EcsUp != 0).then(|| QueryOpTraversal {
+                relationship: Entity::new(term.trav),
+                relationship_path: entity_path(world, Entity::new(term.trav)),
+                self_: term.src.id & sys::EcsSelf as u64 != 0,
+                cascade: term.src.id & sys::EcsCascade != 0,
+                desc: term.src.id & sys::EcsDesc != 0,
+            })
+        });
+
+        let vars = |bits: u64| {
+            (0..64u8)
+                .filter(|&var| bits & (1 << var) != 0)
+                .map(|var| var_name(query, var))
+                .collect::<Vec<_>>()
+        };
+        let writes = vars(used & !*written);
+        let reads = vars(used & *written);
+        *written |= used;
+
+        Self {
+            index,
+            kind,
+            term_index,
+            source,
+            first,
+            second,
+            traversal,
+            writes,
+            reads,
+            profile,
+        }
+    }
+}
+
+/// The path of an entity in the notation of the query DSL, such as `flecs.core.ChildOf`.
+fn entity_path(world: WorldRef, entity: Entity) -> String {
+    EntityView::new_from(world, entity)
+        .path_w_sep(".", "")
+        .unwrap_or_else(|| entity.to_string())
+}
+
+/// The name of a query variable. Anonymous variables are named after their index, like in
+/// the text of [`QueryAPI::plan()`].
+fn var_name(query: &sys::ecs_query_t, var: u8) -> String {
+    if var == 0 {
+        return "this".to_string();
+    }
+    if i32::from(var) < i32::from(query.var_count) {
+        let name = unsafe { *query.vars.add(var as usize) };
+        if !name.is_null() {
+            return unsafe { core::ffi::CStr::from_ptr(name) }
+                .to_string_lossy()
+                .into_owned();
+        }
+    }
+    var.to_string()
+}
+
+/// Keeps the counters of a profiled iterator, which flecs frees when the iterator finishes.
+struct ProfileCapture<'a> {
+    fini: sys::ecs_iter_fini_action_t,
+    counts: &'a mut [[i32; 2]],
+}
+
+#[extern_abi]
+unsafe fn capture_profile_fini(it: *mut sys::ecs_iter_t) {
+    let it = unsafe { &mut *it };
+    let capture = unsafe { &mut *(it.callback_ctx as *mut ProfileCapture) };
+    let profile = unsafe { it.priv_.iter.query.profile };
+    for (index, counts) in capture.counts.iter_mut().enumerate() {
+        *counts = unsafe { (*profile.add(index)).count };
+    }
+    it.callback_ctx = core::ptr::null_mut();
+    if let Some(fini) = capture.fini {
+        unsafe { fini(it) };
+    }
+}
@@ -29,7 +29,21 @@ pub struct ComponentsData<T: QueryTuple, const LEN: usize> {
     _marker: PhantomData<T>,
 }
 
-pub trait ComponentPointers<T: QueryTuple> {
+impl<T: QueryTuple, const LEN: usize> Clone for ComponentsData<T, LEN> {
+    fn clone(&self) -> Self {
+        Self {
+            array_components: self.array_components,
+            is_ref_array_components: self.is_ref_array_components,
+            is_row_array_components: self.is_row_array_components,
+            index_array_components: self.index_array_components,
+            #[cfg(feature = "flecs_safety_locks")]
+            safety_table_records: self.safety_table_records,
+            _marker: PhantomData,
+        }
+    }
+}
+
+pub trait ComponentPointers<T: QueryTuple>: Clone {
     fn new(iter: &sys::ecs_iter_t) -> (IsAnyArray, Self);
 
     fn get_tuple(&mut self, index: usize) -> T::TupleType<'_>;
@@ -42,6 +56,8 @@ pub trait ComponentPointers<T: QueryTuple> {
 
     fn get_tuple_with_ref(&mut self, index: usize) -> T::TupleType<'_>;
 
+    fn get_slices(&mut self, count: usize) -> T::TupleSliceType<'_>;
+
     #[cfg(feature = "flecs_safety_locks")]
     fn safety_table_records(&self) -> &[TableColumnSafety];
 }
@@ -122,6 +138,14 @@ impl<T: QueryTuple, const LEN: usize> ComponentPointers<T> for ComponentsData<T,
         )
     }
 
+    fn get_slices(&mut self, count: usize) -> T::TupleSliceType<'_> {
+        T::create_tuple_slices(
+            &self.array_components[..],
+            &self.is_ref_array_components[..],
+            count,
+        )
+    }
+
     #[cfg(feature = "flecs_safety_locks")]
     fn safety_table_records(&self) -> &[TableColumnSafety] {
         &self.safety_table_records[..]
@@ -146,6 +170,8 @@ pub trait IterableTypeOperation {
         is_ref: bool,
         index: usize,
     ) -> Self::ActualType<'a>;
+
+    fn create_slice_data<'a>(array_components_data: *mut u8, count: usize) -> Self::SliceType<'a>;
 }
 
 impl<T> IterableTypeOperation for &T
@@ -186,6 +212,15 @@ where
             }
         }
     }
+
+    #[inline(always)]
+    fn create_slice_data<'a>(array_components_data: *mut u8, count: usize) -> Self::SliceType<'a> {
+        if count == 0 {
+            return &[];
+        }
+        let data_ptr = array_components_data as Self::CastType;
+        unsafe { core::slice::from_raw_parts(data_ptr, count) }
+    }
 }
 
 impl<T> IterableTypeOperation for &mut T
@@ -226,6 +261,15 @@ where
             }
         }
     }
+
+    #[inline(always)]
+    fn create_slice_data<'a>(array_components_data: *mut u8, count: usize) -> Self::SliceType<'a> {
+        if count == 0 {
+            return &mut [];
+        }
+        let data_ptr = array_components_data as Self::CastType;
+        unsafe { core::slice::from_raw_parts_mut(data_ptr, count) }
+    }
 }
 
 impl<T> IterableTypeOperation for Option<&T>
@@ -270,6 +314,18 @@ where
             Some(unsafe { &*data_ptr.add(index) })
         }
     }
+
+    #[inline(always)]
+    fn create_slice_data<'a>(array_components_data: *mut u8, count: usize) -> Self::SliceType<'a> {
+        let data_ptr = array_components_data as Self::CastType;
+        if data_ptr.is_null() {
+            None
+        } else if count == 0 {
+            Some(&[])
+        } else {
+            Some(unsafe { core::slice::from_raw_parts(data_ptr, count) })
+        }
+    }
 }
 
 impl<T> IterableTypeOperation for Option<&mut T>
@@ -315,11 +371,24 @@ where
             Some(unsafe { &mut *data_ptr.add(index) })
         }
     }
+
+    #[inline(always)]
+    fn create_slice_data<'a>(array_components_data: *mut u8, count: usize) -> Self::SliceType<'a> {
+        let data_ptr = array_components_data as Self::CastType;
+        if data_ptr.is_null() {
+            None
+        } else if count == 0 {
+            Some(&mut [])
+        } else {
+            Some(unsafe { core::slice::from_raw_parts_mut(data_ptr, count) })
+        }
+    }
 }
 
 pub trait QueryTuple: Sized {
     type Pointers: ComponentPointers<Self>;
     type TupleType<'a>;
+    type TupleSliceType<'a>;
     const CONTAINS_ANY_TAG_TERM: bool;
     const COUNT: i32;
     const COUNT_IMMUTABLE: usize;
@@ -375,6 +444,17 @@ pub trait QueryTuple: Sized {
         indexes_array_components: &[i8],
         index_row_entity: usize,
     ) -> Self::TupleType<'a>;
+
+    /// Creates slices of the columns of a table. Fields that aren't stored in the table, such
+    /// as singletons, are slices with one element.
+    ///
+    /// The slices borrow the columns that `array_components` points to rather than the array
+    /// itself, so the caller ties them to whatever keeps the columns alive.
+    fn create_tuple_slices<'a>(
+        array_components: &[*mut u8],
+        is_ref_array_components: &[bool],
+        count: usize,
+    ) -> Self::TupleSliceType<'a>;
 }
 
 /////////////////////
@@ -388,6 +468,7 @@ where
 {
     type Pointers = ComponentsData<A, 1>;
     type TupleType<'w> = A::ActualType<'w>;
+    type TupleSliceType<'w> = A::SliceType<'w>;
     const CONTAINS_ANY_TAG_TERM: bool = <<A::OnlyPairType as ComponentId>::UnderlyingType as ComponentInfo>::IS_TAG;
     const COUNT : i32 = 1;
     const COUNT_IMMUTABLE: usize = if A::IS_IMMUTABLE && !A::IS_OPTIONAL { 1 } else { 0 };
@@ -523,6 +604,15 @@ where
             index_row_entity,
         )
     }
+
+    fn create_tuple_slices<'a>(
+        array_components: &[*mut u8],
+        is_ref_array_components: &[bool],
+        count: usize
+    ) -> Self::TupleSliceType<'a> {
+        let count = if is_ref_array_components[0] { 1 } else { count };
+        A::create_slice_data(array_components[0], count)
+    }
 }
 
 pub struct Wrapper<T>(T);
@@ -621,6 +711,10 @@ macro_rules! impl_iterable {
                 $t::ActualType<'w>,
             )*);
 
+            type TupleSliceType<'w> = ($(
+                $t::SliceType<'w>,
+            )*);
+
             const CONTAINS_ANY_TAG_TERM: bool = $(<<$t::OnlyPairType as ComponentId>::UnderlyingType as ComponentInfo>::IS_TAG ||)* false;
 
             type Pointers = ComponentsData<Self, { tuple_count!($($t),*) }>;
@@ -819,6 +913,18 @@ macro_rules! impl_iterable {
                     $t::create_tuple_with_ref_data(data_ptr, is_ref, index_row_entity)
                 },)*)
             }
+
+            #[allow(unused, clippy::unused_unit)]
+            #[inline(always)]
+            fn create_tuple_slices<'a>(array_components: &[*mut u8], is_ref_array_components: &[bool], count: usize) -> Self::TupleSliceType<'a> {
+                let mut column: usize = 0;
+                ($({
+                    let data_ptr = unsafe { *array_components.get_unchecked(column) };
+                    let is_ref = unsafe { *is_ref_array_components.get_unchecked(column) };
+                    column += 1;
+                    $t::create_slice_data(data_ptr, if is_ref { 1 } else { count })
+                },)*)
+            }
         }
     }
 }
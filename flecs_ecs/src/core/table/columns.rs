@@ -0,0 +1,305 @@
+//! Iteration over several component columns of a table at once.
+//!
+//! The columns returned by [`TableColumns::columns()`] and
+//! [`QueryChunk::columns()`](crate::core::QueryChunk::columns) are plain slices, which are
+//! stored as a struct of arrays. [`ZipColumns`] iterates the elements of equally long slices
+//! side by side with a simple indexed loop, which the compiler can auto-vectorize.
+
+use core::marker::PhantomData;
+
+use crate::core::*;
+use crate::sys;
+
+const TERM_COUNT_MAX: usize = sys::FLECS_TERM_COUNT_MAX as usize;
+
+/// The component columns of a table, which are created with
+/// [`TableOperations::columns_mut()`].
+///
+/// The table stays locked until the columns are dropped, so that entities can't be added to or
+/// removed from it while the columns give access to its components. When the
+/// `flecs_safety_locks` feature is enabled, the columns are also locked for reading or writing,
+/// so that queries can't access them in conflicting ways.
+pub struct TableColumns<'a, T: QueryTuple> {
+    world: WorldRef<'a>,
+    table: *mut sys::ecs_table_t,
+    count: usize,
+    components: [*mut u8; TERM_COUNT_MAX],
+    #[cfg(feature = "flecs_safety_locks")]
+    table_records: [TableColumnSafety; TERM_COUNT_MAX],
+    _phantom: PhantomData<T>,
+}
+
+impl<'a, T: QueryTuple> TableColumns<'a, T> {
+    /// Locks the table and the columns.
+    ///
+    /// `table_records` are ordered like the locks of a query: the immutable fields, the mutable
+    /// fields, the optional immutable fields and then the optional mutable fields.
+    pub(crate) fn new(
+        world: WorldRef<'a>,
+        table: *mut sys::ecs_table_t,
+        count: usize,
+        components: [*mut u8; TERM_COUNT_MAX],
+        #[cfg(feature = "flecs_safety_locks")] table_records: [TableColumnSafety; TERM_COUNT_MAX],
+    ) -> Self {
+        #[cfg(feature = "flecs_safety_locks")]
+        do_read_write_locks::<INCREMENT, false, T>(&world, &table_records[..T::COUNT as usize]);
+        table_lock(world.world_ptr_mut(), table);
+
+        Self {
+            world,
+            table,
+            count,
+            components,
+            #[cfg(feature = "flecs_safety_locks")]
+            table_records,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Number of rows in the columns.
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// The columns as a tuple of slices in the same form as the terms of a query: `&T` gives
+    /// `&[T]`, `&mut T` gives `&mut [T]` and `Option<&T>` gives `Option<&[T]>`.
+    pub fn columns(&mut self) -> T::TupleSliceType<'_> {
+        let is_ref = [false; TERM_COUNT_MAX];
+        T::create_tuple_slices(
+            &self.components[..T::COUNT as usize],
+            &is_ref[..T::COUNT as usize],
+            self.count,
+        )
+    }
+}
+
+impl<T: QueryTuple> Drop for TableColumns<'_, T> {
+    fn drop(&mut self) {
+        table_unlock(self.world.world_ptr_mut(), self.table);
+
+        #[cfg(feature = "flecs_safety_locks")]
+        do_read_write_locks::<DECREMENT, false, T>(
+            &self.world,
+            &self.table_records[..T::COUNT as usize],
+        );
+    }
+}
+
+/// A component column that can be zipped with other columns by [`ZipColumns`].
+///
+/// Implemented for `&[T]` and `&mut [T]`.
+pub trait Column<'a> {
+    /// The element of the column that is passed to the loop.
+    type Item: 'a;
+
+    /// Number of elements in the column.
+    fn len(&self) -> usize;
+
+    /// Whether the column has no elements.
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Pointer to the first element of the column.
+    #[doc(hidden)]
+    fn as_column_ptr(&mut self) -> *mut u8;
+
+    /// # Safety
+    ///
+    /// `ptr` must come from [`Column::as_column_ptr()`], `index` must be in bounds and every
+    /// index must only be accessed once.
+    #[doc(hidden)]
+    unsafe fn item(ptr: *mut u8, index: usize) -> Self::Item;
+}
+
+impl<'a, T: 'a> Column<'a> for &'a [T] {
+    type Item = &'a T;
+
+    #[inline(always)]
+    fn len(&self) -> usize {
+        <[T]>::len(self)
+    }
+
+    #[inline(always)]
+    fn as_column_ptr(&mut self) -> *mut u8 {
+        self.as_ptr() as *mut u8
+    }
+
+    #[inline(always)]
+    unsafe fn item(ptr: *mut u8, index: usize) -> Self::Item {
+        unsafe { &*(ptr as *const T).add(index) }
+    }
+}
+
+impl<'a, T: 'a> Column<'a> for &'a mut [T] {
+    type Item = &'a mut T;
+
+    #[inline(always)]
+    fn len(&self) -> usize {
+        <[T]>::len(self)
+    }
+
+    #[inline(always)]
+    fn as_column_ptr(&mut self) -> *mut u8 {
+        self.as_mut_ptr() as *mut u8
+    }
+
+    #[inline(always)]
+    unsafe fn item(ptr: *mut u8, index: usize) -> Self::Item {
+        unsafe { &mut *(ptr as *mut T).add(index) }
+    }
+}
+
+/// Iterate tuples of equally long columns element by element.
+///
+/// # Panics
+///
+/// The methods panic if the columns don't have the same length.
+///
+/// # Example
+///
+/// ```
+/// use flecs_ecs::prelude::*;
+///
+/// #[derive(Component)]
+/// struct Position {
+///     x: f32,
+///     y: f32,
+/// }
+///
+/// #[derive(Component)]
+/// struct Velocity {
+///     x: f32,
+///     y: f32,
+/// }
+///
+/// let world = World::new();
+/// let e = world
+///     .entity()
+///     .set(Position { x: 0.0, y: 0.0 })
+///     .set(Velocity { x: 1.0, y: 2.0 });
+///
+/// let table = e.table().unwrap();
+/// let mut columns = table
+///     .columns_mut::<(&mut Position, &Velocity)>()
+///     .unwrap();
+/// columns.columns().zip_for_each(|(pos, vel)| {
+///     pos.x += vel.x;
+///     pos.y += vel.y;
+/// });
+///
+/// e.get::<&Position>(|pos| assert_eq!((pos.x, pos.y), (1.0, 2.0)));
+/// ```
+pub trait ZipColumns<'a>: Sized {
+    /// A tuple with an element of each column.
+    type Item;
+
+    /// Call `func` with the elements of every row of the columns.
+    fn zip_for_each(self, func: impl FnMut(Self::Item));
+
+    /// Iterate the elements of every row of the columns.
+    fn zip_iter(self) -> ZippedColumns<'a, Self>;
+
+    #[doc(hidden)]
+    fn column_len(&self) -> usize;
+
+    #[doc(hidden)]
+    fn column_ptrs(&mut self) -> [*mut u8; 8];
+
+    /// # Safety
+    ///
+    /// See [`Column::item()`].
+    #[doc(hidden)]
+    unsafe fn items(ptrs: &[*mut u8; 8], index: usize) -> Self::Item;
+}
+
+/// Iterator over the rows of zipped columns, which is created with [`ZipColumns::zip_iter()`].
+pub struct ZippedColumns<'a, C: ZipColumns<'a>> {
+    ptrs: [*mut u8; 8],
+    index: usize,
+    len: usize,
+    _phantom: PhantomData<(C, &'a ())>,
+}
+
+impl<'a, C: ZipColumns<'a>> Iterator for ZippedColumns<'a, C> {
+    type Item = C::Item;
+
+    #[inline(always)]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.index == self.len {
+            return None;
+        }
+        let index = self.index;
+        self.index += 1;
+        Some(unsafe { C::items(&self.ptrs, index) })
+    }
+
+    #[inline(always)]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.len - self.index;
+        (remaining, Some(remaining))
+    }
+}
+
+impl<'a, C: ZipColumns<'a>> ExactSizeIterator for ZippedColumns<'a, C> {}
+
+impl<'a, C: ZipColumns<'a>> core::iter::FusedIterator for ZippedColumns<'a, C> {}
+
+macro_rules! impl_zip_columns {
+    ($($t:ident: $index:tt),+) => {
+        impl<'a, $($t: Column<'a>),+> ZipColumns<'a> for ($($t,)+) {
+            type Item = ($($t::Item,)+);
+
+            #[inline(always)]
+            fn zip_for_each(mut self, mut func: impl FnMut(Self::Item)) {
+                let len = self.column_len();
+                let ptrs = self.column_ptrs();
+                for index in 0..len {
+                    func(unsafe { Self::items(&ptrs, index) });
+                }
+            }
+
+            #[inline(always)]
+            fn zip_iter(mut self) -> ZippedColumns<'a, Self> {
+                ZippedColumns {
+                    len: self.column_len(),
+                    ptrs: self.column_ptrs(),
+                    index: 0,
+                    _phantom: PhantomData,
+                }
+            }
+
+            #[inline(always)]
+            fn column_len(&self) -> usize {
+                let lens = [$(self.$index.len()),+];
+                let len = lens[0];
+                assert!(
+                    lens.iter().all(|&other| other == len),
+                    "the columns have different lengths: {lens:?}"
+                );
+                len
+            }
+
+            #[inline(always)]
+            fn column_ptrs(&mut self) -> [*mut u8; 8] {
+                let mut ptrs = [core::ptr::null_mut(); 8];
+                $(ptrs[$index] = self.$index.as_column_ptr();)+
+                ptrs
+            }
+
+            #[inline(always)]
+            unsafe fn items(ptrs: &[*mut u8; 8], index: usize) -> Self::Item {
+                unsafe { ($($t::item(ptrs[$index], index),)+) }
+            }
+        }
+    };
+}
+
+impl_zip_columns!(A: 0);
+impl_zip_columns!(A: 0, B: 1);
+impl_zip_columns!(A: 0, B: 1, C: 2);
+impl_zip_columns!(A: 0, B: 1, C: 2, D: 3);
+impl_zip_columns!(A: 0, B: 1, C: 2, D: 3, E: 4);
+impl_zip_columns!(A: 0, B: 1, C: 2, D: 3, E: 4, F: 5);
+impl_zip_columns!(A: 0, B: 1, C: 2, D: 3, E: 4, F: 5, G: 6);
+impl_zip_columns!(A: 0, B: 1, C: 2, D: 3, E: 4, F: 5, G: 6, H: 7);
@@ -0,0 +1,122 @@
+//! Borrows of the typed context of a query, system or observer.
+//!
+//! A context set with `ctx()` on a builder belongs to the object, and every iteration of
+//! that object can access it through [`TableIter::ctx()`] and [`TableIter::ctx_mut()`]. Those
+//! iterations can be nested, such as when a system runs itself from its callback, so the
+//! guards in this module track the borrows at runtime like [`core::cell::Ref`] and
+//! [`core::cell::RefMut`].
+
+use core::fmt::{Debug, Formatter};
+use core::marker::PhantomData;
+use core::ops::{Deref, DerefMut};
+use core::ptr::NonNull;
+
+use crate::core::*;
+
+/// Shared borrow of a typed context, returned by [`TableIter::ctx()`].
+///
+/// The context can't be mutably borrowed until the guard is dropped.
+pub struct CtxRef<'a, C> {
+    ctx: &'a TypedCtx,
+    value: &'a C,
+}
+
+impl<'a, C: 'static> CtxRef<'a, C> {
+    /// Borrows the context, returns `None` if it is not of type `C`.
+    ///
+    /// # Panics
+    ///
+    /// Panics when the context is mutably borrowed.
+    pub(crate) fn new(ctx: &'a TypedCtx) -> Option<Self> {
+        let value = ctx.downcast_ref::<C>()?;
+        assert!(
+            ctx.try_borrow(),
+            "context of type `{}` is already mutably borrowed",
+            ctx.type_name()
+        );
+        Some(Self { ctx, value })
+    }
+}
+
+impl<C> Deref for CtxRef<'_, C> {
+    type Target = C;
+
+    fn deref(&self) -> &C {
+        self.value
+    }
+}
+
+impl<C> Drop for CtxRef<'_, C> {
+    fn drop(&mut self) {
+        self.ctx.release();
+    }
+}
+
+impl<C: Debug> Debug for CtxRef<'_, C> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        self.value.fmt(f)
+    }
+}
+
+/// Mutable borrow of a typed context, returned by [`TableIter::ctx_mut()`].
+///
+/// The context can't be borrowed again until the guard is dropped.
+pub struct CtxRefMut<'a, C> {
+    ctx: NonNull<TypedCtx>,
+    value: NonNull<C>,
+    marker: PhantomData<&'a mut C>,
+}
+
+impl<'a, C: 'static> CtxRefMut<'a, C> {
+    /// Mutably borrows the context, returns `None` if it is not of type `C`.
+    ///
+    /// # Safety
+    ///
+    /// `ctx` must point to a live typed context that outlives `'a`.
+    ///
+    /// # Panics
+    ///
+    /// Panics when the context is already borrowed.
+    pub(crate) unsafe fn new(ctx: *mut TypedCtx) -> Option<Self> {
+        let shared = unsafe { &*ctx };
+        shared.downcast_ref::<C>()?;
+        assert!(
+            shared.try_borrow_mut(),
+            "context of type `{}` is already borrowed",
+            shared.type_name()
+        );
+        // no other borrow exists, so the value can be accessed mutably
+        let value = unsafe { TypedCtx::downcast_ptr::<C>(ctx) }?;
+        Some(Self {
+            ctx: NonNull::new(ctx)?,
+            value,
+            marker: PhantomData,
+        })
+    }
+}
+
+impl<C> Deref for CtxRefMut<'_, C> {
+    type Target = C;
+
+    fn deref(&self) -> &C {
+        unsafe { self.value.as_ref() }
+    }
+}
+
+impl<C> DerefMut for CtxRefMut<'_, C> {
+    fn deref_mut(&mut self) -> &mut C {
+        unsafe { self.value.as_mut() }
+    }
+}
+
+impl<C> Drop for CtxRefMut<'_, C> {
+    fn drop(&mut self) {
+        unsafe { self.ctx.as_ref() }.release_mut();
+    }
+}
+
+impl<C: Debug> Debug for CtxRefMut<'_, C> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        (**self).fmt(f)
+    }
+}
@@ -190,6 +190,27 @@ where
         self.iter.delta_system_time
     }
 
+    /// Return the interpolation alpha of the fixed timestep of the world.
+    ///
+    /// This is the fraction of a fixed step that hasn't been simulated yet, in the range
+    /// `[0, 1)`. Systems that run after the systems of the `FixedUpdate` phase can use it to
+    /// interpolate between the state of the last two steps.
+    ///
+    /// # Returns
+    ///
+    /// The alpha, or `0.0` if the world doesn't have a fixed timestep.
+    ///
+    /// # See also
+    ///
+    /// * [`World::set_fixed_timestep()`]
+    #[cfg(feature = "flecs_pipeline")]
+    pub fn fixed_timestep_alpha(&self) -> FTime {
+        self.real_world()
+            .world_ctx()
+            .fixed_timestep
+            .map_or(0.0, |fixed| fixed.alpha)
+    }
+
     /// Return the table stored in the iterator as an `Archetype` object
     pub fn archetype(&self) -> Option<Archetype<'a>> {
         self.table().map(|t| t.archetype())
@@ -260,6 +281,153 @@ where
         self.iter.ctx
     }
 
+    /// Get the typed context of the system, observer or query that is being iterated.
+    ///
+    /// # Type Parameters
+    ///
+    /// * `C` - The type of the context
+    ///
+    /// # Returns
+    ///
+    /// A guard that derefs to the context that was set with `ctx()` on the builder. The
+    /// context can't be mutably borrowed by a nested iteration while the guard is alive.
+    ///
+    /// # Panics
+    ///
+    /// Panics if no typed context was set, if the context is not of type `C`, or if the
+    /// context is mutably borrowed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use flecs_ecs::prelude::*;
+    ///
+    /// #[derive(Component)]
+    /// struct Position {
+    ///     x: f32,
+    ///     y: f32,
+    /// }
+    ///
+    /// struct Bounds {
+    ///     max_x: f32,
+    /// }
+    ///
+    /// let world = World::new();
+    ///
+    /// world
+    ///     .system::<&mut Position>()
+    ///     .ctx(Bounds { max_x: 10.0 })
+    ///     .each_iter(|it, _, pos| {
+    ///         let bounds = it.ctx::<Bounds>();
+    ///         pos.x = pos.x.min(bounds.max_x);
+    ///     });
+    ///
+    /// let e = world.entity().set(Position { x: 20.0, y: 0.0 });
+    ///
+    /// world.progress();
+    ///
+    /// e.get::<&Position>(|pos| assert_eq!(pos.x, 10.0));
+    /// ```
+    ///
+    /// # See also
+    ///
+    /// * [`TableIter::ctx_mut()`]
+    /// * [`TableIter::try_ctx()`]
+    /// * [`SystemAPI::ctx()`]
+    /// * [`QueryBuilder::ctx()`]
+    pub fn ctx<C: 'static>(&self) -> CtxRef<'_, C> {
+        let (ctx, _) = self.typed_ctx().expect("iterator has no typed context");
+        let ctx = unsafe { &*ctx };
+        CtxRef::new(ctx).unwrap_or_else(|| {
+            panic!(
+                "context is of type `{}`, not `{}`",
+                ctx.type_name(),
+                core::any::type_name::<C>()
+            )
+        })
+    }
+
+    /// Get mutable access to the typed context of the system, observer or query that is
+    /// being iterated.
+    ///
+    /// # Type Parameters
+    ///
+    /// * `C` - The type of the context
+    ///
+    /// # Returns
+    ///
+    /// A guard that mutably derefs to the context that was set with `ctx()` on the builder.
+    /// The context can't be borrowed by a nested iteration while the guard is alive.
+    ///
+    /// # Panics
+    ///
+    /// Panics if no typed context was set, if the context is not of type `C`, if the context
+    /// is already borrowed, or if the iterator belongs to a multi threaded system, as threads
+    /// would alias the context.
+    ///
+    /// # See also
+    ///
+    /// * [`TableIter::ctx()`]
+    pub fn ctx_mut<C: 'static>(&mut self) -> CtxRefMut<'_, C> {
+        let (ctx, multi_threaded) = self.typed_ctx().expect("iterator has no typed context");
+        assert!(
+            !multi_threaded,
+            "cannot mutably access the context of a multi threaded system"
+        );
+        let type_name = unsafe { &*ctx }.type_name();
+        unsafe { CtxRefMut::new(ctx) }.unwrap_or_else(|| {
+            panic!(
+                "context is of type `{type_name}`, not `{}`",
+                core::any::type_name::<C>()
+            )
+        })
+    }
+
+    /// Get the typed context of the system, observer or query that is being iterated.
+    ///
+    /// # Type Parameters
+    ///
+    /// * `C` - The type of the context
+    ///
+    /// # Returns
+    ///
+    /// A guard that derefs to the context that was set with `ctx()` on the builder, or `None`
+    /// if no typed context was set or the context is not of type `C`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the context is mutably borrowed.
+    ///
+    /// # See also
+    ///
+    /// * [`TableIter::ctx()`]
+    pub fn try_ctx<C: 'static>(&self) -> Option<CtxRef<'_, C>> {
+        let (ctx, _) = self.typed_ctx()?;
+        CtxRef::new(unsafe { &*ctx })
+    }
+
+    /// Returns the typed context of the object that is being iterated, and whether that
+    /// object is a multi threaded system.
+    fn typed_ctx(&self) -> Option<(*mut TypedCtx, bool)> {
+        let world = self.iter.real_world;
+        let entity = self.iter.system;
+
+        if entity != 0 {
+            #[cfg(feature = "flecs_system")]
+            if let Some(system) = unsafe { sys::ecs_system_get(world, entity).as_ref() } {
+                return TypedCtx::is_typed(system.ctx_free)
+                    .then_some((system.ctx as *mut TypedCtx, system.multi_threaded));
+            }
+
+            if let Some(observer) = unsafe { sys::ecs_observer_get(world, entity).as_ref() } {
+                return TypedCtx::is_typed(observer.ctx_free)
+                    .then_some((observer.ctx as *mut TypedCtx, false));
+            }
+        }
+
+        QueryBindingCtx::typed_ctx(self.iter.query).map(|ctx| (ctx, false))
+    }
+
     /// Access param.
     /// param contains the pointer passed to the param argument of `system::run`
     ///
@@ -1383,6 +1551,24 @@ where
         unsafe { sys::ecs_iter_get_group(self.iter) }
     }
 
+    /// Get the typed context of the group of the current table
+    ///
+    /// # Type Parameters
+    ///
+    /// * `C` - The type of the group context
+    ///
+    /// # Returns
+    ///
+    /// The context created by [`QueryBuilderImpl::on_group_create_with()`], or `None` if the
+    /// query has no typed group contexts or the context is not of type `C`.
+    pub fn group_ctx<C: 'static>(&self) -> Option<&C> {
+        let query = self.iter.query;
+        if !QueryBindingCtx::get(query).is_some_and(|ctx| ctx.typed_group_ctx) {
+            return None;
+        }
+        QueryBindingCtx::group_ctx::<C>(query, self.group_id())
+    }
+
     #[inline(always)]
     pub(crate) fn field_result<T: ComponentId>(
         &self,
@@ -21,6 +21,7 @@
 //! - [`FieldUntyped`] and [`FieldUntypedMut`]: Untyped access for dynamic component types
 //! - [`FieldIndex`]: Type-safe index for accessing specific entity rows in a field
 //! - [`TableFlags`]: Bitflags describing table properties and capabilities
+//! - [`ZipColumns`]: Element-wise iteration over several component columns at once
 //!
 //! # Common Use Cases
 //!
@@ -98,12 +99,16 @@
 //! });
 //! ```
 
+mod columns;
+mod ctx;
 mod field;
 mod flags;
 mod iter;
 mod multi_src_get;
 
+pub use columns::{Column, TableColumns, ZipColumns, ZippedColumns};
 use core::{ffi::CStr, ffi::c_void, ptr::NonNull};
+pub use ctx::{CtxRef, CtxRefMut};
 pub use field::{Field, FieldAt, FieldAtMut, FieldIndex, FieldMut, FieldUntyped, FieldUntypedMut};
 pub(crate) use field::{flecs_field, flecs_field_w_size};
 pub use multi_src_get::*;
@@ -372,6 +377,142 @@ pub trait TableOperations<'a>: IntoTable {
             })
     }
 
+    /// Get several component columns of the table at once. [`TableColumns::columns()`] returns
+    /// them as a tuple of slices in the same form as the terms of a query: `&T` gives `&[T]`,
+    /// `&mut T` gives `&mut [T]` and `Option<&T>` gives `Option<&[T]>`.
+    ///
+    /// The table stays locked until the returned columns are dropped.
+    ///
+    /// # Type parameters
+    ///
+    /// * `T` - The tuple of components, such as `(&mut Position, &Velocity)`.
+    ///
+    /// # Returns
+    ///
+    /// The columns, or `None` if the table doesn't store a column of a component that isn't
+    /// optional, such as a tag or a sparse component.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a mutable column is requested more than once, or together with an immutable
+    /// column of the same component.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use flecs_ecs::prelude::*;
+    ///
+    /// #[derive(Component)]
+    /// struct Position {
+    ///     x: f32,
+    /// }
+    ///
+    /// #[derive(Component)]
+    /// struct Velocity {
+    ///     x: f32,
+    /// }
+    ///
+    /// let world = World::new();
+    /// for i in 0..4 {
+    ///     world
+    ///         .entity()
+    ///         .set(Position { x: 0.0 })
+    ///         .set(Velocity { x: i as f32 });
+    /// }
+    ///
+    /// let e = world.entity().set(Position { x: 0.0 }).set(Velocity { x: 0.0 });
+    /// let table = e.table().unwrap();
+    /// let mut columns = table
+    ///     .columns_mut::<(&mut Position, &Velocity)>()
+    ///     .unwrap();
+    /// let (positions, velocities) = columns.columns();
+    /// for (pos, vel) in positions.iter_mut().zip(velocities) {
+    ///     pos.x += vel.x;
+    /// }
+    /// drop(columns);
+    ///
+    /// assert!(table.columns_mut::<(&mut Position, Option<&Velocity>)>().is_some());
+    /// ```
+    ///
+    /// # See also
+    ///
+    /// * [`ZipColumns`]
+    fn columns_mut<T: QueryTuple>(&self) -> Option<TableColumns<'a, T>> {
+        let world = self.world();
+        let mut terms: [sys::ecs_term_t; sys::FLECS_TERM_COUNT_MAX as usize] = Default::default();
+        T::register_ids_descriptor_at(world.world_ptr_mut(), &mut terms, &mut 0);
+
+        let fields = &terms[..T::COUNT as usize];
+        let mut columns = [-1; sys::FLECS_TERM_COUNT_MAX as usize];
+        let mut components = [core::ptr::null_mut::<u8>(); sys::FLECS_TERM_COUNT_MAX as usize];
+        for (i, term) in fields.iter().enumerate() {
+            match self.find_column_index(term.id) {
+                Some(column) => {
+                    columns[i] = column;
+                    components[i] =
+                        self.column_untyped(column).unwrap_or(core::ptr::null_mut()) as *mut u8;
+                }
+                None if term.oper == OperKind::Optional as i16 => {}
+                None => return None,
+            }
+        }
+
+        // the locks are taken per kind of field, like the locks of a query
+        #[cfg(feature = "flecs_safety_locks")]
+        let table_records = {
+            let mut table_records =
+                [TableColumnSafety::default(); sys::FLECS_TERM_COUNT_MAX as usize];
+            let mut next = [
+                0,
+                T::COUNT_IMMUTABLE,
+                T::COUNT_IMMUTABLE + T::COUNT_MUTABLE,
+                T::COUNT_IMMUTABLE + T::COUNT_MUTABLE + T::COUNT_OPTIONAL_IMMUTABLE,
+            ];
+            for (i, term) in fields.iter().enumerate() {
+                let kind = match (
+                    term.inout == InOutKind::In as i16,
+                    term.oper == OperKind::Optional as i16,
+                ) {
+                    (true, false) => 0,
+                    (false, false) => 1,
+                    (true, true) => 2,
+                    (false, true) => 3,
+                };
+                if columns[i] != -1 {
+                    table_records[next[kind]].table_record = unsafe {
+                        sys::flecs_component_get_table(
+                            sys::flecs_components_get(world.world_ptr(), term.id),
+                            self.table_ptr_mut(),
+                        )
+                    };
+                }
+                next[kind] += 1;
+            }
+            table_records
+        };
+
+        for (i, term) in fields.iter().enumerate() {
+            if term.inout == InOutKind::In as i16 || columns[i] == -1 {
+                continue;
+            }
+            if let Some(other) = (0..fields.len()).find(|&j| j != i && columns[j] == columns[i]) {
+                panic!(
+                    "the column of {} is borrowed mutably by field {i} and also by field {other}",
+                    IdView::new_from_id(world, term.id).to_str()
+                );
+            }
+        }
+
+        Some(TableColumns::new(
+            world,
+            self.table_ptr_mut(),
+            self.count() as usize,
+            components,
+            #[cfg(feature = "flecs_safety_locks")]
+            table_records,
+        ))
+    }
+
     /// Get column, components array ptr from table by component type.
     ///
     /// # Arguments
@@ -1,8 +1,13 @@
-#![doc(hidden)]
+//! Error codes used by the ecs asserts and the error type returned by the fallible `try_*` operations.
 use core::fmt::{Display, Formatter};
 
-#[doc(hidden)]
-/// Enum representing the error codes that can be used by `ecs_asserts` and `ecs_abort`
+extern crate alloc;
+use alloc::string::String;
+
+/// Enum representing the error codes that can be used by `ecs_asserts` and `ecs_abort`.
+///
+/// The codes mirror the error codes of the C API and are carried by [`FlecsError`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum FlecsErrorCode {
     InvalidOperation,
     InvalidParameter,
@@ -126,6 +131,72 @@ impl FlecsErrorCode {
     }
 }
 
+/// Error returned by the fallible `try_*` operations, such as [`EntityView::try_set()`]
+/// and [`World::try_make_alive()`].
+///
+/// Where the regular operations would trip a (C) assert and abort the process,
+/// the `try_*` variants check the preconditions up front and return this error instead.
+///
+/// # Example
+///
+/// ```
+/// use flecs_ecs::prelude::*;
+///
+/// #[derive(Component)]
+/// struct Position {
+///     x: f32,
+///     y: f32,
+/// }
+///
+/// let world = World::new();
+///
+/// let e = world.entity();
+/// e.destruct();
+///
+/// let err = e.try_set(Position { x: 1.0, y: 2.0 }).unwrap_err();
+/// assert_eq!(err.code(), FlecsErrorCode::InvalidParameter);
+/// ```
+///
+/// [`EntityView::try_set()`]: crate::core::EntityView::try_set
+/// [`World::try_make_alive()`]: crate::core::World::try_make_alive
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FlecsError {
+    code: FlecsErrorCode,
+    message: String,
+}
+
+impl FlecsError {
+    /// Create a new error with the provided code and message.
+    pub fn new(code: FlecsErrorCode, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+        }
+    }
+
+    /// The error code of the failed precondition.
+    pub fn code(&self) -> FlecsErrorCode {
+        self.code
+    }
+
+    /// Human readable description of what went wrong.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl Display for FlecsError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        if self.message.is_empty() {
+            write!(f, "{}", self.code)
+        } else {
+            write!(f, "{}: {}", self.code, self.message)
+        }
+    }
+}
+
+impl core::error::Error for FlecsError {}
+
 /// Macro to assert a condition.
 /// In release mode, the condition is not checked.
 /// Can be turned off by disabling the `flecs_ecs_asserts` feature
@@ -575,6 +575,169 @@ pub(crate) fn check_add_id_validity(world: *const sys::ecs_world_t, id: u64) {
     }
 }
 
+/// Fallible counterpart of [`check_add_id_validity`], used by the `try_*` operations.
+pub(crate) fn try_check_add_id_validity(
+    world: *const sys::ecs_world_t,
+    id: u64,
+) -> Result<(), FlecsError> {
+    check_id_valid(world, id, false, "add")?;
+
+    let is_not_tag = unsafe { sys::ecs_get_typeid(world, id) != 0 };
+
+    if is_not_tag && !has_default_hook(world, id) {
+        return Err(FlecsError::new(
+            FlecsErrorCode::InvalidParameter,
+            "id is not a zero-sized type (ZST) and does not implement the Default hook",
+        ));
+    }
+
+    Ok(())
+}
+
+/// Checks that `entity` is a valid, alive entity before `operation` uses it.
+pub(crate) fn check_entity_alive(
+    world: *const sys::ecs_world_t,
+    entity: u64,
+    operation: &str,
+) -> Result<(), FlecsError> {
+    let reason = if entity == 0 {
+        "entity id is 0"
+    } else if entity & sys::ECS_COMPONENT_MASK != entity {
+        "entity id contains flag bits"
+    } else if !unsafe { sys::ecs_is_alive(world, entity) } {
+        "entity is not alive"
+    } else {
+        return Ok(());
+    };
+
+    Err(FlecsError::new(
+        FlecsErrorCode::InvalidParameter,
+        format!("invalid entity #{entity} passed to {operation}(): {reason}"),
+    ))
+}
+
+/// Checks that `id` is a valid component, pair or entity before `operation` uses it.
+///
+/// Unlike `ecs_id_is_valid` this also rejects ids (or pair elements) that refer to entities
+/// that are no longer alive. Wildcards are only accepted when `allow_wildcard` is set,
+/// such as for remove operations.
+pub(crate) fn check_id_valid(
+    world: *const sys::ecs_world_t,
+    id: u64,
+    allow_wildcard: bool,
+    operation: &str,
+) -> Result<(), FlecsError> {
+    let is_alive_or_wildcard =
+        |e: u64| e == ECS_WILDCARD || e == ECS_ANY || unsafe { sys::ecs_get_alive(world, e) != 0 };
+
+    let reason = if id == 0 {
+        "id is 0 (is the component registered?)"
+    } else if !allow_wildcard && unsafe { sys::ecs_id_is_wildcard(id) } {
+        "cannot add wildcards"
+    } else if ecs_is_pair(id) {
+        let first = ecs_entity_id_high(id & RUST_ECS_COMPONENT_MASK);
+        let second = ecs_entity_id_low(id);
+        if first == 0 || second == 0 {
+            "invalid pair: element is 0"
+        } else if !is_alive_or_wildcard(first) {
+            "invalid pair: first element is not alive"
+        } else if !is_alive_or_wildcard(second) {
+            "invalid pair: second element is not alive"
+        } else {
+            return Ok(());
+        }
+    } else if !is_alive_or_wildcard(id & RUST_ECS_COMPONENT_MASK) {
+        "entity is not alive"
+    } else {
+        return Ok(());
+    };
+
+    Err(FlecsError::new(
+        FlecsErrorCode::InvalidParameter,
+        format!("invalid id #{id} passed to {operation}(): {reason}"),
+    ))
+}
+
+/// Checks that `T` is registered with the world. Components are registered on first use
+/// unless the `flecs_manual_registration` feature is enabled, in which case this can fail.
+#[inline(always)]
+#[cfg_attr(
+    not(feature = "flecs_manual_registration"),
+    allow(clippy::extra_unused_type_parameters)
+)]
+pub(crate) fn check_component_registered<'a, T: ComponentId>(
+    world: impl WorldProvider<'a>,
+) -> Result<(), FlecsError> {
+    #[cfg(feature = "flecs_manual_registration")]
+    if !T::is_registered_with_world(world) {
+        return Err(FlecsError::new(
+            FlecsErrorCode::ComponentNotRegistered,
+            format!(
+                "component {} is not registered with the world before usage",
+                <T as ComponentOrPairId>::name()
+            ),
+        ));
+    }
+    #[cfg(not(feature = "flecs_manual_registration"))]
+    let _ = world;
+
+    Ok(())
+}
+
+/// Checks that adding `(relationship, target)` to `entity` does not create a cycle,
+/// which is the case when `entity` is `target` itself or one of its ancestors.
+pub(crate) fn check_no_cycle(
+    world: *const sys::ecs_world_t,
+    relationship: u64,
+    entity: u64,
+    target: u64,
+    operation: &str,
+) -> Result<(), FlecsError> {
+    fn reaches(world: *const sys::ecs_world_t, relationship: u64, from: u64, to: u64) -> bool {
+        if from == to {
+            return true;
+        }
+        let mut index = 0;
+        loop {
+            let next = unsafe { sys::ecs_get_target(world, from, relationship, index) };
+            if next == 0 {
+                return false;
+            }
+            if reaches(world, relationship, next, to) {
+                return true;
+            }
+            index += 1;
+        }
+    }
+
+    if reaches(world, relationship, target, entity) {
+        Err(FlecsError::new(
+            FlecsErrorCode::CycleDetected,
+            format!(
+                "{operation}() would create a cycle: #{target} is #{entity} or one of its descendants"
+            ),
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+/// Checks that the world is not in multi-threaded readonly mode, in which all
+/// mutations to the world itself are disallowed.
+pub(crate) fn check_not_multithreaded(
+    world: *const sys::ecs_world_t,
+    operation: &str,
+) -> Result<(), FlecsError> {
+    if unsafe { sys::ecs_world_get_flags(world) } & sys::EcsWorldMultiThreaded != 0 {
+        Err(FlecsError::new(
+            FlecsErrorCode::InvalidWhileReadonly,
+            format!("cannot call {operation}() while world is in multithreaded mode"),
+        ))
+    } else {
+        Ok(())
+    }
+}
+
 #[inline(never)]
 pub(crate) fn has_default_hook(world: *const sys::ecs_world_t, id: u64) -> bool {
     let hooks = unsafe { sys::ecs_get_hooks_id(world, id) };
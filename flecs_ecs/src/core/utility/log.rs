@@ -1,6 +1,18 @@
 //! sets various internal logging options
+//!
+//! By default flecs writes log messages to stderr. With the `flecs_tracing` feature, log messages
+//! are forwarded to `tracing` instead.
+use core::ffi::{CStr, c_char, c_void};
+
+#[cfg(feature = "std")]
+extern crate std;
+use std::sync::{Mutex, PoisonError};
+
 use crate::sys;
 
+extern crate alloc;
+use alloc::string::String;
+
 /// Sets the logging level to the specified value.
 ///
 /// # Arguments
@@ -53,3 +65,109 @@ pub fn enable_timedelta_logging(enabled: bool) {
         sys::ecs_log_enable_timedelta(enabled);
     }
 }
+
+/// Converts a string that was allocated by flecs, and frees it.
+pub(crate) fn take_log(log: *mut c_char) -> Option<String> {
+    if log.is_null() {
+        return None;
+    }
+
+    let str = unsafe { CStr::from_ptr(log) }
+        .to_string_lossy()
+        .into_owned();
+    unsafe { sys::ecs_os_api.free_.expect("os api is missing")(log as *mut c_void) };
+    Some(str)
+}
+
+/// Logs an error with the flecs log, like `ecs_err` does.
+#[track_caller]
+pub(crate) fn log_error(message: &str) {
+    let location = core::panic::Location::caller();
+    let file = alloc::format!("{}\0", location.file());
+    let message = alloc::format!("{}\0", message.replace('\0', ""));
+    unsafe {
+        sys::ecs_log_(
+            -3,
+            file.as_ptr() as *const c_char,
+            location.line() as i32,
+            c"%s".as_ptr(),
+            message.as_ptr() as *const c_char,
+        );
+    }
+}
+
+/// Serializes captures of the log, which flecs stores in a global.
+static CAPTURE: Mutex<()> = Mutex::new(());
+
+/// Runs a flecs operation while capturing the first error it logs.
+///
+/// Captures of different threads run one after the other. Captures can't be nested, so `f` must
+/// not call flecs operations that capture the log themselves, like `ecs_script_update`, or call
+/// this again.
+pub(crate) fn capture_log<R>(f: impl FnOnce() -> R) -> (R, Option<String>) {
+    let _capture = CAPTURE.lock().unwrap_or_else(PoisonError::into_inner);
+    unsafe { sys::ecs_log_start_capture(true) };
+    let result = f();
+    let log = unsafe { sys::ecs_log_stop_capture() };
+    (result, take_log(log))
+}
+
+/// A diagnostic that flecs logged for code that it failed to parse or run.
+///
+/// Syntax errors have the form `line: message`, followed by the offending line of the code and
+/// a line with a `^` under the offending token. Other errors only contain a message.
+pub(crate) struct Diagnostic<'a> {
+    /// The line of the error, starting at 1.
+    pub(crate) line: Option<u32>,
+    pub(crate) message: &'a str,
+    /// The offending line of the code.
+    pub(crate) snippet: Option<&'a str>,
+    /// Byte offset of the `^` in its line, which is the offset in `snippet` it points at.
+    pub(crate) caret: Option<usize>,
+}
+
+impl<'a> Diagnostic<'a> {
+    pub(crate) fn parse(log: Option<&'a str>) -> Self {
+        let mut lines = log.unwrap_or_default().lines();
+        let first = lines.next().unwrap_or_default();
+
+        let (line, message) = match first.split_once(": ") {
+            Some((line, message)) if line.bytes().all(|b| b.is_ascii_digit()) => {
+                (line.parse().ok(), message)
+            }
+            _ => (None, first),
+        };
+
+        let snippet = lines.next();
+        let caret = lines.next().and_then(|caret| caret.find('^'));
+
+        Self {
+            line,
+            message,
+            snippet,
+            caret,
+        }
+    }
+
+    /// The column of the offending token in its line, starting at 1, counted in characters.
+    ///
+    /// The caret can point at the whitespace before the offending token, which is skipped.
+    pub(crate) fn column(&self) -> Option<u32> {
+        let snippet = self.snippet?;
+        let offset = self.caret?.min(snippet.len());
+        if !snippet.is_char_boundary(offset) {
+            return None;
+        }
+        let skipped = snippet[offset..].len() - snippet[offset..].trim_start().len();
+        Some(snippet[..offset + skipped].chars().count() as u32 + 1)
+    }
+
+    /// The message, or `default` if flecs didn't log one.
+    pub(crate) fn message_or(&self, default: &'a str) -> &'a str {
+        if self.message.is_empty() {
+            default
+        } else {
+            self.message
+        }
+    }
+}
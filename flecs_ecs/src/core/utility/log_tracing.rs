@@ -0,0 +1,95 @@
+//! Forwards flecs log messages and performance traces to [`tracing`].
+//!
+//! Installed as an [`ecs_os_api`](crate::core::ecs_os_api) hook when the `flecs_tracing` feature
+//! is enabled. Log messages become events with the `flecs` target, and when the
+//! `flecs_perf_trace` feature is enabled as well, performance traces become spans.
+extern crate std;
+
+#[cfg(feature = "flecs_perf_trace")]
+extern crate alloc;
+
+use core::ffi::{CStr, c_char};
+
+use flecs_ecs_derive::extern_abi;
+use tracing::Level;
+
+use crate::sys;
+
+#[cfg(feature = "flecs_perf_trace")]
+use {alloc::vec::Vec, core::cell::RefCell, tracing::span::EnteredSpan};
+
+/// Converts a string from flecs, which may be null, to a `&str`.
+///
+/// # Safety
+///
+/// `ptr` must be null or point to a null-terminated string that outlives `'a`.
+unsafe fn to_str<'a>(ptr: *const c_char) -> &'a str {
+    if ptr.is_null() {
+        ""
+    } else {
+        unsafe { CStr::from_ptr(ptr) }.to_str().unwrap_or("")
+    }
+}
+
+#[extern_abi]
+fn log(level: i32, file: *const c_char, line: i32, msg: *const c_char) {
+    let message = unsafe { to_str(msg) };
+    let file = unsafe { to_str(file) };
+    let depth = unsafe { sys::ecs_os_api.log_indent_ };
+
+    // the level of an event is part of its static metadata, so each level gets its own event
+    macro_rules! forward {
+        ($level:expr) => {
+            tracing::event!(
+                target: "flecs",
+                $level,
+                level,
+                file,
+                line,
+                depth,
+                "{message}"
+            )
+        };
+    }
+
+    // positive levels are debug levels, where levels from 4 upwards are used by the journal
+    match level {
+        4.. => forward!(Level::TRACE),
+        1..=3 => forward!(Level::DEBUG),
+        0 => forward!(Level::INFO),
+        -2 => forward!(Level::WARN),
+        _ => forward!(Level::ERROR),
+    }
+}
+
+#[cfg(feature = "flecs_perf_trace")]
+std::thread_local! {
+    /// Spans of the performance traces that are pushed on this thread, innermost last.
+    static PERF_TRACE_SPANS: RefCell<Vec<EnteredSpan>> = const { RefCell::new(Vec::new()) };
+}
+
+#[cfg(feature = "flecs_perf_trace")]
+#[extern_abi]
+fn perf_trace_push(file: *const c_char, line: usize, name: *const c_char) {
+    let name = unsafe { to_str(name) };
+    let file = unsafe { to_str(file) };
+    let span = tracing::info_span!(target: "flecs", "perf_trace", name, file, line).entered();
+    PERF_TRACE_SPANS.with_borrow_mut(|spans| spans.push(span));
+}
+
+#[cfg(feature = "flecs_perf_trace")]
+#[extern_abi]
+fn perf_trace_pop(_file: *const c_char, _line: usize, _name: *const c_char) {
+    // flecs pushes and pops traces in order, so the innermost span is the one that ends
+    PERF_TRACE_SPANS.with_borrow_mut(|spans| drop(spans.pop()));
+}
+
+/// Installs the handlers that forward to [`tracing`] in the OS API.
+pub(crate) fn install(api: &mut sys::ecs_os_api_t) {
+    api.log_ = Some(log);
+    #[cfg(feature = "flecs_perf_trace")]
+    {
+        api.perf_trace_push_ = Some(perf_trace_push);
+        api.perf_trace_pop_ = Some(perf_trace_pop);
+    }
+}
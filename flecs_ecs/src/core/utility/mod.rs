@@ -6,6 +6,8 @@ mod functions;
 pub mod id;
 pub(crate) mod id_map;
 mod log;
+#[cfg(feature = "flecs_tracing")]
+pub(crate) mod log_tracing;
 #[cfg(feature = "flecs_safety_locks")]
 pub(crate) mod safety;
 pub mod traits;
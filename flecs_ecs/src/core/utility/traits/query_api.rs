@@ -955,6 +955,60 @@ where
         plan
     }
 
+    /// Get the plan of the query as a list of operations.
+    ///
+    /// This is the structured form of [`QueryAPI::plan()`], with the term, source, traversal
+    /// and variables of every operation, and whether the operation uses the query cache.
+    ///
+    /// # See also
+    ///
+    /// * [`QueryPlan`]
+    /// * [`QueryAPI::explain_profiled()`]
+    fn explain(&self) -> QueryPlan {
+        QueryPlan::new(self.query_ptr(), false)
+    }
+
+    /// Evaluate the query and get its plan with how often every operation ran.
+    ///
+    /// A high [`redo`](QueryOpProfile::redo) count shows which operation the query engine
+    /// backtracks into the most, such as an `up` traversal that has to search many parents.
+    /// The results are not passed to the application, and are not marked as changed.
+    ///
+    /// The counters are only kept when flecs is built in debug mode, which is the default for
+    /// debug builds. Otherwise [`QueryPlanOp::profile()`] returns `None`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use flecs_ecs::prelude::*;
+    ///
+    /// #[derive(Component)]
+    /// struct Position {
+    ///     x: f32,
+    ///     y: f32,
+    /// }
+    ///
+    /// let world = World::new();
+    /// let parent = world.entity().set(Position { x: 0.0, y: 0.0 });
+    /// for _ in 0..10 {
+    ///     world.entity().child_of(parent);
+    /// }
+    ///
+    /// let query = world
+    ///     .query::<&Position>()
+    ///     .term_at(0)
+    ///     .parent()
+    ///     .build();
+    ///
+    /// let plan = query.explain_profiled();
+    /// # #[cfg(debug_assertions)]
+    /// assert!(plan.ops().iter().all(|op| op.profile().is_some()));
+    /// println!("{plan}");
+    /// ```
+    fn explain_profiled(&self) -> QueryPlan {
+        QueryPlan::new(self.query_ptr(), true)
+    }
+
     fn iterable(&self) -> QueryIter<'_, P, T> {
         QueryIter::new(self.retrieve_iter(), self.iter_next_func())
     }
@@ -963,6 +1017,97 @@ where
         QueryIter::new(self.retrieve_iter_stage(stage), self.iter_next_func())
     }
 
+    /// Iterate the tables that the query matched, as [`QueryChunk`]s that give access to the
+    /// components of their entities as column slices.
+    ///
+    /// Each chunk keeps its table locked until it's dropped. With the `flecs_safety_locks`
+    /// feature, the components of the chunk are also locked for reading or writing.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the query writes a component that all entities share, such as a singleton.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use flecs_ecs::prelude::*;
+    ///
+    /// #[derive(Component)]
+    /// struct Position {
+    ///     x: f32,
+    /// }
+    ///
+    /// #[derive(Component)]
+    /// struct Velocity {
+    ///     x: f32,
+    /// }
+    ///
+    /// let world = World::new();
+    /// world.entity().set(Position { x: 0.0 }).set(Velocity { x: 1.0 });
+    /// world.entity().set(Position { x: 5.0 });
+    ///
+    /// let query = world.new_query::<(&mut Position, &Velocity)>();
+    /// for mut chunk in query.iter() {
+    ///     let (positions, velocities) = chunk.columns();
+    ///     for (pos, vel) in positions.iter_mut().zip(velocities) {
+    ///         pos.x += vel.x;
+    ///     }
+    /// }
+    ///
+    /// let counts = query.iter().map(|chunk| chunk.count()).collect::<Vec<_>>();
+    /// assert_eq!(counts, [1]);
+    /// ```
+    ///
+    /// # See also
+    ///
+    /// * [`QueryAPI::iter_rows()`]
+    /// * [`QueryAPI::run()`]
+    fn iter(&self) -> QueryChunks<'_, T> {
+        // the world outlives the query that the chunks borrow
+        let world = unsafe { WorldRef::from_ptr(self.world_ptr_mut()) };
+        QueryChunks::new(self.retrieve_iter(), self.iter_next_func(), world)
+    }
+
+    /// Iterate the entities that the query matched, as [`QueryRow`]s that give access to the
+    /// components of an entity.
+    ///
+    /// Each row keeps its table locked until it's dropped. With the `flecs_safety_locks`
+    /// feature, the components of the row are also locked for reading or writing.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the query writes a component that all entities share, such as a singleton.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use flecs_ecs::prelude::*;
+    ///
+    /// #[derive(Component)]
+    /// struct Position {
+    ///     x: f32,
+    /// }
+    ///
+    /// let world = World::new();
+    /// world.entity_named("a").set(Position { x: 1.0 });
+    /// world.entity_named("b").set(Position { x: -1.0 });
+    ///
+    /// let query = world.new_query::<&Position>();
+    /// let names = query
+    ///     .iter_rows()
+    ///     .filter_map(|mut row| (row.get().x > 0.0).then(|| row.entity().name()))
+    ///     .collect::<Vec<_>>();
+    /// assert_eq!(names, ["a"]);
+    /// ```
+    ///
+    /// # See also
+    ///
+    /// * [`QueryAPI::iter()`]
+    /// * [`QueryAPI::each_entity()`]
+    fn iter_rows(&self) -> QueryRows<'_, T> {
+        self.iter().rows()
+    }
+
     /// Return first matching entity.
     ///
     /// # Returns
@@ -16,6 +16,46 @@ where
     /// Set context
     fn set_context(&mut self, context: *mut c_void) -> &mut Self;
 
+    /// Set a typed context that is owned by the system or observer.
+    ///
+    /// The context can be accessed while iterating with [`TableIter::ctx()`] and
+    /// [`TableIter::ctx_mut()`], and is dropped when the system or observer is deleted.
+    /// Replaces a context that was set with [`SystemAPI::set_context()`].
+    ///
+    /// # Type Parameters
+    ///
+    /// * `C` - The type of the context. It must be `Send + Sync`, as multi threaded systems
+    ///   access it from several threads at once.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The context value
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use flecs_ecs::prelude::*;
+    ///
+    /// #[derive(Component)]
+    /// struct Position {
+    ///     x: f32,
+    ///     y: f32,
+    /// }
+    ///
+    /// let world = World::new();
+    ///
+    /// world
+    ///     .observer::<flecs::OnSet, &Position>()
+    ///     .ctx(Vec::<Entity>::new())
+    ///     .each_iter(|mut it, index, _| {
+    ///         let e = it.entity(index).id();
+    ///         it.ctx_mut::<Vec<Entity>>().push(e);
+    ///     });
+    ///
+    /// world.entity().set(Position { x: 1.0, y: 2.0 });
+    /// ```
+    fn ctx<C: Send + Sync + 'static>(&mut self, value: C) -> &mut Self;
+
     /// Each iterator for systems.
     ///
     /// The "each" iterator accepts a function that is invoked for each matching entity.
@@ -1034,9 +1074,15 @@ macro_rules! implement_reactor_api {
             T: QueryTuple,
         {
             fn set_context(&mut self, context: *mut c_void) -> &mut Self {
+                TypedCtx::clear(&mut self.desc.ctx, &mut self.desc.ctx_free);
                 self.desc.ctx = context;
                 self
             }
+
+            fn ctx<C: Send + Sync + 'static>(&mut self, value: C) -> &mut Self {
+                TypedCtx::set(&mut self.desc.ctx, &mut self.desc.ctx_free, value);
+                self
+            }
         }
     };
     ($type:ty) => {
@@ -1090,9 +1136,15 @@ macro_rules! implement_reactor_api {
             P: ComponentId,
         {
             fn set_context(&mut self, context: *mut c_void) -> &mut Self {
+                TypedCtx::clear(&mut self.desc.ctx, &mut self.desc.ctx_free);
                 self.desc.ctx = context;
                 self
             }
+
+            fn ctx<C: Send + Sync + 'static>(&mut self, value: C) -> &mut Self {
+                TypedCtx::set(&mut self.desc.ctx, &mut self.desc.ctx_free, value);
+                self
+            }
         }
     };
 }
@@ -18,9 +18,11 @@ mod operations;
 mod pipeline;
 mod query;
 mod singleton;
+mod snapshot;
 #[cfg(feature = "flecs_system")]
 mod system;
 mod world;
 
 pub use singleton::*;
+pub use snapshot::WorldSnapshot;
 pub use world::*;
@@ -1183,6 +1183,45 @@ impl World {
         })
     }
 
+    /// Set the current scope, returning an error instead of panicking when the scope
+    /// entity is not alive. Passing `0` resets the scope to the root.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The ID of the scope entity to set.
+    ///
+    /// # Returns
+    ///
+    /// Returns an `EntityView` representing the previous set scope.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use flecs_ecs::prelude::*;
+    ///
+    /// let world = World::new();
+    ///
+    /// let e = world.entity_named("scope");
+    /// e.destruct();
+    ///
+    /// assert!(world.try_set_scope(e).is_err());
+    /// assert!(world.get_scope().is_none());
+    /// ```
+    ///
+    /// # See also
+    ///
+    /// * [`World::get_scope()`]
+    /// * [`World::set_scope()`]
+    pub fn try_set_scope(&self, id: impl IntoId) -> Result<EntityView<'_>, FlecsError> {
+        let id = *id.into_id(self);
+        if id != 0 {
+            check_entity_alive(self.raw_world.as_ptr(), id, "set_scope")?;
+        }
+        Ok(EntityView::new_from(self, unsafe {
+            sys::ecs_set_scope(self.raw_world.as_ptr(), id)
+        }))
+    }
+
     /// Sets the search path for entity lookup operations.
     ///
     /// This function configures the search path used for looking up an entity.
@@ -1376,6 +1415,25 @@ impl World {
         set_helper(self.raw_world.as_ptr(), id, component, id);
     }
 
+    /// Sets a singleton component of type `T` on the world, returning an error
+    /// instead of panicking when the component is not registered.
+    ///
+    /// # Arguments
+    ///
+    /// * `component` - The singleton component to set on the world.
+    ///
+    /// # See also
+    ///
+    /// * [`World::set()`]
+    pub fn try_set<T: ComponentId + DataComponent + ComponentType<Struct>>(
+        &self,
+        component: T,
+    ) -> Result<(), FlecsError> {
+        check_component_registered::<T>(self)?;
+        self.set(component);
+        Ok(())
+    }
+
     /// Set a singleton pair using the second element type and a first id.
     ///
     /// # Safety
@@ -1803,6 +1861,22 @@ impl World {
         }
     }
 
+    /// Delete all entities with the given id, returning an error instead of
+    /// panicking when the id is not a valid component, pair or entity.
+    /// Wildcard ids are accepted.
+    ///
+    /// # Arguments
+    ///
+    /// * `id`: The id to delete.
+    pub fn try_delete_entities_with(&self, id: impl IntoId) -> Result<(), FlecsError> {
+        let id = *id.into_id(self);
+        check_id_valid(self.raw_world.as_ptr(), id, true, "delete_with")?;
+        unsafe {
+            sys::ecs_delete_with(self.raw_world.as_ptr(), id);
+        }
+        Ok(())
+    }
+
     /// Delete all entities with the given enum constant
     ///
     /// # Type Parameters
@@ -1855,6 +1929,21 @@ impl World {
         }
     }
 
+    /// Remove all instances of the given id from entities, returning an error instead
+    /// of panicking when the id is not a valid component, pair or entity.
+    ///
+    /// # Arguments
+    ///
+    /// * `id`: The id to remove.
+    pub fn try_remove_all(&self, id: impl IntoId) -> Result<(), FlecsError> {
+        let id = *id.into_id(self);
+        check_id_valid(self.raw_world.as_ptr(), id, true, "remove_all")?;
+        unsafe {
+            sys::ecs_remove_all(self.raw_world.as_ptr(), id);
+        }
+        Ok(())
+    }
+
     /// Remove all instances with the given enum constant from entities
     ///
     /// # Type Parameters
@@ -1966,6 +2055,66 @@ impl World {
         EntityView::new_from(self, entity)
     }
 
+    /// Ensures that entity with provided generation is alive, returning an error
+    /// where [`World::make_alive()`] would panic.
+    ///
+    /// This fails when the entity is 0, when an entity exists with the same id and a
+    /// different generation, or when the world is in multithreaded mode.
+    ///
+    /// # Arguments
+    ///
+    /// * `entity` - The entity to ensure is alive.
+    ///
+    /// # Returns
+    ///
+    /// The entity with the provided generation.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use flecs_ecs::prelude::*;
+    ///
+    /// let world = World::new();
+    ///
+    /// let e = world.entity();
+    /// e.destruct();
+    /// let recycled = world.entity();
+    ///
+    /// // `e` shares its id with `recycled`, but has an older generation
+    /// let err = world.try_make_alive(e).unwrap_err();
+    /// assert_eq!(err.code(), FlecsErrorCode::InvalidOperation);
+    /// assert!(world.try_make_alive(recycled).is_ok());
+    /// ```
+    pub fn try_make_alive(&self, entity: impl Into<Entity>) -> Result<EntityView<'_>, FlecsError> {
+        let entity = *entity.into();
+        let world = self.raw_world.as_ptr();
+
+        if entity == 0 {
+            return Err(FlecsError::new(
+                FlecsErrorCode::InvalidParameter,
+                "invalid entity #0 passed to make_alive()",
+            ));
+        }
+
+        check_not_multithreaded(world, "make_alive")?;
+
+        let current = unsafe { sys::ecs_get_alive(world, entity as u32 as u64) };
+        if current != 0 && current != entity {
+            return Err(FlecsError::new(
+                FlecsErrorCode::InvalidOperation,
+                format!(
+                    "entity {} is alive with different generation ({} vs {})",
+                    entity as u32,
+                    current >> 32,
+                    entity >> 32
+                ),
+            ));
+        }
+
+        unsafe { sys::ecs_make_alive(world, entity) };
+        Ok(EntityView::new_from(self, entity))
+    }
+
     /// Run callback after completing frame
     ///
     /// # Arguments
@@ -85,6 +85,67 @@ impl World {
         })
     }
 
+    /// Get the schedule of a pipeline.
+    ///
+    /// The schedule lists the systems of the pipeline in the order in which they run, grouped
+    /// into the segments between the sync points at which the pipeline merges commands. Each
+    /// segment records why the merge before it was inserted, which helps to find out why a
+    /// pipeline has a sync point. The schedule can be rendered with
+    /// [`PipelineSchedule::to_dot()`] and [`PipelineSchedule::to_mermaid()`].
+    ///
+    /// The schedule is the one that flecs runs the pipeline with. It is built first if the
+    /// systems of the pipeline changed, unless the world is in readonly mode, such as while
+    /// a system runs.
+    ///
+    /// # Arguments
+    ///
+    /// * `pipeline` - The pipeline, for example [`World::get_pipeline()`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use flecs_ecs::prelude::*;
+    ///
+    /// #[derive(Component)]
+    /// struct Position {
+    ///     x: f32,
+    /// }
+    ///
+    /// let world = World::new();
+    /// world.entity().set(Position { x: 0.0 });
+    ///
+    /// // enqueues a command that sets Position
+    /// world
+    ///     .system_named::<()>("Spawn")
+    ///     .write(Position::id())
+    ///     .run(|mut it| while it.next() {});
+    ///
+    /// // reads Position from the main storage, which requires a merge first
+    /// world
+    ///     .system_named::<&Position>("Print")
+    ///     .kind(flecs::pipeline::PostUpdate)
+    ///     .each(|_| {});
+    ///
+    /// let schedule = world.pipeline_schedule(world.get_pipeline());
+    /// let segments = schedule.segments();
+    /// assert_eq!(segments.len(), 2);
+    /// assert_eq!(segments[1].systems()[0].system().name(), "Print");
+    /// assert!(matches!(segments[1].sync_reason(), Some(SyncReason::Component(_))));
+    ///
+    /// println!("{}", schedule.to_mermaid());
+    /// ```
+    ///
+    /// [`PipelineSchedule::to_dot()`]: crate::addons::pipeline::PipelineSchedule::to_dot
+    /// [`PipelineSchedule::to_mermaid()`]: crate::addons::pipeline::PipelineSchedule::to_mermaid
+    pub fn pipeline_schedule(
+        &self,
+        pipeline: impl IntoEntity,
+    ) -> crate::addons::pipeline::PipelineSchedule<'_> {
+        let pipeline =
+            crate::addons::pipeline::ordering::apply_to_pipeline(self, pipeline.into_entity(self));
+        crate::addons::pipeline::pipeline_schedule(self, pipeline)
+    }
+
     /// Progress world one tick.
     ///
     /// Progresses the world by running all enabled and periodic systems
@@ -128,9 +189,14 @@ impl World {
     /// # See also
     ///
     /// * [`World::progress()`]
+    /// * [`World::set_fixed_timestep()`]
     /// * C API: `ecs_progress`
     #[inline(always)]
     pub fn progress_time(&self, delta_time: f32) -> bool {
+        crate::addons::pipeline::ordering::apply(self);
+        if self.world_ctx().fixed_timestep.is_some() {
+            return crate::addons::pipeline::progress(self, delta_time);
+        }
         unsafe { sys::ecs_progress(self.raw_world.as_ptr(), delta_time) }
     }
 
@@ -185,12 +251,10 @@ impl World {
     #[inline(always)]
     pub fn run_pipeline_time(&self, pipeline: impl IntoEntity, delta_time: FTime) {
         let world = self.world();
+        let pipeline =
+            crate::addons::pipeline::ordering::apply_to_pipeline(self, pipeline.into_entity(world));
         unsafe {
-            sys::ecs_run_pipeline(
-                self.raw_world.as_ptr(),
-                *pipeline.into_entity(world),
-                delta_time,
-            );
+            sys::ecs_run_pipeline(self.raw_world.as_ptr(), *pipeline, delta_time);
         }
     }
 
@@ -277,6 +341,219 @@ impl World {
         }
     }
 
+    /// Set a fixed timestep.
+    ///
+    /// Each frame, [`World::progress()`] adds the time passed since the last frame to an
+    /// accumulator, and runs the systems of the [`FixedUpdate`] phase once for each step that
+    /// fits in the accumulator, with `step` as their delta time. The steps run before the
+    /// systems of the default pipeline, which keep running once per frame. The fraction of a
+    /// step that remains in the accumulator is available to render systems as an interpolation
+    /// alpha, with [`TableIter::fixed_timestep_alpha()`].
+    ///
+    /// Calling this again changes the step and the maximum number of steps, but keeps the
+    /// accumulator.
+    ///
+    /// # Arguments
+    ///
+    /// * `step` - The delta time of a step. Pass `0.0` to remove the fixed timestep.
+    /// * `max_steps` - The maximum number of steps per frame, or `0` to not limit the number of
+    ///   steps. When more steps fit in a frame, the time of the remaining steps is dropped, so
+    ///   that a slow frame doesn't cause the next frames to be slow as well.
+    ///
+    /// # See also
+    ///
+    /// * [`World::fixed_timestep()`]
+    /// * [`FixedUpdate`]
+    ///
+    /// [`FixedUpdate`]: crate::addons::pipeline::FixedUpdate
+    pub fn set_fixed_timestep(&self, step: FTime, max_steps: i32) {
+        let ctx = self.world_ctx_mut();
+        if step <= 0.0 {
+            if let Some(fixed) = ctx.fixed_timestep.take() {
+                self.entity_from_id(fixed.pipeline).destruct();
+            }
+            return;
+        }
+
+        if let Some(fixed) = ctx.fixed_timestep.as_mut() {
+            fixed.step = step;
+            fixed.max_steps = max_steps;
+            return;
+        }
+
+        let pipeline = crate::addons::pipeline::fixed_pipeline_init(self);
+        self.world_ctx_mut().fixed_timestep = Some(crate::addons::pipeline::FixedTimestep {
+            step,
+            max_steps,
+            accumulator: 0.0,
+            alpha: 0.0,
+            steps: 0,
+            pipeline,
+        });
+    }
+
+    /// Get the fixed timestep.
+    ///
+    /// # Returns
+    ///
+    /// The state of the fixed timestep, or `None` if the world doesn't have a fixed timestep.
+    ///
+    /// # See also
+    ///
+    /// * [`World::set_fixed_timestep()`]
+    pub fn fixed_timestep(&self) -> Option<crate::addons::pipeline::FixedTimestep> {
+        self.world_ctx().fixed_timestep
+    }
+
+    /// Create a named [`SystemSet`], or get it if it already exists.
+    ///
+    /// Systems are added to the set with [`SystemBuilder::in_set()`]. The set can be ordered
+    /// before or after other systems and sets, which orders all of its systems.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the set.
+    ///
+    /// # See also
+    ///
+    /// * [`SystemBuilder::before()`]
+    /// * [`SystemBuilder::after()`]
+    ///
+    /// [`SystemSet`]: crate::addons::pipeline::SystemSet
+    /// [`SystemBuilder::in_set()`]: crate::addons::system::SystemBuilder::in_set
+    /// [`SystemBuilder::before()`]: crate::addons::system::SystemBuilder::before
+    /// [`SystemBuilder::after()`]: crate::addons::system::SystemBuilder::after
+    pub fn system_set(&self, name: &str) -> crate::addons::pipeline::SystemSet<'_> {
+        crate::addons::pipeline::SystemSet::new(self.entity_named(name))
+    }
+
+    /// Check the ordering constraints of the systems.
+    ///
+    /// The systems of each phase are sorted by their constraints when the constraints change.
+    /// If they contain a cycle, the error is logged and the systems keep their previous order.
+    ///
+    /// # Returns
+    ///
+    /// An error with the names of the systems in a cycle, if there is one.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use flecs_ecs::prelude::*;
+    ///
+    /// let world = World::new();
+    ///
+    /// let a = world.system_named::<()>("A").run(|mut it| while it.next() {});
+    /// let b = world
+    ///     .system_named::<()>("B")
+    ///     .after(a)
+    ///     .run(|mut it| while it.next() {});
+    /// world
+    ///     .system_named::<()>("C")
+    ///     .after(b)
+    ///     .before(a)
+    ///     .run(|mut it| while it.next() {});
+    ///
+    /// let err = world.validate_system_order().unwrap_err();
+    /// assert_eq!(err.phase(), "OnUpdate");
+    /// assert_eq!(err.systems(), ["A", "B", "C"]);
+    /// assert_eq!(
+    ///     err.to_string(),
+    ///     "cycle in the order of the systems of phase OnUpdate: A -> B -> C -> A"
+    /// );
+    /// ```
+    pub fn validate_system_order(&self) -> Result<(), crate::addons::pipeline::SystemOrderError> {
+        crate::addons::pipeline::ordering::validate(self)
+    }
+
+    /// Start the profiler, which records when systems run and when commands are merged at sync
+    /// points, on each thread that runs systems. Spans that were recorded before are dropped.
+    ///
+    /// The profiler records the performance traces of all worlds in the process. Write the
+    /// recorded spans with [`World::write_chrome_trace()`].
+    ///
+    /// # Arguments
+    ///
+    /// * `capacity` - The number of spans that each thread keeps. When a thread has recorded
+    ///   more spans, its oldest spans are dropped.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use flecs_ecs::prelude::*;
+    ///
+    /// let world = World::new();
+    /// world.set_threads(4);
+    ///
+    /// world.system_named::<()>("Move").each(|_| {});
+    ///
+    /// world.start_profiler(10_000);
+    /// world.progress();
+    /// world.stop_profiler();
+    ///
+    /// let trace = world.chrome_trace();
+    /// assert!(trace.contains("\"name\":\"Move\""));
+    /// ```
+    ///
+    /// # See also
+    ///
+    /// * [`World::stop_profiler()`]
+    /// * [`World::chrome_trace()`]
+    #[cfg(feature = "flecs_perf_trace")]
+    pub fn start_profiler(&self, capacity: usize) {
+        crate::addons::pipeline::profiler::start(capacity);
+    }
+
+    /// Stop the profiler. The spans that were recorded are kept until the profiler is started
+    /// again.
+    ///
+    /// # See also
+    ///
+    /// * [`World::start_profiler()`]
+    #[cfg(feature = "flecs_perf_trace")]
+    pub fn stop_profiler(&self) {
+        crate::addons::pipeline::profiler::stop();
+    }
+
+    /// Returns whether the profiler is recording.
+    ///
+    /// # See also
+    ///
+    /// * [`World::start_profiler()`]
+    #[cfg(feature = "flecs_perf_trace")]
+    pub fn is_profiler_recording(&self) -> bool {
+        crate::addons::pipeline::profiler::is_recording()
+    }
+
+    /// Get the spans recorded by the profiler as a Chrome trace, in the JSON trace event
+    /// format that `chrome://tracing` and Perfetto open.
+    ///
+    /// Each thread is a track of the trace. System runs have the name of the phase of the system
+    /// as category, and merges of commands at sync points have the `sync` category.
+    ///
+    /// # See also
+    ///
+    /// * [`World::start_profiler()`]
+    /// * [`World::write_chrome_trace()`]
+    #[cfg(feature = "flecs_perf_trace")]
+    pub fn chrome_trace(&self) -> String {
+        crate::addons::pipeline::profiler::chrome_trace(self)
+    }
+
+    /// Write the spans recorded by the profiler to a file, as a Chrome trace.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the file to write.
+    ///
+    /// # See also
+    ///
+    /// * [`World::chrome_trace()`]
+    #[cfg(feature = "flecs_perf_trace")]
+    pub fn write_chrome_trace(&self, path: impl AsRef<std::path::Path>) -> std::io::Result<()> {
+        std::fs::write(path, self.chrome_trace())
+    }
+
     /// Reset world clock. Reset the clock that keeps track of the total time passed in the simulation.
     #[inline(always)]
     pub fn reset_clock(&self) {
@@ -147,6 +147,80 @@ impl World {
             .expect("entity / query is not alive or valid")
     }
 
+    /// Create an untyped [`Query`] from a query expression in the flecs query DSL.
+    ///
+    /// Unlike [`QueryBuilder::expr()`], which panics in `build()` when the expression is
+    /// invalid, this returns an error with the location of the offending token, what was
+    /// expected instead and the term that contains it.
+    ///
+    /// # Arguments
+    ///
+    /// * `expr` - The query expression, such as `"Position, [in] Velocity(up ChildOf), !Dead"`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use flecs_ecs::prelude::*;
+    ///
+    /// #[derive(Component)]
+    /// struct Position {
+    ///     x: f32,
+    ///     y: f32,
+    /// }
+    ///
+    /// let world = World::new();
+    /// world.component_named::<Position>("Position");
+    /// world.entity().set(Position { x: 1.0, y: 2.0 });
+    ///
+    /// let query = world.try_query_from_expr("Position, !Prefab").unwrap();
+    /// assert_eq!(query.count(), 1);
+    ///
+    /// let err = world.try_query_from_expr("Position, [in Velocity").err().unwrap();
+    /// assert_eq!(err.column(), Some(12));
+    /// assert_eq!(err.expected(), Some("']'"));
+    /// assert_eq!(err.term(), Some("[in Velocity"));
+    /// ```
+    ///
+    /// # See also
+    ///
+    /// * [`World::validate_query_expr()`]
+    /// * [`QueryBuilder::expr()`]
+    pub fn try_query_from_expr(&self, expr: &str) -> Result<Query<()>, QueryParseError> {
+        query_expr::query_from_expr(self, expr)
+    }
+
+    /// Check whether a query expression is valid without keeping the query, such as to report
+    /// mistakes while the expression is edited.
+    ///
+    /// The query is created and destroyed right away, so the names in the expression are
+    /// resolved against the current state of the world.
+    ///
+    /// # Arguments
+    ///
+    /// * `expr` - The query expression.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use flecs_ecs::prelude::*;
+    ///
+    /// let world = World::new();
+    ///
+    /// assert!(world.validate_query_expr("(ChildOf, $parent), !Prefab").is_ok());
+    ///
+    /// let err = world.validate_query_expr("ChildOf, Velocity").unwrap_err();
+    /// assert_eq!(err.message(), "unresolved identifier 'Velocity'");
+    /// assert_eq!(err.column(), Some(10));
+    /// assert_eq!(err.term(), Some("Velocity"));
+    /// ```
+    ///
+    /// # See also
+    ///
+    /// * [`World::try_query_from_expr()`]
+    pub fn validate_query_expr(&self, expr: &str) -> Result<(), QueryParseError> {
+        query_expr::query_from_expr(self, expr).map(drop)
+    }
+
     /// Create and iterate an uncached query.
     ///
     /// This function creates a query and immediately iterates it.
@@ -0,0 +1,506 @@
+//! Whole-world snapshots that can be used to roll a world back to an earlier state.
+
+use core::ffi::c_void;
+
+use alloc::alloc::{alloc, dealloc, handle_alloc_error};
+use core::alloc::Layout;
+
+use super::*;
+
+/// An owned copy of the entities and component values of a [`World`].
+///
+/// A snapshot stores every user entity with its exact id (including its
+/// generation), its full type (components, tags, pairs, names and hierarchy)
+/// and a copy of every component value. Component values are copied with the
+/// lifecycle hooks of the component, so Rust components are cloned with their
+/// [`Clone`] implementation.
+///
+/// Builtin entities, modules, components, queries, observers and systems are
+/// not part of the snapshot and are left untouched when it is restored, as are
+/// Rust components that don't implement [`Clone`].
+///
+/// The snapshot holds a reference to its world, which keeps the world alive
+/// until the snapshot is dropped, as the copied values may need the world to
+/// be destructed.
+///
+/// Created with [`World::snapshot()`] and applied with [`World::restore()`].
+pub struct WorldSnapshot {
+    // declared before `world` so that the values are dropped while the world is alive
+    tables: Vec<SnapshotTable>,
+    world: World,
+}
+
+/// Entities that shared a table when the snapshot was taken.
+struct SnapshotTable {
+    /// Sorted type of the table without components that can't be cloned, empty for entities
+    /// without components.
+    ids: Vec<sys::ecs_id_t>,
+    entities: Vec<sys::ecs_entity_t>,
+    columns: Vec<SnapshotColumn>,
+}
+
+/// Copied component values for all entities in a [`SnapshotTable`].
+struct SnapshotColumn {
+    id: sys::ecs_id_t,
+    type_info: sys::ecs_type_info_t,
+    data: *mut u8,
+    capacity: i32,
+    /// Number of constructed values.
+    count: i32,
+}
+
+impl WorldSnapshot {
+    /// Returns the number of entities stored in the snapshot.
+    pub fn entity_count(&self) -> usize {
+        self.tables.iter().map(|table| table.entities.len()).sum()
+    }
+
+    /// Returns whether the snapshot contains the entity with this exact id.
+    pub fn contains(&self, entity: impl Into<Entity>) -> bool {
+        let entity = *entity.into();
+        self.tables
+            .iter()
+            .any(|table| table.entities.contains(&entity))
+    }
+}
+
+impl core::fmt::Debug for WorldSnapshot {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("WorldSnapshot")
+            .field("tables", &self.tables.len())
+            .field("entities", &self.entity_count())
+            .finish()
+    }
+}
+
+impl SnapshotColumn {
+    fn layout(type_info: &sys::ecs_type_info_t, capacity: i32) -> Layout {
+        Layout::from_size_align(
+            type_info.size as usize * capacity.max(1) as usize,
+            type_info.alignment as usize,
+        )
+        .expect("invalid component layout")
+    }
+
+    /// Allocates storage for `capacity` values, none of which are constructed yet.
+    fn with_capacity(id: sys::ecs_id_t, type_info: sys::ecs_type_info_t, capacity: i32) -> Self {
+        let layout = Self::layout(&type_info, capacity);
+        let data = unsafe { alloc(layout) };
+        if data.is_null() {
+            handle_alloc_error(layout);
+        }
+
+        Self {
+            id,
+            type_info,
+            data,
+            capacity,
+            count: 0,
+        }
+    }
+
+    /// Copy constructs `count` values at `row` from `src`.
+    unsafe fn copy_from(&self, row: i32, src: *const c_void, count: i32) {
+        let ti = &self.type_info;
+        let dst = unsafe { self.ptr(row) };
+        let hooks = &ti.hooks;
+        unsafe {
+            if let Some(copy_ctor) = hooks.copy_ctor {
+                copy_ctor(dst, src, count, ti);
+            } else if let Some(copy) = hooks.copy {
+                if let Some(ctor) = hooks.ctor {
+                    ctor(dst, count, ti);
+                }
+                copy(dst, src, count, ti);
+            } else {
+                core::ptr::copy_nonoverlapping(
+                    src as *const u8,
+                    dst as *mut u8,
+                    ti.size as usize * count as usize,
+                );
+            }
+        }
+    }
+
+    unsafe fn ptr(&self, row: i32) -> *mut c_void {
+        unsafe {
+            self.data
+                .add(self.type_info.size as usize * row as usize)
+                .cast()
+        }
+    }
+}
+
+impl Drop for SnapshotColumn {
+    fn drop(&mut self) {
+        unsafe {
+            if let Some(dtor) = self.type_info.hooks.dtor
+                && self.count > 0
+            {
+                dtor(self.data.cast(), self.count, &self.type_info);
+            }
+            dealloc(self.data, Self::layout(&self.type_info, self.capacity));
+        }
+    }
+}
+
+/// Returns whether the entities in a table are part of a snapshot.
+fn is_snapshot_table(table: *mut sys::ecs_table_t) -> bool {
+    [
+        TableFlags::HasBuiltins,
+        TableFlags::HasModule,
+        TableFlags::NotQueryable,
+    ]
+    .iter()
+    .all(|flag| !unsafe { sys::ecs_table_has_flags(table, flag.bits()) })
+}
+
+/// Returns whether an entity is part of a snapshot. Entities are excluded when
+/// they are builtin or when they are (nested) children of an excluded entity,
+/// such as the members of a component.
+fn is_snapshot_entity(world: *const sys::ecs_world_t, entity: sys::ecs_entity_t) -> bool {
+    let table = unsafe { sys::ecs_get_table(world, entity) };
+    if table.is_null() {
+        return entity >= sys::EcsFirstUserEntityId as u64;
+    }
+
+    if !is_snapshot_table(table) {
+        return false;
+    }
+
+    if unsafe { sys::ecs_table_has_flags(table, TableFlags::HasChildOf.bits()) } {
+        let parent = unsafe { sys::ecs_get_target(world, entity, ECS_CHILD_OF, 0) };
+        return parent == 0 || is_snapshot_entity(world, parent);
+    }
+
+    true
+}
+
+/// Returns the ids of all alive entities in the world.
+fn alive_entities(world: *const sys::ecs_world_t) -> &'static [sys::ecs_entity_t] {
+    let entities = unsafe { sys::ecs_get_entities(world) };
+    if entities.alive_count == 0 {
+        return &[];
+    }
+    unsafe { core::slice::from_raw_parts(entities.ids, entities.alive_count as usize) }
+}
+
+/// Returns whether `id` is a Rust component that doesn't implement [`Clone`]. Its values
+/// can't be copied, so it is left out of snapshots.
+fn is_not_clone(world: *const sys::ecs_world_t, id: sys::ecs_id_t) -> bool {
+    let ti = unsafe { sys::ecs_get_type_info(world, id) };
+    !ti.is_null() && lifecycle_traits::is_panic_copy_hook(unsafe { (*ti).hooks.copy })
+}
+
+fn type_ids(table: *mut sys::ecs_table_t) -> &'static [sys::ecs_id_t] {
+    if table.is_null() {
+        return &[];
+    }
+    let type_ = unsafe { &*sys::ecs_table_get_type(table) };
+    if type_.count == 0 {
+        return &[];
+    }
+    unsafe { core::slice::from_raw_parts(type_.array, type_.count as usize) }
+}
+
+impl World {
+    /// Take a snapshot of all entities and component values in the world.
+    ///
+    /// The snapshot captures every user entity with its exact id, all of its
+    /// components, tags and pairs (which includes names and hierarchy), and a
+    /// copy of every component value. Values are copied with the copy hooks of
+    /// the component, which for Rust components means they are cloned.
+    ///
+    /// Builtin entities, modules, components, queries, observers and systems are
+    /// not captured. Components with the `DontFragment` trait are not stored in
+    /// tables and are not captured either. Rust components that don't implement
+    /// [`Clone`] are not captured, and are left as they are when the snapshot is
+    /// restored. Other components that do not allow copying (such as internal
+    /// script and meta components) are captured as ids without a value.
+    ///
+    /// # Returns
+    ///
+    /// An owned [`WorldSnapshot`] that can be passed to [`World::restore()`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use flecs_ecs::prelude::*;
+    ///
+    /// #[derive(Component, Clone, Debug, PartialEq)]
+    /// struct Position {
+    ///     x: f32,
+    ///     y: f32,
+    /// }
+    ///
+    /// let world = World::new();
+    ///
+    /// let e = world.entity_named("player").set(Position { x: 1.0, y: 2.0 });
+    /// let snapshot = world.snapshot();
+    ///
+    /// e.set(Position { x: 5.0, y: 5.0 });
+    /// let bullet = world.entity().id();
+    ///
+    /// world.restore(&snapshot);
+    ///
+    /// assert!(!world.is_alive(bullet));
+    /// e.get::<&Position>(|pos| {
+    ///     assert_eq!(*pos, Position { x: 1.0, y: 2.0 });
+    /// });
+    /// ```
+    ///
+    /// # See also
+    ///
+    /// * [`World::restore()`]
+    pub fn snapshot(&self) -> WorldSnapshot {
+        let world = self.raw_world.as_ptr();
+        let mut tables = Vec::new();
+        let mut visited: hashbrown::HashSet<*mut sys::ecs_table_t> = Default::default();
+        let mut empty = Vec::new();
+
+        for &entity in alive_entities(world) {
+            let table = unsafe { sys::ecs_get_table(world, entity) };
+            if !table.is_null() && !visited.insert(table) {
+                continue;
+            }
+
+            if !is_snapshot_entity(world, entity) {
+                continue;
+            }
+
+            if table.is_null() {
+                empty.push(entity);
+                continue;
+            }
+
+            tables.push(unsafe { self.snapshot_table(table) });
+        }
+
+        if !empty.is_empty() {
+            tables.push(SnapshotTable {
+                ids: Vec::new(),
+                entities: empty,
+                columns: Vec::new(),
+            });
+        }
+
+        WorldSnapshot {
+            tables,
+            world: self.clone(),
+        }
+    }
+
+    unsafe fn snapshot_table(&self, table: *mut sys::ecs_table_t) -> SnapshotTable {
+        let world = self.raw_world.as_ptr();
+        let count = unsafe { sys::ecs_table_count(table) };
+        let entities =
+            unsafe { core::slice::from_raw_parts(sys::ecs_table_entities(table), count as usize) };
+        let ids = type_ids(table);
+
+        let mut columns = Vec::new();
+        for (index, &id) in ids.iter().enumerate() {
+            if is_not_clone(world, id) {
+                continue;
+            }
+
+            let ti = unsafe { sys::ecs_get_type_info(world, id) };
+            if ti.is_null() {
+                continue;
+            }
+
+            let ti = unsafe { *ti };
+            if ti.size == 0 || ti.hooks.flags & ECS_TYPE_HOOK_COPY_ILLEGAL != 0 {
+                continue;
+            }
+
+            let mut column = SnapshotColumn::with_capacity(id, ti, count);
+            let column_index = unsafe { sys::ecs_table_type_to_column_index(table, index as i32) };
+            if column_index >= 0 {
+                unsafe {
+                    let src = sys::ecs_table_get_column(table, column_index, 0);
+                    column.copy_from(0, src, count);
+                }
+                column.count = count;
+            } else {
+                // Sparse components are not stored in table columns
+                for (row, &entity) in entities.iter().enumerate() {
+                    let src = unsafe { sys::ecs_get_id(world, entity, id) };
+                    ecs_assert!(
+                        !src.is_null(),
+                        FlecsErrorCode::InternalError,
+                        "sparse component missing from entity"
+                    );
+                    unsafe { column.copy_from(row as i32, src, 1) };
+                    column.count = row as i32 + 1;
+                }
+            }
+
+            if unsafe { sys::ecs_id_match(id, ecs_pair(ECS_IDENTIFIER, ECS_WILDCARD)) } {
+                // Copied identifiers still point to the name index entry of the
+                // original, which would prevent restore from reinserting them.
+                for row in 0..column.count {
+                    let identifier = unsafe { &mut *column.ptr(row).cast::<sys::EcsIdentifier>() };
+                    identifier.index = core::ptr::null_mut();
+                    identifier.index_hash = 0;
+                }
+            }
+
+            columns.push(column);
+        }
+
+        SnapshotTable {
+            ids: ids
+                .iter()
+                .copied()
+                .filter(|&id| !is_not_clone(world, id))
+                .collect(),
+            entities: entities.to_vec(),
+            columns,
+        }
+    }
+
+    /// Restore the world to the state captured by a snapshot.
+    ///
+    /// After this operation the world contains exactly the entities from the
+    /// snapshot, with the same ids, types and component values:
+    ///
+    /// * entities created after the snapshot was taken are deleted,
+    /// * entities deleted after the snapshot was taken are recreated with their original id,
+    /// * components, tags and pairs added or removed since are reverted,
+    /// * all component values are assigned from the snapshot.
+    ///
+    /// Values are assigned with the regular set operation, which means that
+    /// `on_set` hooks and `OnSet` observers are invoked for restored components.
+    /// Entities and values not captured by [`World::snapshot()`] are not modified.
+    ///
+    /// # Arguments
+    ///
+    /// * `snapshot` - The snapshot to restore. It can be restored multiple times.
+    ///
+    /// # Panics
+    ///
+    /// Panics when the snapshot was taken from a different world, or when the world is deferred.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use flecs_ecs::prelude::*;
+    ///
+    /// #[derive(Component, Clone)]
+    /// struct Health(u32);
+    ///
+    /// let world = World::new();
+    ///
+    /// let parent = world.entity_named("parent");
+    /// let child = world.entity_named("child").child_of(parent).set(Health(10));
+    /// let snapshot = world.snapshot();
+    ///
+    /// child.destruct();
+    /// assert!(world.try_lookup("parent::child").is_none());
+    ///
+    /// world.restore(&snapshot);
+    ///
+    /// let restored = world.lookup("parent::child");
+    /// assert_eq!(restored, child);
+    /// assert!(restored.has(Health::id()));
+    /// ```
+    ///
+    /// # See also
+    ///
+    /// * [`World::snapshot()`]
+    pub fn restore(&self, snapshot: &WorldSnapshot) {
+        let world = self.raw_world.as_ptr();
+        ecs_assert!(
+            core::ptr::eq(snapshot.world.raw_world.as_ptr(), world),
+            FlecsErrorCode::InvalidParameter,
+            "snapshot was taken from a different world"
+        );
+        ecs_assert!(
+            !self.is_deferred(),
+            FlecsErrorCode::InvalidOperation,
+            "cannot restore a snapshot while the world is deferred"
+        );
+
+        let mut in_snapshot: hashbrown::HashSet<sys::ecs_entity_t> = Default::default();
+        for table in &snapshot.tables {
+            in_snapshot.extend(table.entities.iter().copied());
+        }
+
+        // Delete entities that did not exist when the snapshot was taken
+        let created: Vec<sys::ecs_entity_t> = alive_entities(world)
+            .iter()
+            .copied()
+            .filter(|entity| !in_snapshot.contains(entity))
+            .filter(|&entity| is_snapshot_entity(world, entity))
+            .collect();
+
+        for entity in created {
+            unsafe {
+                if sys::ecs_is_alive(world, entity) {
+                    sys::ecs_delete(world, entity);
+                }
+            }
+        }
+
+        // Recreate deleted entities with their original id & generation
+        for &entity in snapshot.tables.iter().flat_map(|table| &table.entities) {
+            unsafe {
+                if sys::ecs_is_alive(world, entity) {
+                    continue;
+                }
+                let current = sys::ecs_get_alive(world, entity);
+                if current != 0 {
+                    sys::ecs_delete(world, current);
+                }
+                sys::ecs_make_alive(world, entity);
+            }
+        }
+
+        // Remove ids that were added after the snapshot. Names are always removed
+        // and reassigned, so that renamed entities can't conflict in the name index.
+        // Components that can't be cloned weren't captured, so they are kept.
+        let identifier = ecs_pair(ECS_IDENTIFIER, ECS_WILDCARD);
+        for table in &snapshot.tables {
+            for &entity in &table.entities {
+                let current = type_ids(unsafe { sys::ecs_get_table(world, entity) }).to_vec();
+                for id in current {
+                    if is_not_clone(world, id) {
+                        continue;
+                    }
+                    if table.ids.binary_search(&id).is_err()
+                        || unsafe { sys::ecs_id_match(id, identifier) }
+                    {
+                        unsafe { sys::ecs_remove_id(world, entity, id) };
+                    }
+                }
+            }
+        }
+
+        // Add ids that were removed after the snapshot, then assign values
+        for table in &snapshot.tables {
+            for &entity in &table.entities {
+                for &id in &table.ids {
+                    unsafe {
+                        if !sys::ecs_owns_id(world, entity, id) {
+                            sys::ecs_add_id(world, entity, id);
+                        }
+                    }
+                }
+            }
+
+            for column in &table.columns {
+                for (row, &entity) in table.entities.iter().enumerate() {
+                    unsafe {
+                        sys::ecs_set_id(
+                            world,
+                            entity,
+                            column.id,
+                            column.type_info.size as usize,
+                            column.ptr(row as i32),
+                        );
+                    }
+                }
+            }
+        }
+    }
+}
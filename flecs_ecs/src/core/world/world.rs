@@ -140,6 +140,9 @@ impl World {
         // used for event handling with no data
         self.component_named::<()>("flecs::rust::() - None");
 
+        #[cfg(feature = "flecs_timer")]
+        crate::addons::timer::timer_callbacks_init(self);
+
         #[cfg(feature = "flecs_meta")]
         {
             self.component_named::<crate::prelude::meta::EcsTypeKind>("flecs::meta::type_kind");
@@ -12,6 +12,12 @@ pub(crate) struct WorldCtx {
     pub(crate) components: FlecsIdMap,
     pub(crate) components_array: FlecsArray,
     is_panicking: bool,
+    #[cfg(feature = "flecs_pipeline")]
+    pub(crate) fixed_timestep: Option<crate::addons::pipeline::FixedTimestep>,
+    #[cfg(feature = "flecs_pipeline")]
+    pub(crate) system_order: crate::addons::pipeline::ordering::SystemOrder,
+    #[cfg(feature = "flecs_timer")]
+    pub(crate) timer_callbacks: Option<crate::addons::timer::TimerCallbacks>,
 }
 
 impl WorldCtx {
@@ -21,6 +27,12 @@ impl WorldCtx {
             components: Default::default(),
             components_array: vec![0; 500],
             is_panicking: false,
+            #[cfg(feature = "flecs_pipeline")]
+            fixed_timestep: None,
+            #[cfg(feature = "flecs_pipeline")]
+            system_order: Default::default(),
+            #[cfg(feature = "flecs_timer")]
+            timer_callbacks: None,
         }
     }
 
@@ -0,0 +1,130 @@
+#![cfg(feature = "flecs_alerts")]
+
+extern crate alloc;
+
+use alloc::rc::Rc;
+use core::cell::RefCell;
+
+use crate::common_test::*;
+use flecs_ecs::addons::metrics::MetricsModule;
+
+type Log = Rc<RefCell<Vec<(Entity, Entity, String)>>>;
+
+#[test]
+fn alerts_on_raise_on_clear() {
+    let world = World::new();
+    // import metrics first, so its ids match the worlds of the metrics tests
+    world.import::<MetricsModule>();
+    world.import::<AlertsModule>();
+
+    let raised: Log = Default::default();
+    let cleared: Log = Default::default();
+    world
+        .alert::<&Position>()
+        .without(Velocity::id())
+        .severity(flecs::alerts::Warning::id())
+        .message("$this has no velocity")
+        .on_raise({
+            let raised = raised.clone();
+            move |_, source, severity, message| {
+                raised
+                    .borrow_mut()
+                    .push((source.id(), severity.id(), message.to_string()));
+            }
+        })
+        .on_clear({
+            let cleared = cleared.clone();
+            move |_, source, severity, message| {
+                cleared
+                    .borrow_mut()
+                    .push((source.id(), severity.id(), message.to_string()));
+            }
+        })
+        .build();
+
+    let e = world.entity_named("e1").set(Position { x: 1, y: 2 });
+    world
+        .entity_named("e2")
+        .set(Position { x: 1, y: 2 })
+        .set(Velocity { x: 1, y: 1 });
+
+    world.progress_time(1.0);
+    world.progress_time(1.0);
+
+    let warning = Entity::from(flecs::alerts::Warning);
+    let expected = (e.id(), warning, "e1 has no velocity".to_string());
+    assert_eq!(raised.borrow().as_slice(), core::slice::from_ref(&expected));
+    assert!(cleared.borrow().is_empty());
+
+    e.set(Velocity { x: 1, y: 1 });
+    world.progress_time(1.0);
+    world.progress_time(1.0);
+
+    assert_eq!(raised.borrow().len(), 1);
+    assert_eq!(*cleared.borrow(), [expected]);
+}
+
+#[test]
+fn alerts_active_alerts() {
+    let world = World::new();
+    // import metrics first, so its ids match the worlds of the metrics tests
+    world.import::<MetricsModule>();
+    world.import::<AlertsModule>();
+
+    let alert = world
+        .alert::<&Position>()
+        .without(Velocity::id())
+        .message("$this has no velocity")
+        .build();
+
+    assert_eq!(world.active_alerts().count(), 0);
+
+    let e = world.entity_named("e1").set(Position { x: 1, y: 2 });
+    world.progress_time(1.0);
+    world.progress_time(1.0);
+
+    let active: Vec<_> = world.active_alerts().collect();
+    assert_eq!(active.len(), 1);
+    assert_eq!(active[0].alert, alert.id());
+    assert_eq!(active[0].source, e.id());
+    assert_eq!(active[0].severity, Entity::from(flecs::alerts::Error));
+    assert_eq!(active[0].message, "e1 has no velocity");
+    assert!(active[0].duration > 0.0);
+    assert_eq!(e.alert_count(0), 1);
+
+    e.set(Velocity { x: 1, y: 1 });
+    world.progress_time(1.0);
+    world.progress_time(1.0);
+    assert_eq!(world.active_alerts().count(), 0);
+}
+
+#[test]
+fn alerts_on_raise_empty_message() {
+    let world = World::new();
+    // import metrics first, so its ids match the worlds of the metrics tests
+    world.import::<MetricsModule>();
+    world.import::<AlertsModule>();
+
+    let raised: Log = Default::default();
+    world
+        .alert::<&Position>()
+        .without(Velocity::id())
+        .message("")
+        .on_raise({
+            let raised = raised.clone();
+            move |_, source, severity, message| {
+                raised
+                    .borrow_mut()
+                    .push((source.id(), severity.id(), message.to_string()));
+            }
+        })
+        .build();
+
+    let e = world.entity().set(Position { x: 1, y: 2 });
+    world.progress_time(1.0);
+    world.progress_time(1.0);
+    world.progress_time(1.0);
+
+    let error = Entity::from(flecs::alerts::Error);
+    assert_eq!(*raised.borrow(), [(e.id(), error, String::new())]);
+}
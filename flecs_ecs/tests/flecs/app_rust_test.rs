@@ -0,0 +1,168 @@
+#![cfg(feature = "flecs_app")]
+#![allow(clippy::float_cmp)]
+
+extern crate alloc;
+use alloc::{rc::Rc, vec, vec::Vec};
+use core::cell::{Cell, RefCell};
+
+use flecs_ecs::prelude::*;
+
+#[derive(Component, Default)]
+struct Ticks(u32);
+
+#[test]
+fn app_hooks_run_in_order() {
+    let world = World::new();
+    let counter = world.entity().set(Ticks(0)).id();
+    world.system::<&mut Ticks>().each(|ticks| ticks.0 += 1);
+
+    let events = Rc::new(RefCell::new(Vec::new()));
+    let (init, begin, end) = (events.clone(), events.clone(), events.clone());
+    let result = world
+        .app()
+        .set_delta_time(0.5)
+        .set_frames(2)
+        .on_init(move |world| {
+            assert_eq!(
+                world.entity_from_id(counter).get::<&Ticks>(|ticks| ticks.0),
+                0
+            );
+            init.borrow_mut().push("init");
+        })
+        .on_frame_begin(move |_, dt| {
+            assert_eq!(dt, 0.5);
+            begin.borrow_mut().push("begin");
+        })
+        .on_frame_end(move |world, dt| {
+            assert_eq!(dt, 0.5);
+            assert!(world.entity_from_id(counter).get::<&Ticks>(|ticks| ticks.0) > 0);
+            end.borrow_mut().push("end");
+        })
+        .run();
+
+    assert_eq!(result, 0);
+    assert_eq!(
+        *events.borrow(),
+        vec!["init", "begin", "end", "begin", "end"]
+    );
+    assert_eq!(
+        world.entity_from_id(counter).get::<&Ticks>(|ticks| ticks.0),
+        2
+    );
+}
+
+#[test]
+fn app_until() {
+    let world = World::new();
+    let counter = world.entity().set(Ticks(0)).id();
+    world.system::<&mut Ticks>().each(|ticks| ticks.0 += 1);
+
+    world
+        .app()
+        .set_delta_time(1.0 / 60.0)
+        .until(|world| world.entity_from_id(counter).get::<&Ticks>(|ticks| ticks.0) == 5)
+        .run();
+
+    assert_eq!(
+        world.entity_from_id(counter).get::<&Ticks>(|ticks| ticks.0),
+        5
+    );
+}
+
+#[test]
+fn app_quit_stops_loop() {
+    let world = World::new();
+    let counter = world.entity().set(Ticks(0)).id();
+    world.system::<&mut Ticks>().each_iter(|it, _, ticks| {
+        ticks.0 += 1;
+        if ticks.0 == 3 {
+            it.world().quit();
+        }
+    });
+
+    let frames = Rc::new(Cell::new(0));
+    let count = frames.clone();
+    world
+        .app()
+        .set_delta_time(1.0 / 60.0)
+        .on_frame_end(move |_, _| count.set(count.get() + 1))
+        .run();
+
+    assert_eq!(frames.get(), 3);
+    assert_eq!(
+        world.entity_from_id(counter).get::<&Ticks>(|ticks| ticks.0),
+        3
+    );
+}
+
+#[test]
+fn app_closure_driver() {
+    let world = World::new();
+    let counter = world.entity().set(Ticks(0)).id();
+    world.system::<&mut Ticks>().each(|ticks| ticks.0 += 1);
+
+    let delta_times = Rc::new(RefCell::new(Vec::new()));
+    let recorded = delta_times.clone();
+    let result = world
+        .app()
+        .on_frame_end(move |_, dt| recorded.borrow_mut().push(dt))
+        .driver(|frames: &mut AppFrames| {
+            frames.frame_time(0.25);
+            frames.frame_time(0.5);
+            frames.frame_count()
+        })
+        .run();
+
+    assert_eq!(result, 2);
+    assert_eq!(*delta_times.borrow(), vec![0.25, 0.5]);
+    assert_eq!(
+        world.entity_from_id(counter).get::<&Ticks>(|ticks| ticks.0),
+        2
+    );
+}
+
+#[test]
+fn app_driver_stops_after_frames() {
+    struct Headless {
+        frames: usize,
+    }
+
+    impl FrameDriver for Headless {
+        fn run(&mut self, frames: &mut AppFrames) -> i32 {
+            for _ in 0..self.frames {
+                if !frames.frame_time(1.0 / 60.0) {
+                    break;
+                }
+            }
+            frames.frame_count()
+        }
+    }
+
+    let world = World::new();
+    let result = world
+        .app()
+        .set_frames(3)
+        .driver(Headless { frames: 10 })
+        .run();
+
+    assert_eq!(result, 3);
+}
+
+#[test]
+fn app_fixed_timestep() {
+    let world = World::new();
+    world.set_fixed_timestep(0.25, 0);
+    let counter = world.entity().set(Ticks(0)).id();
+    world
+        .system::<&mut Ticks>()
+        .kind(FixedUpdate)
+        .each(|ticks| ticks.0 += 1);
+
+    let result = world.app().set_delta_time(0.5).set_frames(3).run();
+
+    assert_eq!(result, 0);
+    assert_eq!(
+        world.entity_from_id(counter).get::<&Ticks>(|ticks| ticks.0),
+        6
+    );
+}
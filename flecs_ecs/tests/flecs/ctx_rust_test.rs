@@ -0,0 +1,263 @@
+#![allow(dead_code)]
+
+extern crate alloc;
+
+use alloc::sync::Arc;
+use core::ffi::c_void;
+use core::sync::atomic::{AtomicU32, Ordering};
+
+use flecs_ecs::core::*;
+
+use crate::common_test::*;
+
+struct Counter {
+    count: u32,
+}
+
+#[test]
+fn ctx_system() {
+    let world = World::new();
+
+    let sys = world
+        .system::<&Position>()
+        .ctx(Counter { count: 0 })
+        .each_iter(|mut it, _, _| {
+            it.ctx_mut::<Counter>().count += 1;
+        });
+
+    world.entity().set(Position { x: 10, y: 20 });
+    world.entity().set(Position { x: 30, y: 40 });
+
+    sys.run();
+    sys.run();
+
+    sys.query().run(|mut it| {
+        while it.next() {
+            assert_eq!(it.ctx::<Counter>().count, 4);
+        }
+    });
+}
+
+#[test]
+fn ctx_system_dropped_with_entity() {
+    let world = World::new();
+    let value = Arc::new(());
+
+    let sys = world.system::<&Position>().ctx(value.clone()).each(|_| {});
+    assert_eq!(Arc::strong_count(&value), 2);
+
+    sys.destruct();
+    assert_eq!(Arc::strong_count(&value), 1);
+}
+
+#[test]
+fn ctx_system_replaced() {
+    let world = World::new();
+    let first = Arc::new(());
+    let second = Arc::new(());
+
+    world
+        .system::<&Position>()
+        .ctx(first.clone())
+        .ctx(second.clone())
+        .each(|_| {});
+
+    assert_eq!(Arc::strong_count(&first), 1);
+    assert_eq!(Arc::strong_count(&second), 2);
+}
+
+#[test]
+fn ctx_system_set_context_after_ctx() {
+    let world = World::new();
+    let value = Arc::new(());
+    let mut raw = 10;
+
+    let mut sys = world.system::<&Position>().ctx(value.clone()).each(|_| {});
+    sys.set_context(&mut raw as *mut i32 as *mut c_void);
+    assert_eq!(Arc::strong_count(&value), 1);
+
+    world.entity().set(Position { x: 10, y: 20 });
+    assert_eq!(sys.context(), &mut raw as *mut i32 as *mut c_void);
+    sys.query().run(|mut it| {
+        while it.next() {
+            assert!(it.try_ctx::<Arc<()>>().is_none());
+        }
+    });
+
+    sys.destruct();
+}
+
+#[test]
+#[should_panic(expected = "not `u32`")]
+fn ctx_system_wrong_type() {
+    let world = World::new();
+
+    let sys = world
+        .system::<&Position>()
+        .ctx(Counter { count: 0 })
+        .run(|it| {
+            it.ctx::<u32>();
+        });
+
+    sys.run();
+}
+
+#[test]
+#[should_panic(expected = "no typed context")]
+fn ctx_system_no_ctx() {
+    let world = World::new();
+
+    let sys = world.system::<&Position>().run(|it| {
+        it.ctx::<u32>();
+    });
+
+    sys.run();
+}
+
+#[test]
+fn ctx_observer() {
+    let world = World::new();
+    let count = Arc::new(AtomicU32::new(0));
+
+    world
+        .observer::<flecs::OnSet, &Position>()
+        .ctx(count.clone())
+        .each_iter(|it, _, _| {
+            it.ctx::<Arc<AtomicU32>>().fetch_add(1, Ordering::Relaxed);
+        });
+
+    world.entity().set(Position { x: 10, y: 20 });
+    world.entity().set(Position { x: 30, y: 40 });
+
+    assert_eq!(count.load(Ordering::Relaxed), 2);
+}
+
+#[test]
+fn ctx_observer_multi_term() {
+    let world = World::new();
+    let count = Arc::new(AtomicU32::new(0));
+
+    world
+        .observer::<flecs::OnSet, (&Position, &Velocity)>()
+        .ctx(count.clone())
+        .each_iter(|mut it, _, _| {
+            it.ctx_mut::<Arc<AtomicU32>>()
+                .fetch_add(1, Ordering::Relaxed);
+        });
+
+    world
+        .entity()
+        .set(Position { x: 10, y: 20 })
+        .set(Velocity { x: 1, y: 2 });
+
+    assert_eq!(count.load(Ordering::Relaxed), 1);
+}
+
+#[test]
+fn ctx_observer_dropped_with_entity() {
+    let world = World::new();
+    let value = Arc::new(());
+
+    let observer = world
+        .observer::<flecs::OnSet, &Position>()
+        .ctx(value.clone())
+        .each(|_| {});
+    assert_eq!(Arc::strong_count(&value), 2);
+
+    observer.destruct();
+    assert_eq!(Arc::strong_count(&value), 1);
+}
+
+#[test]
+fn ctx_query() {
+    let world = World::new();
+
+    let query = world.query::<&Position>().ctx(Counter { count: 5 }).build();
+
+    world.entity().set(Position { x: 10, y: 20 });
+
+    query.run(|mut it| {
+        while it.next() {
+            assert_eq!(it.ctx::<Counter>().count, 5);
+            it.ctx_mut::<Counter>().count += 1;
+        }
+    });
+
+    query.run(|mut it| {
+        while it.next() {
+            assert_eq!(it.ctx::<Counter>().count, 6);
+        }
+    });
+}
+
+#[test]
+fn ctx_query_dropped_with_query() {
+    let world = World::new();
+    let value = Arc::new(());
+
+    let query = world
+        .query::<&Position>()
+        .set_cached()
+        .ctx(value.clone())
+        .build();
+    assert_eq!(Arc::strong_count(&value), 2);
+
+    query.destruct();
+    assert_eq!(Arc::strong_count(&value), 1);
+}
+
+#[test]
+fn ctx_query_none() {
+    let world = World::new();
+
+    let query = world.new_query::<&Position>();
+    world.entity().set(Position { x: 10, y: 20 });
+
+    query.run(|mut it| {
+        while it.next() {
+            assert!(it.try_ctx::<Counter>().is_none());
+        }
+    });
+}
+
+#[test]
+fn ctx_nested_shared_borrows() {
+    let world = World::new();
+
+    let query = world.query::<&Position>().ctx(Counter { count: 1 }).build();
+    world.entity().set(Position { x: 10, y: 20 });
+
+    let mut count = 0;
+    query.run(|mut it| {
+        while it.next() {
+            let outer = it.ctx::<Counter>();
+            query.run(|mut it| {
+                while it.next() {
+                    count += it.ctx::<Counter>().count + outer.count;
+                }
+            });
+        }
+    });
+    assert_eq!(count, 2);
+}
+
+#[test]
+#[should_panic(expected = "already mutably borrowed")]
+fn ctx_nested_mut_borrow() {
+    let world = World::new();
+
+    let query = world.query::<&Position>().ctx(Counter { count: 0 }).build();
+    world.entity().set(Position { x: 10, y: 20 });
+
+    query.run(|mut it| {
+        while it.next() {
+            let mut outer = it.ctx_mut::<Counter>();
+            query.run(|mut it| {
+                while it.next() {
+                    it.ctx::<Counter>();
+                }
+            });
+            outer.count += 1;
+        }
+    });
+}
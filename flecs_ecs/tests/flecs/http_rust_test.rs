@@ -0,0 +1,254 @@
+#![cfg(feature = "flecs_http")]
+
+extern crate alloc;
+
+use alloc::sync::Arc;
+use core::fmt::Write;
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+use flecs_ecs::addons::http::*;
+use flecs_ecs::prelude::*;
+
+#[test]
+fn http_server_get_route() {
+    let world = World::new();
+
+    let server = world
+        .http_server()
+        .get("/hello", |_, reply| {
+            reply.content_type(c"text/plain").body("hello world");
+        })
+        .build();
+
+    let response = server.request(HttpMethod::Get, "/hello", None);
+    assert_eq!(response.code(), 200);
+    assert_eq!(response.status(), "OK");
+    assert_eq!(response.content_type(), Some("text/plain"));
+    assert_eq!(response.body(), "hello world");
+}
+
+#[test]
+fn http_server_params_and_headers() {
+    let world = World::new();
+
+    let server = world
+        .http_server()
+        .get("debug/entities", |req, reply| {
+            let limit = req.param_as::<u32>("limit").unwrap_or(10);
+            let name = req.param("name").unwrap_or("none");
+            assert_eq!(req.path(), "debug/entities");
+            assert_eq!(req.param_as::<u32>("name"), None);
+            assert_eq!(req.params().count(), 2);
+            write!(reply, "{name}:{limit}").unwrap();
+            reply.header("X-Limit", &limit.to_string());
+        })
+        .build();
+
+    let response = server.request(
+        HttpMethod::Get,
+        "debug/entities?limit=5&name=foo%20bar",
+        None,
+    );
+    assert_eq!(response.body(), "foo bar:5");
+    assert_eq!(response.header("x-limit"), Some("5"));
+
+    let response = server.request(HttpMethod::Get, "debug/entities?limit=all&verbose=1", None);
+    assert_eq!(response.body(), "none:10");
+}
+
+#[test]
+fn http_server_post_body() {
+    let world = World::new();
+
+    let server = world
+        .http_server()
+        .post("/echo", |req, reply| {
+            assert_eq!(req.method(), HttpMethod::Post);
+            assert_eq!(req.header_as::<usize>("Content-Length"), Some(5));
+            reply.status(201).body(req.body().unwrap_or_default());
+        })
+        .build();
+
+    let response = server.request(HttpMethod::Post, "/echo", Some("hello"));
+    assert_eq!(response.code(), 201);
+    assert_eq!(response.status(), "Created");
+    assert_eq!(response.body(), "hello");
+}
+
+#[test]
+fn http_server_not_found() {
+    let world = World::new();
+
+    let server = world
+        .http_server()
+        .get("/hello", |_, reply| {
+            reply.body("hello");
+        })
+        .build();
+
+    let response = server.request(HttpMethod::Get, "/other", None);
+    assert_eq!(response.code(), 404);
+
+    let response = server.request(HttpMethod::Put, "/hello", None);
+    assert_eq!(response.code(), 405);
+}
+
+#[test]
+fn http_server_not_found_connection() {
+    use std::io::{Read, Write};
+
+    let world = World::new();
+
+    let server = world
+        .http_server()
+        .ipaddr("127.0.0.1")
+        .port(27798)
+        .post("/hello", |_, reply| {
+            reply.body("hello");
+        })
+        .build();
+    assert!(server.start());
+
+    let send = |request: &'static str| {
+        let client = std::thread::spawn(move || {
+            let mut stream = std::net::TcpStream::connect("127.0.0.1:27798").unwrap();
+            stream.write_all(request.as_bytes()).unwrap();
+            let mut response = String::new();
+            stream.read_to_string(&mut response).unwrap();
+            response
+        });
+        while !client.is_finished() {
+            world.progress();
+            std::thread::sleep(core::time::Duration::from_millis(1));
+        }
+        client.join().unwrap()
+    };
+
+    let response = send("GET /hello HTTP/1.1\r\nConnection: close\r\n\r\n");
+    assert!(response.starts_with("HTTP/1.1 405"), "{response}");
+
+    let response = send("GET /other HTTP/1.1\r\nConnection: close\r\n\r\n");
+    assert!(response.starts_with("HTTP/1.1 404"), "{response}");
+
+    server.stop();
+}
+
+#[test]
+fn http_server_prefix_route() {
+    let world = World::new();
+
+    let server = world
+        .http_server()
+        .get("/entity/*", |req, reply| {
+            reply.body(req.path().trim_start_matches("entity/"));
+        })
+        .build();
+
+    let response = server.request(HttpMethod::Get, "/entity/parent/child", None);
+    assert_eq!(response.body(), "parent/child");
+}
+
+#[test]
+fn http_server_handler_state() {
+    let world = World::new();
+    let count = Arc::new(AtomicUsize::new(0));
+
+    let server = world
+        .http_server()
+        .route(HttpMethod::Delete, "/count", {
+            let count = count.clone();
+            move |_, _| {
+                count.fetch_add(1, Ordering::SeqCst);
+            }
+        })
+        .build();
+
+    server.request(HttpMethod::Delete, "/count", None);
+    server.request(HttpMethod::Delete, "/count", None);
+    assert_eq!(count.load(Ordering::SeqCst), 2);
+}
+
+#[test]
+fn http_server_header_injection() {
+    let world = World::new();
+
+    let server = world
+        .http_server()
+        .get("/headers", |_, reply| {
+            reply
+                .header("X-Value", "a\r\nSet-Cookie: b")
+                .header("X-Name: c\r\nX-Other", "d")
+                .header("X-Valid", "e");
+        })
+        .build();
+
+    let response = server.request(HttpMethod::Get, "/headers", None);
+    assert_eq!(response.header("x-value"), None);
+    assert_eq!(response.header("set-cookie"), None);
+    assert_eq!(response.header("x-other"), None);
+    assert_eq!(response.header("x-valid"), Some("e"));
+}
+
+#[test]
+fn http_server_request_from_handler() {
+    // handlers are 'static, so the server they send a request to must outlive them
+    let world: &'static World = Box::leak(Box::new(World::new()));
+    let server = alloc::rc::Rc::new(core::cell::OnceCell::<HttpServer<'static>>::new());
+
+    let inner = world
+        .http_server()
+        .get("/outer", {
+            let server = server.clone();
+            move |_, reply| {
+                let response = server
+                    .get()
+                    .unwrap()
+                    .request(HttpMethod::Get, "/outer", None);
+                assert_eq!(response.code(), 500);
+                reply.body("outer");
+            }
+        })
+        .build();
+    let _ = server.set(inner);
+
+    let response = server
+        .get()
+        .unwrap()
+        .request(HttpMethod::Get, "/outer", None);
+    assert_eq!(response.code(), 200);
+    assert_eq!(response.body(), "outer");
+}
+
+#[test]
+fn http_server_deleted_with_system() {
+    let world = World::new();
+    let dropped = Arc::new(AtomicUsize::new(0));
+
+    struct DropCounter(Arc<AtomicUsize>);
+    impl Drop for DropCounter {
+        fn drop(&mut self) {
+            self.0.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    let counter = DropCounter(dropped.clone());
+    let server = world
+        .http_server()
+        .ipaddr("127.0.0.1")
+        .port(27799)
+        .get("/hello", move |_, _| {
+            let _ = &counter;
+        })
+        .build();
+
+    assert!(server.entity().has(flecs::pipeline::OnLoad));
+    world.progress();
+    assert!(server.start());
+    assert!(server.is_running());
+    assert!(!server.start());
+    world.progress();
+    assert_eq!(dropped.load(Ordering::SeqCst), 0);
+
+    server.entity().destruct();
+    assert_eq!(dropped.load(Ordering::SeqCst), 1);
+}
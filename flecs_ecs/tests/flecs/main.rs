@@ -5,9 +5,12 @@
 
 pub mod common_test;
 
+mod alerts_rust_test;
+mod app_rust_test;
 mod clone_default_impl_test;
 mod component_lifecycle_test;
 mod component_test;
+mod ctx_rust_test;
 mod derive_attr_component_traits;
 mod entity_bulk_rust_test;
 mod entity_rust_test;
@@ -15,19 +18,29 @@ mod entity_test;
 mod enum_test;
 mod eq_test;
 mod flecs_ids;
+mod http_rust_test;
 //mod flecs_docs_test;
 mod is_ref_test;
 mod meta_macro_test;
+mod meta_serde_test;
 mod meta_test;
 mod meta_test_rust;
 mod meta_trait_test;
+mod metrics_rust_test;
 mod module_test;
 mod observer_rust_test;
 mod observer_test;
+mod pipeline_rust_test;
 mod query_builder_test;
 mod query_rust_test;
 mod query_test;
 #[cfg(feature = "flecs_safety_locks")]
 mod safety;
+mod script_rust_test;
+mod snapshot_rust_test;
+mod stats_rust_test;
 mod system_test;
+mod table_rust_test;
+mod timer_rust_test;
+mod try_ops_rust_test;
 mod world_test;
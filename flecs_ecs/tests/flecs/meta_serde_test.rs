@@ -0,0 +1,372 @@
+#![cfg(feature = "serde")]
+
+use core::mem::offset_of;
+use flecs_ecs::prelude::*;
+use serde::de::DeserializeSeed;
+
+#[derive(Debug, Default, Clone, PartialEq, Component)]
+#[flecs(meta)]
+pub struct SerdePosition {
+    pub x: f32,
+    pub y: f32,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Component)]
+#[flecs(meta)]
+pub struct SerdePrimitives {
+    pub b: bool,
+    pub u8: u8,
+    pub u16: u16,
+    pub u32: u32,
+    pub u64: u64,
+    pub i8: i8,
+    pub i16: i16,
+    pub i32: i32,
+    pub i64: i64,
+    pub f64: f64,
+    pub usize: usize,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Component)]
+#[repr(C)]
+#[flecs(meta)]
+pub enum SerdeColor {
+    #[default]
+    Red,
+    Green,
+    Blue,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Component)]
+#[flecs(meta)]
+pub struct SerdeNested {
+    pub color: SerdeColor,
+    pub pos: SerdePosition,
+    pub name: String,
+    pub names: Vec<String>,
+    pub values: Vec<i32>,
+}
+
+fn nested() -> SerdeNested {
+    SerdeNested {
+        color: SerdeColor::Blue,
+        pos: SerdePosition { x: 1.5, y: -2.0 },
+        name: "hello".to_string(),
+        names: vec!["a".to_string(), "b".to_string()],
+        values: vec![1, 2, 3],
+    }
+}
+
+#[test]
+fn serde_struct_to_json() {
+    let world = World::new();
+    world.component::<SerdePosition>();
+
+    let pos = SerdePosition { x: 10.0, y: 20.5 };
+    let json = serde_json::to_string(&world.to_serde::<SerdePosition>(&pos)).unwrap();
+    assert_eq!(json, r#"{"x":10.0,"y":20.5}"#);
+}
+
+#[test]
+fn serde_struct_from_json() {
+    let world = World::new();
+    world.component::<SerdePosition>();
+
+    let mut pos = SerdePosition::default();
+    let mut de = serde_json::Deserializer::from_str(r#"{"y":3.0,"x":4.0}"#);
+    world
+        .from_serde::<SerdePosition>(&mut pos)
+        .deserialize(&mut de)
+        .unwrap();
+    assert_eq!(pos, SerdePosition { x: 4.0, y: 3.0 });
+}
+
+#[test]
+fn serde_missing_member_keeps_value() {
+    let world = World::new();
+    world.component::<SerdePosition>();
+
+    let mut pos = SerdePosition { x: 1.0, y: 2.0 };
+    let mut de = serde_json::Deserializer::from_str(r#"{"y":5.0}"#);
+    world
+        .from_serde::<SerdePosition>(&mut pos)
+        .deserialize(&mut de)
+        .unwrap();
+    assert_eq!(pos, SerdePosition { x: 1.0, y: 5.0 });
+}
+
+#[test]
+fn serde_unknown_member() {
+    let world = World::new();
+    world.component::<SerdePosition>();
+
+    let mut pos = SerdePosition::default();
+    let mut de = serde_json::Deserializer::from_str(r#"{"z":5.0}"#);
+    let err = world
+        .from_serde::<SerdePosition>(&mut pos)
+        .deserialize(&mut de)
+        .unwrap_err();
+    assert!(err.to_string().contains("unknown member 'z'"));
+}
+
+#[test]
+fn serde_primitives_round_trip() {
+    let world = World::new();
+    world.component::<SerdePrimitives>();
+
+    let value = SerdePrimitives {
+        b: true,
+        u8: 200,
+        u16: 60000,
+        u32: 4_000_000_000,
+        u64: u64::MAX,
+        i8: -100,
+        i16: -30000,
+        i32: -2_000_000_000,
+        i64: i64::MIN,
+        f64: 0.125,
+        usize: 12345,
+    };
+
+    let json = serde_json::to_string(&world.to_serde::<SerdePrimitives>(&value)).unwrap();
+    let mut result = SerdePrimitives::default();
+    let mut de = serde_json::Deserializer::from_str(&json);
+    world
+        .from_serde::<SerdePrimitives>(&mut result)
+        .deserialize(&mut de)
+        .unwrap();
+    assert_eq!(result, value);
+}
+
+#[test]
+fn serde_enum() {
+    let world = World::new();
+    world.component::<SerdeColor>();
+
+    let json = serde_json::to_string(&world.to_serde::<SerdeColor>(&SerdeColor::Green)).unwrap();
+    assert_eq!(json, r#""Green""#);
+
+    let mut color = SerdeColor::Red;
+    let mut de = serde_json::Deserializer::from_str(r#""Blue""#);
+    world
+        .from_serde::<SerdeColor>(&mut color)
+        .deserialize(&mut de)
+        .unwrap();
+    assert_eq!(color, SerdeColor::Blue);
+
+    let mut de = serde_json::Deserializer::from_str(r#""Purple""#);
+    assert!(
+        world
+            .from_serde::<SerdeColor>(&mut color)
+            .deserialize(&mut de)
+            .is_err()
+    );
+}
+
+#[test]
+fn serde_bitmask() {
+    #[derive(Debug, Default, PartialEq, Component)]
+    struct Toppings {
+        value: u32,
+    }
+
+    #[derive(Debug, Default, PartialEq, Component)]
+    struct Sandwich {
+        toppings: Toppings,
+    }
+
+    let world = World::new();
+    world
+        .component::<Toppings>()
+        .bit("bacon", 0x1u32)
+        .bit("lettuce", 0x2u32)
+        .bit("tomato", 0x4u32);
+    world.component::<Sandwich>().member(
+        Toppings::id(),
+        ("toppings", Count(0), offset_of!(Sandwich, toppings)),
+    );
+
+    let value = Sandwich {
+        toppings: Toppings { value: 0x1 | 0x4 },
+    };
+    let json = serde_json::to_string(&world.to_serde::<Sandwich>(&value)).unwrap();
+    let json_value: serde_json::Value = serde_json::from_str(&json).unwrap();
+    let mut flags: Vec<&str> = json_value["toppings"]
+        .as_str()
+        .unwrap()
+        .split('|')
+        .collect();
+    flags.sort();
+    assert_eq!(flags, ["bacon", "tomato"]);
+
+    let mut result = Sandwich::default();
+    let mut de = serde_json::Deserializer::from_str(&json);
+    world
+        .from_serde::<Sandwich>(&mut result)
+        .deserialize(&mut de)
+        .unwrap();
+    assert_eq!(result, value);
+
+    let empty = Sandwich::default();
+    let json = serde_json::to_string(&world.to_serde::<Sandwich>(&empty)).unwrap();
+    assert_eq!(json, r#"{"toppings":"0"}"#);
+
+    let mut de = serde_json::Deserializer::from_str(&json);
+    world
+        .from_serde::<Sandwich>(&mut result)
+        .deserialize(&mut de)
+        .unwrap();
+    assert_eq!(result, empty);
+}
+
+#[test]
+fn serde_array_member() {
+    #[derive(Debug, Default, PartialEq, Component)]
+    struct Path {
+        points: [i32; 3],
+        weight: f32,
+    }
+
+    let world = World::new();
+    world
+        .component::<Path>()
+        .member(i32::id(), ("points", Count(3), offset_of!(Path, points)))
+        .member(f32::id(), ("weight", Count(0), offset_of!(Path, weight)));
+
+    let value = Path {
+        points: [1, 2, 3],
+        weight: 0.5,
+    };
+    let json = serde_json::to_string(&world.to_serde::<Path>(&value)).unwrap();
+    assert_eq!(json, r#"{"points":[1,2,3],"weight":0.5}"#);
+
+    let mut result = Path::default();
+    let mut de = serde_json::Deserializer::from_str(&json);
+    world
+        .from_serde::<Path>(&mut result)
+        .deserialize(&mut de)
+        .unwrap();
+    assert_eq!(result, value);
+
+    let mut de = serde_json::Deserializer::from_str(r#"{"points":[1,2,3,4]}"#);
+    assert!(
+        world
+            .from_serde::<Path>(&mut result)
+            .deserialize(&mut de)
+            .is_err()
+    );
+}
+
+#[test]
+fn serde_opaque_string_and_vec() {
+    let world = World::new();
+    world.component::<SerdeNested>();
+
+    let value = nested();
+    let json = serde_json::to_string(&world.to_serde::<SerdeNested>(&value)).unwrap();
+    assert_eq!(
+        json,
+        r#"{"color":"Blue","pos":{"x":1.5,"y":-2.0},"name":"hello","names":["a","b"],"values":[1,2,3]}"#
+    );
+
+    let mut result = SerdeNested {
+        names: vec!["x".to_string(); 5],
+        values: vec![9; 10],
+        ..Default::default()
+    };
+    let mut de = serde_json::Deserializer::from_str(&json);
+    world
+        .from_serde::<SerdeNested>(&mut result)
+        .deserialize(&mut de)
+        .unwrap();
+    assert_eq!(result, value);
+}
+
+#[test]
+fn serde_empty_vec() {
+    let world = World::new();
+    world.component::<SerdeNested>();
+
+    let mut result = nested();
+    let mut de = serde_json::Deserializer::from_str(r#"{"names":[],"values":[]}"#);
+    world
+        .from_serde::<SerdeNested>(&mut result)
+        .deserialize(&mut de)
+        .unwrap();
+    assert!(result.names.is_empty());
+    assert!(result.values.is_empty());
+}
+
+#[test]
+fn serde_entity_member() {
+    #[derive(Debug, Component)]
+    #[flecs(meta)]
+    struct Target {
+        entity: Entity,
+    }
+
+    let world = World::new();
+    world.component::<Target>();
+
+    let parent = world.entity_named("parent");
+    let child = world.entity_named("child").child_of(parent);
+
+    let value = Target { entity: child.id() };
+    let json = serde_json::to_string(&world.to_serde::<Target>(&value)).unwrap();
+    assert_eq!(json, r#"{"entity":"parent.child"}"#);
+
+    let mut result = Target {
+        entity: Entity::null(),
+    };
+    let mut de = serde_json::Deserializer::from_str(&json);
+    world
+        .from_serde::<Target>(&mut result)
+        .deserialize(&mut de)
+        .unwrap();
+    assert_eq!(result.entity, child.id());
+}
+
+#[test]
+fn serde_bincode_round_trip() {
+    let world = World::new();
+    world.component::<SerdeNested>();
+
+    let value = nested();
+    let config = bincode::config::standard();
+    let bytes =
+        bincode::serde::encode_to_vec(world.to_serde::<SerdeNested>(&value), config).unwrap();
+
+    let mut result = SerdeNested::default();
+    let (_, read) = bincode::serde::seed_decode_from_slice(
+        world.from_serde::<SerdeNested>(&mut result),
+        &bytes,
+        config,
+    )
+    .unwrap();
+    assert_eq!(read, bytes.len());
+    assert_eq!(result, value);
+}
+
+#[test]
+fn serde_component_id() {
+    let world = World::new();
+    let id = world.component::<SerdePosition>().id();
+
+    let pos = SerdePosition { x: 1.0, y: 2.0 };
+    let json = serde_json::to_string(
+        &world.to_serde_id(id, &pos as *const SerdePosition as *const core::ffi::c_void),
+    )
+    .unwrap();
+    assert_eq!(json, r#"{"x":1.0,"y":2.0}"#);
+
+    let mut result = SerdePosition::default();
+    let mut de = serde_json::Deserializer::from_str(&json);
+    world
+        .from_serde_id(
+            id,
+            &mut result as *mut SerdePosition as *mut core::ffi::c_void,
+        )
+        .deserialize(&mut de)
+        .unwrap();
+    assert_eq!(result, pos);
+}
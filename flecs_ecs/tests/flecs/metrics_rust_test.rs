@@ -0,0 +1,128 @@
+#![cfg(feature = "flecs_metrics")]
+
+use core::mem::offset_of;
+
+use flecs_ecs::addons::metrics::*;
+use flecs_ecs::addons::units::duration::Seconds;
+use flecs_ecs::prelude::*;
+
+#[derive(Component, Default)]
+struct Cooldown {
+    value: f64,
+}
+
+#[derive(Component)]
+struct Npc;
+
+#[test]
+fn metrics_prometheus_gauge() {
+    let world = World::new();
+    world.import::<MetricsModule>();
+
+    world.component::<Cooldown>().member_unit(
+        f64::id(),
+        Seconds,
+        ("value", Count(0), offset_of!(Cooldown, value)),
+    );
+
+    let metrics = world.entity_named("game");
+    world.component::<Cooldown>().metric::<Gauge>(
+        Some(metrics),
+        Some("Time until \"ready\""),
+        Some("cooldown"),
+    );
+
+    let parent = world.entity_named("units");
+    world
+        .entity_named("archer")
+        .child_of(parent)
+        .set(Cooldown { value: 1.5 });
+    world.entity_named("knight").set(Cooldown { value: 0.25 });
+    world.progress();
+
+    let text = world.metrics_to_prometheus();
+    assert!(text.contains("# HELP game_cooldown_seconds Time until \"ready\"\n"));
+    assert!(text.contains("# TYPE game_cooldown_seconds gauge\n"));
+    assert!(text.contains("game_cooldown_seconds{entity=\"units.archer\"} 1.5\n"));
+    assert!(text.contains("game_cooldown_seconds{entity=\"knight\"} 0.25\n"));
+}
+
+#[test]
+fn metrics_prometheus_counter_id() {
+    let world = World::new();
+    world.import::<MetricsModule>();
+
+    world
+        .metric(world.entity_named("npc_seconds"))
+        .kind(CounterId)
+        .id(Npc);
+
+    world.entity().add(Npc);
+    world.entity().add(Npc);
+    world.progress_time(1.0);
+    world.progress_time(1.0);
+
+    let text = world.metrics_to_prometheus();
+    assert!(text.contains("# TYPE npc_seconds_total counter\n"));
+    assert!(text.contains("npc_seconds_total 4\n"));
+    assert!(!text.contains("# HELP npc_seconds_total"));
+}
+
+#[derive(Component)]
+struct Movement;
+
+#[test]
+fn metrics_prometheus_oneof_targets() {
+    let world = World::new();
+    world.import::<MetricsModule>();
+
+    let movement = world.component::<Movement>().add(flecs::OneOf);
+    let walking = world.entity_named("Walking").child_of(movement);
+    world.entity_named("Running").child_of(movement);
+
+    world
+        .metric(world.entity_named("movement"))
+        .kind(Gauge)
+        .id((movement, flecs::Wildcard::ID))
+        .targets(true);
+
+    world.entity_named("player").add((movement, walking));
+    world.progress();
+
+    let text = world.metrics_to_prometheus();
+    assert!(text.contains("# TYPE movement_seconds gauge\n"));
+    assert!(text.contains("movement_seconds{entity=\"player\",target=\"walking\"} 1\n"));
+    assert!(text.contains("movement_seconds{entity=\"player\",target=\"running\"} 0\n"));
+}
+
+#[test]
+fn metrics_prometheus_empty() {
+    let world = World::new();
+    world.import::<MetricsModule>();
+    assert_eq!(world.metrics_to_prometheus(), "");
+}
+
+#[cfg(feature = "flecs_http")]
+#[test]
+fn metrics_prometheus_http_route() {
+    use flecs_ecs::addons::http::*;
+
+    let world = World::new();
+    world.import::<MetricsModule>();
+    world
+        .metric(world.entity_named("npcs"))
+        .kind(CounterId)
+        .id(Npc);
+    world.entity().add(Npc);
+    world.progress_time(1.0);
+
+    let server = world.http_server().metrics_route("/metrics").build();
+    let response = server.request(HttpMethod::Get, "/metrics", None);
+    assert_eq!(response.code(), 200);
+    assert_eq!(
+        response.content_type(),
+        Some("text/plain; version=0.0.4; charset=utf-8")
+    );
+    assert_eq!(response.body(), world.metrics_to_prometheus());
+    assert!(response.body().contains("npcs_total 1\n"));
+}
@@ -0,0 +1,667 @@
+#![allow(clippy::float_cmp)]
+
+extern crate alloc;
+use alloc::{rc::Rc, string::String, vec, vec::Vec};
+use core::cell::RefCell;
+
+use flecs_ecs::prelude::*;
+
+#[derive(Component, Default)]
+struct Steps(u32);
+
+fn assert_near(value: FTime, expected: FTime) {
+    assert!(
+        (value - expected).abs() < 1e-4,
+        "expected {expected}, got {value}"
+    );
+}
+
+fn count_steps(world: &World) -> Entity {
+    let counter = world.entity().set(Steps(0)).id();
+    world
+        .system::<&mut Steps>()
+        .kind(FixedUpdate)
+        .each_iter(|it, _, steps| {
+            assert_eq!(it.delta_time(), 0.25);
+            steps.0 += 1;
+        });
+    counter
+}
+
+#[test]
+fn pipeline_fixed_timestep_steps() {
+    let world = World::new();
+    let counter = count_steps(&world);
+    world.set_fixed_timestep(0.25, 0);
+
+    world.progress_time(0.6);
+    world
+        .entity_from_id(counter)
+        .get::<&Steps>(|steps| assert_eq!(steps.0, 2));
+    let fixed = world.fixed_timestep().unwrap();
+    assert_eq!(fixed.steps(), 2);
+    assert_near(fixed.accumulator(), 0.1);
+    assert_near(fixed.alpha(), 0.4);
+
+    world.progress_time(0.2);
+    world
+        .entity_from_id(counter)
+        .get::<&Steps>(|steps| assert_eq!(steps.0, 3));
+    let fixed = world.fixed_timestep().unwrap();
+    assert_eq!(fixed.steps(), 1);
+    assert_near(fixed.alpha(), 0.2);
+
+    world.progress_time(0.1);
+    world
+        .entity_from_id(counter)
+        .get::<&Steps>(|steps| assert_eq!(steps.0, 3));
+    assert_eq!(world.fixed_timestep().unwrap().steps(), 0);
+}
+
+#[test]
+fn pipeline_fixed_timestep_max_steps() {
+    let world = World::new();
+    let counter = count_steps(&world);
+    world.set_fixed_timestep(0.25, 2);
+
+    world.progress_time(1.1);
+    world
+        .entity_from_id(counter)
+        .get::<&Steps>(|steps| assert_eq!(steps.0, 2));
+    // the steps that didn't fit are dropped
+    let fixed = world.fixed_timestep().unwrap();
+    assert_eq!(fixed.max_steps(), 2);
+    assert_near(fixed.accumulator(), 0.1);
+
+    world.progress_time(0.2);
+    world
+        .entity_from_id(counter)
+        .get::<&Steps>(|steps| assert_eq!(steps.0, 3));
+}
+
+#[test]
+fn pipeline_fixed_update_not_in_default_pipeline() {
+    let world = World::new();
+    let counter = count_steps(&world);
+
+    world.progress_time(1.0);
+    world
+        .entity_from_id(counter)
+        .get::<&Steps>(|steps| assert_eq!(steps.0, 0));
+    assert!(world.fixed_timestep().is_none());
+
+    world.set_fixed_timestep(0.25, 0);
+    world.progress_time(0.5);
+    world
+        .entity_from_id(counter)
+        .get::<&Steps>(|steps| assert_eq!(steps.0, 2));
+
+    world.set_fixed_timestep(0.0, 0);
+    assert!(world.fixed_timestep().is_none());
+    world.progress_time(0.5);
+    world
+        .entity_from_id(counter)
+        .get::<&Steps>(|steps| assert_eq!(steps.0, 2));
+}
+
+#[test]
+fn pipeline_fixed_timestep_alpha_in_render_phase() {
+    let world = World::new();
+    world.set_fixed_timestep(0.25, 0);
+
+    let events = Rc::new(RefCell::new(Vec::new()));
+    let fixed = events.clone();
+    world.system::<()>().kind(FixedUpdate).run(move |mut it| {
+        while it.next() {}
+        fixed.borrow_mut().push(-1.0);
+    });
+    let render = events.clone();
+    world
+        .system::<()>()
+        .kind(flecs::pipeline::OnStore)
+        .run(move |mut it| {
+            while it.next() {}
+            render.borrow_mut().push(it.fixed_timestep_alpha());
+        });
+
+    world.progress_time(0.3);
+    world.progress_time(0.3);
+
+    let events = events.borrow();
+    assert_eq!(events.len(), 4);
+    assert_eq!(events[0], -1.0);
+    assert_near(events[1], 0.2);
+    assert_eq!(events[2], -1.0);
+    assert_near(events[3], 0.4);
+}
+
+#[test]
+fn pipeline_fixed_timestep_runs_startup_systems() {
+    let world = World::new();
+    world.set_fixed_timestep(0.25, 0);
+
+    let events = Rc::new(RefCell::new(Vec::new()));
+    for (phase, name) in [
+        (Entity::from(flecs::pipeline::OnStart), "start"),
+        (Entity::from(flecs::pipeline::OnUpdate), "update"),
+    ] {
+        let events = events.clone();
+        world.system::<()>().kind(phase).run(move |mut it| {
+            while it.next() {}
+            events.borrow_mut().push(name);
+        });
+    }
+    let fixed = events.clone();
+    world.system::<()>().kind(FixedUpdate).run(move |mut it| {
+        while it.next() {}
+        fixed.borrow_mut().push("fixed");
+    });
+
+    world.progress_time(0.25);
+    world.progress_time(0.25);
+
+    assert_eq!(
+        *events.borrow(),
+        vec!["start", "fixed", "update", "fixed", "update"]
+    );
+}
+
+#[derive(Component)]
+struct PositionS {
+    x: f32,
+}
+
+#[derive(Component)]
+struct VelocityS {
+    x: f32,
+}
+
+/// Creates a system that sets a component with a command, followed by systems that read it.
+fn sync_point_systems(world: &World) {
+    world
+        .entity()
+        .set(PositionS { x: 0.0 })
+        .set(VelocityS { x: 1.0 });
+
+    world
+        .system_named::<()>("SetVelocity")
+        .with(&PositionS::id())
+        .set_inout_none()
+        .write(VelocityS::id())
+        .each_entity(|e, ()| {
+            e.set(VelocityS { x: 2.0 });
+        });
+
+    world
+        .system_named::<(&mut PositionS, &VelocityS)>("Move")
+        .each(|(p, v)| p.x += v.x);
+
+    world.system_named::<&PositionS>("Print").each(|_| {});
+}
+
+fn schedule_names(schedule: &PipelineSchedule) -> Vec<Vec<String>> {
+    schedule
+        .segments()
+        .iter()
+        .map(|segment| {
+            segment
+                .systems()
+                .iter()
+                .map(|system| system.system().name())
+                .collect()
+        })
+        .collect()
+}
+
+#[test]
+fn pipeline_schedule_sync_point() {
+    let world = World::new();
+    sync_point_systems(&world);
+
+    let schedule = world.pipeline_schedule(world.get_pipeline());
+    assert_eq!(schedule.pipeline(), world.get_pipeline());
+    assert_eq!(
+        schedule_names(&schedule),
+        vec![vec!["SetVelocity"], vec!["Move", "Print"]]
+    );
+
+    let position = world.id_view_from(PositionS::id());
+    let velocity = world.id_view_from(VelocityS::id());
+    let segments = schedule.segments();
+    assert_eq!(segments[0].sync_reason(), None);
+    assert_eq!(
+        segments[1].sync_reason(),
+        Some(SyncReason::Component(velocity))
+    );
+    for segment in segments {
+        assert!(!segment.is_multi_threaded());
+        assert!(!segment.is_immediate());
+    }
+
+    let set_velocity = &segments[0].systems()[0];
+    assert!(set_velocity.reads().is_empty());
+    assert_eq!(set_velocity.writes(), [velocity]);
+
+    let move_system = &segments[1].systems()[0];
+    assert_eq!(move_system.reads(), [position, velocity]);
+    assert_eq!(move_system.writes(), [position]);
+    assert_eq!(schedule.systems().count(), 3);
+}
+
+#[test]
+fn pipeline_schedule_threading_and_staging() {
+    let world = World::new();
+    world.entity().set(PositionS { x: 0.0 });
+
+    world
+        .system_named::<&mut PositionS>("Parallel")
+        .par_each(|p| p.x += 1.0);
+    world.system_named::<&PositionS>("Serial").each(|_| {});
+    world
+        .system_named::<&PositionS>("Immediate")
+        .immediate(true)
+        .each(|_| {});
+    world.system_named::<&PositionS>("Staged").each(|_| {});
+
+    let schedule = world.pipeline_schedule(world.get_pipeline());
+    assert_eq!(
+        schedule_names(&schedule),
+        vec![
+            vec!["Parallel"],
+            vec!["Serial"],
+            vec!["Immediate"],
+            vec!["Staged"]
+        ]
+    );
+
+    let segments = schedule.segments();
+    assert!(segments[0].is_multi_threaded());
+    assert!(segments[0].systems()[0].is_multi_threaded());
+    assert!(segments[2].is_immediate());
+    assert!(segments[2].systems()[0].is_immediate());
+    assert_eq!(
+        segments
+            .iter()
+            .map(ScheduleSegment::sync_reason)
+            .collect::<Vec<_>>(),
+        vec![
+            None,
+            Some(SyncReason::MultiThreaded),
+            Some(SyncReason::Immediate),
+            Some(SyncReason::Immediate)
+        ]
+    );
+}
+
+#[test]
+fn pipeline_schedule_skips_inactive_systems() {
+    let world = World::new();
+    world.entity().set(PositionS { x: 0.0 });
+
+    world.system_named::<&PositionS>("Active").each(|_| {});
+    world.system_named::<&VelocityS>("Inactive").each(|_| {});
+
+    let schedule = world.pipeline_schedule(world.get_pipeline());
+    assert_eq!(schedule_names(&schedule), vec![vec!["Active"]]);
+}
+
+#[test]
+#[cfg(feature = "flecs_stats")]
+fn pipeline_schedule_matches_flecs() {
+    let world = World::new();
+    sync_point_systems(&world);
+    world
+        .system_named::<&mut PositionS>("Parallel")
+        .par_each(|p| p.x += 1.0);
+    world
+        .system_named::<&PositionS>("Immediate")
+        .kind(flecs::pipeline::PostUpdate)
+        .immediate(true)
+        .each(|_| {});
+
+    world.progress();
+
+    // the pipeline stats list the systems that flecs scheduled, with a 0 for each merge
+    let mut stats: flecs_ecs::sys::ecs_pipeline_stats_t = unsafe { core::mem::zeroed() };
+    unsafe {
+        flecs_ecs::sys::ecs_pipeline_stats_get(
+            world.ptr_mut(),
+            *world.get_pipeline().id(),
+            &mut stats,
+        );
+    }
+    let scheduled = unsafe {
+        core::slice::from_raw_parts(
+            stats.systems.array as *const u64,
+            stats.systems.count as usize,
+        )
+    };
+    let expected = scheduled
+        .split(|&system| system == 0)
+        .filter(|segment| !segment.is_empty())
+        .map(<[u64]>::to_vec)
+        .collect::<Vec<_>>();
+    unsafe { flecs_ecs::sys::ecs_pipeline_stats_fini(&mut stats) };
+    assert!(expected.len() >= 3);
+
+    let schedule = world.pipeline_schedule(world.get_pipeline());
+    let segments = schedule
+        .segments()
+        .iter()
+        .map(|segment| {
+            segment
+                .systems()
+                .iter()
+                .map(|system| *system.system().id())
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(segments, expected);
+}
+
+/// Returns the number of merges in a frame of the world.
+fn merges_per_frame(world: &World) -> i64 {
+    let merge_count = || unsafe {
+        (*flecs_ecs::sys::ecs_get_world_info(world.ptr_mut())
+            .cast::<flecs_ecs::sys::ecs_world_info_t>())
+        .merge_count_total
+    };
+    world.progress();
+    let before = merge_count();
+    world.progress();
+    merge_count() - before
+}
+
+#[test]
+fn pipeline_schedule_merge_count() {
+    let scenarios: [fn(&World); 3] = [
+        sync_point_systems,
+        |world| {
+            world.entity().set(PositionS { x: 0.0 });
+            world
+                .system_named::<&mut PositionS>("Parallel")
+                .par_each(|p| p.x += 1.0);
+            world.system_named::<&PositionS>("Serial").each(|_| {});
+            world
+                .system_named::<&PositionS>("Immediate")
+                .immediate(true)
+                .each(|_| {});
+            world.system_named::<&PositionS>("Staged").each(|_| {});
+        },
+        |world| {
+            world.entity().set(PositionS { x: 0.0 });
+            world.system_named::<&PositionS>("Active").each(|_| {});
+            world.system_named::<&VelocityS>("Inactive").each(|_| {});
+        },
+    ];
+
+    for scenario in scenarios {
+        let world = World::new();
+        scenario(&world);
+        let schedule = world.pipeline_schedule(world.get_pipeline());
+        // the pipeline merges after each staged segment, including the last one
+        let staged = schedule
+            .segments()
+            .iter()
+            .filter(|segment| !segment.is_immediate())
+            .count();
+        assert_eq!(staged as i64, merges_per_frame(&world));
+    }
+}
+
+#[test]
+fn pipeline_schedule_to_dot_and_mermaid() {
+    let world = World::new();
+    sync_point_systems(&world);
+
+    let schedule = world.pipeline_schedule(world.get_pipeline());
+
+    let dot = schedule.to_dot();
+    assert!(dot.starts_with("digraph pipeline {\n"));
+    assert!(dot.contains("subgraph cluster_0 {"));
+    assert!(dot.contains("label=\"segment 1: single threaded, staged\";"));
+    assert!(dot.contains("system0 [label=\"SetVelocity\\nwrites: VelocityS\"];"));
+    assert!(dot.contains("merge1 [shape=diamond, label=\"merge: VelocityS was written\"];"));
+    assert!(dot.contains("system0 -> merge1;"));
+    assert!(dot.contains("merge1 -> system1;"));
+    assert!(dot.contains("system1 -> system2;"));
+    assert!(dot.ends_with("}\n"));
+
+    let mermaid = schedule.to_mermaid();
+    assert!(mermaid.starts_with("flowchart TD\n"));
+    assert!(mermaid.contains("subgraph segment1[\"segment 1: single threaded, staged\"]"));
+    assert!(
+        mermaid.contains("system1[\"Move<br/>reads: PositionS, VelocityS<br/>writes: PositionS\"]")
+    );
+    assert!(mermaid.contains("merge1{{\"merge: VelocityS was written\"}}"));
+    assert!(mermaid.contains("system0 --> merge1"));
+    assert!(mermaid.contains("merge1 --> system1"));
+}
+
+type Events = Rc<RefCell<Vec<&'static str>>>;
+
+/// Returns a system callback that records the name of the system when it runs.
+fn record(events: &Events, name: &'static str) -> impl FnMut(TableIter<true, ()>) + 'static {
+    let events = events.clone();
+    move |mut it| {
+        while it.next() {}
+        events.borrow_mut().push(name);
+    }
+}
+
+#[test]
+fn pipeline_order_before_after() {
+    let world = World::new();
+    let events = Events::default();
+
+    let a = world.system_named::<()>("A").run(record(&events, "A"));
+    world.system_named::<()>("B").run(record(&events, "B"));
+    let c = world
+        .system_named::<()>("C")
+        .before(a)
+        .run(record(&events, "C"));
+    world
+        .system_named::<()>("D")
+        .after(a)
+        .before(c)
+        .kind(flecs::pipeline::PreUpdate)
+        .run(record(&events, "D"));
+
+    // systems that aren't constrained keep their creation order, and constraints between
+    // systems of different phases are ignored
+    world.progress();
+    assert_eq!(*events.borrow(), vec!["D", "B", "C", "A"]);
+
+    // constraints that are added later reorder the systems again
+    world
+        .system_named::<()>("E")
+        .before(c)
+        .run(record(&events, "E"));
+    events.borrow_mut().clear();
+    world.progress();
+    world.progress();
+    assert_eq!(
+        *events.borrow(),
+        vec!["D", "B", "E", "C", "A", "D", "B", "E", "C", "A"]
+    );
+    assert!(world.validate_system_order().is_ok());
+}
+
+#[test]
+fn pipeline_order_system_sets() {
+    let world = World::new();
+    let events = Events::default();
+
+    let input = world
+        .system_named::<()>("Input")
+        .run(record(&events, "Input"));
+
+    // sets can be ordered before their systems are created
+    let physics = world.system_set("Physics");
+    let render = world.system_set("Render");
+    render.after(physics);
+    physics.after(input);
+
+    world
+        .system_named::<()>("Draw")
+        .in_set(render)
+        .run(record(&events, "Draw"));
+    world
+        .system_named::<()>("Collide")
+        .in_set(physics)
+        .run(record(&events, "Collide"));
+    world
+        .system_named::<()>("Integrate")
+        .in_set(physics)
+        .before(world.lookup("Collide"))
+        .run(record(&events, "Integrate"));
+
+    assert_eq!(world.system_set("Physics").id(), physics.id());
+
+    world.progress();
+    assert_eq!(
+        *events.borrow(),
+        vec!["Input", "Integrate", "Collide", "Draw"]
+    );
+
+    let schedule = world.pipeline_schedule(world.get_pipeline());
+    let names = schedule
+        .systems()
+        .map(|system| system.system().name())
+        .collect::<Vec<_>>();
+    assert_eq!(names, ["Input", "Integrate", "Collide", "Draw"]);
+}
+
+#[test]
+fn pipeline_order_fixed_update() {
+    let world = World::new();
+    world.set_fixed_timestep(0.25, 0);
+    let events = Events::default();
+
+    let first = world
+        .system_named::<()>("First")
+        .kind(FixedUpdate)
+        .run(record(&events, "First"));
+    world
+        .system_named::<()>("Second")
+        .kind(FixedUpdate)
+        .before(first)
+        .run(record(&events, "Second"));
+
+    world.progress_time(0.5);
+    assert_eq!(*events.borrow(), vec!["Second", "First", "Second", "First"]);
+}
+
+#[test]
+fn pipeline_order_cycle() {
+    let world = World::new();
+
+    let a = world
+        .system_named::<()>("A")
+        .run(|mut it| while it.next() {});
+    let set = world.system_set("Set");
+    world
+        .system_named::<()>("B")
+        .in_set(set)
+        .after(a)
+        .run(|mut it| while it.next() {});
+    world
+        .system_named::<()>("C")
+        .run(|mut it| while it.next() {});
+    set.before(world.lookup("C"));
+    assert!(world.validate_system_order().is_ok());
+
+    world
+        .system_named::<()>("D")
+        .after(world.lookup("C"))
+        .before(a)
+        .run(|mut it| while it.next() {});
+
+    let err = world.validate_system_order().unwrap_err();
+    assert_eq!(err.phase(), "OnUpdate");
+    assert_eq!(err.systems(), ["A", "B", "C", "D"]);
+    assert_eq!(
+        err.to_string(),
+        "cycle in the order of the systems of phase OnUpdate: A -> B -> C -> D -> A"
+    );
+}
+
+#[test]
+fn pipeline_order_cycle_keeps_previous_order() {
+    let world = World::new();
+    let events = Rc::new(RefCell::new(Vec::new()));
+
+    let a = world.system_named::<()>("A").run(record(&events, "A"));
+    let b = world
+        .system_named::<()>("B")
+        .before(a)
+        .run(record(&events, "B"));
+    world
+        .system_named::<()>("C")
+        .after(a)
+        .before(b)
+        .run(record(&events, "C"));
+
+    world.progress();
+    assert_eq!(*events.borrow(), vec!["B", "A", "C"]);
+}
+
+#[test]
+fn pipeline_order_before_first_frame() {
+    let world = World::new();
+    let events = Rc::new(RefCell::new(Vec::new()));
+
+    let first = world
+        .system_named::<()>("First")
+        .run(record(&events, "First"));
+    world
+        .system_named::<()>("Second")
+        .before(first)
+        .run(record(&events, "Second"));
+
+    let schedule = world.pipeline_schedule(world.get_pipeline());
+    let names: Vec<_> = schedule.segments()[0]
+        .systems()
+        .iter()
+        .map(|system| system.system().name())
+        .collect();
+    assert_eq!(names, ["Second", "First"]);
+
+    world.run_pipeline(world.get_pipeline());
+    assert_eq!(*events.borrow(), vec!["Second", "First"]);
+}
+
+#[test]
+fn pipeline_order_disabled_system() {
+    let world = World::new();
+    let events = Rc::new(RefCell::new(Vec::new()));
+
+    let a = world.system_named::<()>("A").run(record(&events, "A"));
+    a.disable_self();
+    world
+        .system_named::<()>("B")
+        .after(a)
+        .run(record(&events, "B"));
+    // every system with a constraint in this phase is disabled
+    let c = world
+        .system_named::<()>("C")
+        .kind(flecs::pipeline::OnStore)
+        .run(record(&events, "C"));
+    c.disable_self();
+    let d = world
+        .system_named::<()>("D")
+        .kind(flecs::pipeline::OnStore)
+        .before(c)
+        .run(record(&events, "D"));
+    d.disable_self();
+
+    world.progress();
+    assert_eq!(*events.borrow(), vec!["B"]);
+
+    events.borrow_mut().clear();
+    a.enable_self();
+    c.enable_self();
+    d.enable_self();
+    world.progress();
+    assert_eq!(*events.borrow(), vec!["A", "B", "D", "C"]);
+}
@@ -1,4 +1,6 @@
 #![allow(dead_code)]
+extern crate alloc;
+
 use flecs_ecs::core::*;
 
 use crate::common_test::*;
@@ -118,3 +120,782 @@ fn test_trait_query() {
 
     assert_eq!(count, 6);
 }
+
+#[test]
+fn query_rust_group_by_with() {
+    let world = World::new();
+
+    let tgt_a = world.entity();
+    let tgt_b = world.entity();
+    let tgt_c = world.entity();
+
+    world
+        .entity()
+        .set(Position { x: 3, y: 0 })
+        .add((Rel::id(), tgt_c));
+    world
+        .entity()
+        .set(Position { x: 1, y: 0 })
+        .add((Rel::id(), tgt_a));
+    world
+        .entity()
+        .set(Position { x: 2, y: 0 })
+        .add((Rel::id(), tgt_b));
+
+    let order = [tgt_b.id(), tgt_c.id(), tgt_a.id()];
+    let query = world
+        .query::<&Position>()
+        .group_by_with(Rel::id(), move |_world, table, rel| {
+            order
+                .iter()
+                .position(|&tgt| table.has((rel, tgt)))
+                .map(|index| index as u64 + 1)
+                .unwrap_or(0)
+        })
+        .build();
+
+    let mut xs = Vec::new();
+    query.each(|pos| xs.push(pos.x));
+    assert_eq!(xs, [2, 3, 1]);
+}
+
+#[test]
+fn query_rust_group_ctx() {
+    struct GroupName(String);
+
+    let world = World::new();
+
+    world.entity().set(Position { x: 1, y: 0 });
+    world.entity().set(Position { x: 2, y: 0 }).add(TagA::id());
+
+    let query = world
+        .query::<&Position>()
+        .group_by_with(
+            TagA::id(),
+            |_world, table, tag| if table.has(tag) { 2 } else { 1 },
+        )
+        .on_group_create_with(|_world, group_id| GroupName(format!("group {group_id}")))
+        .build();
+
+    let mut count = 0;
+    query.run(|mut it| {
+        while it.next() {
+            let name = it.group_ctx::<GroupName>().unwrap();
+            assert_eq!(name.0, format!("group {}", it.group_id()));
+            assert!(it.group_ctx::<u32>().is_none());
+            count += 1;
+        }
+    });
+    assert_eq!(count, 2);
+
+    assert_eq!(query.group_ctx::<GroupName>(1).unwrap().0, "group 1");
+    assert!(query.group_ctx::<GroupName>(3).is_none());
+}
+
+#[test]
+fn query_rust_group_ctx_dropped() {
+    use alloc::rc::Rc;
+
+    let world = World::new();
+    let counter = Rc::new(());
+
+    world.entity().set(Position { x: 1, y: 0 }).add(TagA::id());
+    world.entity().set(Position { x: 2, y: 0 });
+
+    let deleted = Rc::new(core::cell::RefCell::new(Vec::new()));
+    let counter_create = counter.clone();
+    let deleted_delete = deleted.clone();
+    let query = world
+        .query::<&Position>()
+        .group_by_with(
+            TagA::id(),
+            |_world, table, tag| if table.has(tag) { 2 } else { 1 },
+        )
+        .on_group_create_delete_with(
+            move |_world, _group_id| counter_create.clone(),
+            move |_world, group_id, ctx: Rc<()>| {
+                deleted_delete.borrow_mut().push(group_id);
+                drop(ctx);
+            },
+        )
+        .build();
+
+    assert_eq!(Rc::strong_count(&counter), 4);
+
+    query.destruct();
+    deleted.borrow_mut().sort();
+    assert_eq!(*deleted.borrow(), [1, 2]);
+    assert_eq!(Rc::strong_count(&counter), 1);
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic]
+fn query_rust_group_ctx_raw_delete() {
+    let world = World::new();
+
+    world
+        .query::<&Position>()
+        .group_by(TagA::id())
+        .on_group_create_with(|_world, group_id| group_id)
+        .on_group_delete(None);
+}
+
+#[test]
+fn query_rust_group_ctx_untyped() {
+    let world = World::new();
+
+    world.entity().set(Position { x: 1, y: 0 });
+
+    let query = world.query::<&Position>().group_by(TagA::id()).build();
+
+    query.run(|mut it| {
+        while it.next() {
+            assert!(it.group_ctx::<u32>().is_none());
+        }
+    });
+}
+
+#[test]
+fn query_rust_iter_chunks() {
+    let world = World::new();
+
+    world
+        .entity()
+        .set(Position { x: 1, y: 0 })
+        .set(Velocity { x: 1, y: 2 });
+    world
+        .entity()
+        .set(Position { x: 2, y: 0 })
+        .set(Velocity { x: 1, y: 2 });
+    world
+        .entity()
+        .set(Position { x: 3, y: 0 })
+        .set(Velocity { x: 1, y: 2 })
+        .add(TagA::id());
+    world.entity().set(Position { x: 4, y: 0 });
+
+    let query = world.new_query::<(&mut Position, &Velocity)>();
+
+    let counts = query.iter().map(|chunk| chunk.count()).collect::<Vec<_>>();
+    assert_eq!(counts, [2, 1]);
+
+    for mut chunk in query.iter() {
+        assert_eq!(chunk.entities().len(), chunk.count());
+        assert!(chunk.table().is_some());
+        let (positions, velocities) = chunk.columns();
+        for (pos, vel) in positions.iter_mut().zip(velocities.iter()) {
+            pos.x += vel.x;
+            pos.y += vel.y;
+        }
+    }
+
+    let xs = query
+        .iter()
+        .flat_map(|mut chunk| chunk.columns().0.iter().map(|p| p.x).collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+    assert_eq!(xs, [2, 3, 4]);
+
+    let mut chunk = query.iter().next().unwrap();
+    let (pos, vel) = chunk.get(1);
+    assert_eq!(pos.x, 3);
+    assert_eq!(vel.y, 2);
+    assert_eq!(chunk.iter_mut().len(), 2);
+}
+
+#[test]
+fn query_rust_iter_rows() {
+    let world = World::new();
+
+    let a = world.entity().set(Position { x: 1, y: 0 });
+    let b = world.entity().set(Position { x: 2, y: 0 }).add(TagA::id());
+    let c = world.entity().set(Position { x: 3, y: 0 });
+    world.entity().set(Velocity { x: 0, y: 0 });
+
+    let query = world.new_query::<&mut Position>();
+
+    let entities = query
+        .iter_rows()
+        .map(|row| row.entity().id())
+        .collect::<Vec<_>>();
+    assert_eq!(entities, [a.id(), c.id(), b.id()]);
+
+    let odd = query
+        .iter_rows()
+        .filter(|row| row.entity() != c)
+        .map(|mut row| row.get().x)
+        .collect::<Vec<_>>();
+    assert_eq!(odd, [1, 2]);
+
+    // both sides of the zip only read, which the safety locks allow
+    let read = world.new_query::<&Position>();
+    let pairs = read
+        .iter_rows()
+        .zip(read.iter_rows().skip(1))
+        .map(|(mut first, second)| (first.get().x, second.entity().id()))
+        .collect::<Vec<_>>();
+    assert_eq!(pairs, [(1, c.id()), (3, b.id())]);
+
+    for mut row in query.iter_rows() {
+        row.get().y = 10;
+    }
+    query.each(|pos| assert_eq!(pos.y, 10));
+}
+
+#[test]
+fn query_rust_iter_early_break() {
+    let world = World::new();
+
+    for i in 0..4 {
+        let e = world.entity().set(Position { x: i, y: 0 });
+        if i % 2 == 0 {
+            e.add(TagA::id());
+        }
+    }
+
+    let query = world.new_query::<&Position>();
+
+    let mut visited = 0;
+    for row in query.iter_rows() {
+        visited += 1;
+        if row.entity().has(TagA::id()) {
+            break;
+        }
+    }
+    assert_eq!(visited, 3);
+
+    let first = query.iter().next().map(|chunk| chunk.count());
+    assert_eq!(first, Some(2));
+
+    // tables are unlocked once the items are dropped
+    let e = world.entity().set(Position { x: 10, y: 0 });
+    e.add(TagB::id());
+    assert_eq!(query.iter_rows().count(), 5);
+}
+
+#[test]
+fn query_rust_iter_sparse() {
+    let world = World::new();
+
+    world.component::<Position>().add_trait::<flecs::Sparse>();
+
+    world
+        .entity()
+        .set(Position { x: 1, y: 0 })
+        .set(Velocity { x: 1, y: 2 });
+    world
+        .entity()
+        .set(Position { x: 2, y: 0 })
+        .set(Velocity { x: 1, y: 2 });
+
+    let query = world.new_query::<(&mut Position, &Velocity)>();
+
+    for mut chunk in query.iter() {
+        for (pos, vel) in chunk.iter_mut() {
+            pos.x += vel.x;
+        }
+    }
+
+    let xs = query
+        .iter_rows()
+        .map(|mut row| row.get().0.x)
+        .collect::<Vec<_>>();
+    assert_eq!(xs, [2, 3]);
+}
+
+#[test]
+#[should_panic]
+fn query_rust_iter_sparse_columns() {
+    let world = World::new();
+
+    world.component::<Position>().add_trait::<flecs::Sparse>();
+    world.entity().set(Position { x: 1, y: 0 });
+
+    let query = world.new_query::<&Position>();
+    for mut chunk in query.iter() {
+        let _ = chunk.columns();
+    }
+}
+
+#[test]
+fn query_rust_iter_singleton() {
+    let world = World::new();
+
+    world
+        .component::<Position>()
+        .add_trait::<flecs::Singleton>();
+    world.set(Position { x: 5, y: 0 });
+    world.entity().set(Velocity { x: 1, y: 0 });
+    world.entity().set(Velocity { x: 2, y: 0 });
+
+    let query = world.new_query::<(&mut Velocity, &Position)>();
+    for mut chunk in query.iter() {
+        let (velocities, positions) = chunk.columns();
+        assert_eq!(positions.len(), 1);
+        for vel in velocities {
+            vel.x += positions[0].x;
+        }
+    }
+
+    let xs = query
+        .iter_rows()
+        .map(|mut row| row.get().0.x)
+        .collect::<Vec<_>>();
+    assert_eq!(xs, [6, 7]);
+}
+
+#[test]
+#[should_panic]
+fn query_rust_iter_singleton_write() {
+    let world = World::new();
+
+    world
+        .component::<Position>()
+        .add_trait::<flecs::Singleton>();
+    world.set(Position { x: 5, y: 0 });
+    world.entity().set(Velocity { x: 1, y: 0 });
+
+    let query = world.new_query::<(&Velocity, &mut Position)>();
+    let _ = query.iter().count();
+}
+
+struct ScopedThreads;
+
+impl QueryThreadPool for ScopedThreads {
+    fn run_jobs(&self, jobs: usize, job: &(dyn Fn(usize) + Sync)) {
+        std::thread::scope(|scope| {
+            for index in 1..jobs {
+                scope.spawn(move || job(index));
+            }
+            job(0);
+        });
+    }
+}
+
+#[test]
+fn query_rust_par_each() {
+    let world = World::new();
+    world.set_threads(4);
+
+    for i in 0..1000 {
+        let e = world
+            .entity()
+            .set(Position { x: 0, y: 0 })
+            .set(Velocity { x: i, y: 1 });
+        if i % 3 == 0 {
+            e.add(TagA::id());
+        }
+    }
+    world.entity().set(Position { x: 0, y: 0 });
+
+    let query = world.new_query::<(&mut Position, &Velocity)>();
+    let visited = core::sync::atomic::AtomicUsize::new(0);
+    query.par_each(|(pos, vel)| {
+        pos.x += vel.x;
+        pos.y += vel.y;
+        visited.fetch_add(1, core::sync::atomic::Ordering::Relaxed);
+    });
+    assert_eq!(visited.into_inner(), 1000);
+
+    query.each(|(pos, vel)| {
+        assert_eq!(pos.x, vel.x);
+        assert_eq!(pos.y, 1);
+    });
+    assert!(!world.is_readonly());
+
+    // the world can be changed again after the iteration
+    world.entity().set(Position { x: 0, y: 0 }).add(TagB::id());
+}
+
+#[test]
+fn query_rust_par_each_single_stage() {
+    let world = World::new();
+
+    for _ in 0..10 {
+        world.entity().set(Position { x: 0, y: 0 });
+    }
+
+    let query = world.new_query::<&mut Position>();
+    query.par_each(|pos| pos.x += 1);
+    query.each(|pos| assert_eq!(pos.x, 1));
+}
+
+#[test]
+fn query_rust_par_each_with_pool() {
+    let world = World::new();
+    world.set_stage_count(3);
+
+    world
+        .component::<Velocity>()
+        .add_trait::<flecs::Singleton>();
+    world.set(Velocity { x: 2, y: 0 });
+    for _ in 0..100 {
+        world.entity().set(Position { x: 0, y: 0 });
+    }
+
+    let threads = std::sync::Mutex::new(std::collections::HashSet::new());
+    let query = world.new_query::<(&mut Position, &Velocity)>();
+    query.par_each_with(&ScopedThreads, |(pos, vel)| {
+        pos.x += vel.x;
+        threads.lock().unwrap().insert(std::thread::current().id());
+    });
+    assert_eq!(threads.into_inner().unwrap().len(), 3);
+    query.each(|(pos, _)| assert_eq!(pos.x, 2));
+}
+
+#[test]
+#[should_panic(expected = "boom")]
+fn query_rust_par_each_panic() {
+    let world = World::new();
+    world.set_threads(2);
+
+    for _ in 0..10 {
+        world.entity().set(Position { x: 0, y: 0 });
+    }
+
+    let query = world.new_query::<&Position>();
+    query.par_each(|_| panic!("boom"));
+}
+
+#[test]
+#[should_panic]
+fn query_rust_par_each_singleton_write() {
+    let world = World::new();
+    world.set_threads(2);
+
+    world
+        .component::<Velocity>()
+        .add_trait::<flecs::Singleton>();
+    world.set(Velocity { x: 2, y: 0 });
+    world.entity().set(Position { x: 0, y: 0 });
+
+    let query = world.new_query::<(&Position, &mut Velocity)>();
+    query.par_each(|_| {});
+}
+
+#[test]
+#[should_panic]
+fn query_rust_par_each_in_system() {
+    let world = World::new();
+    world.set_threads(2);
+
+    world.entity().set(Position { x: 0, y: 0 });
+
+    let query = world.new_query::<&Position>();
+    world.system::<()>().run(move |mut it| {
+        while it.next() {}
+        query.par_each(|_| {});
+    });
+    world.progress();
+}
+
+fn query_expr_world() -> World {
+    let world = World::new();
+    world.component_named::<Position>("Position");
+    world.component_named::<Velocity>("Velocity");
+    world.component_named::<TagA>("Dead");
+    world
+}
+
+#[test]
+fn query_rust_try_query_from_expr() {
+    let world = query_expr_world();
+
+    let parent = world.entity().set(Velocity { x: 1, y: 2 });
+    world.entity().set(Position { x: 0, y: 0 }).child_of(parent);
+    world
+        .entity()
+        .set(Position { x: 0, y: 0 })
+        .child_of(parent)
+        .add(TagA::id());
+    world.entity().set(Position { x: 0, y: 0 });
+
+    let query = world
+        .try_query_from_expr("Position, [in] Velocity(up ChildOf), !Dead")
+        .unwrap();
+    assert_eq!(query.field_count(), 3);
+    assert_eq!(query.count(), 1);
+}
+
+#[test]
+fn query_rust_try_query_from_expr_unexpected_token() {
+    let world = query_expr_world();
+
+    let err = world
+        .try_query_from_expr("Position,, Velocity")
+        .err()
+        .unwrap();
+    assert_eq!(err.message(), "unexpected ','");
+    assert_eq!((err.line(), err.column()), (Some(1), Some(10)));
+    assert_eq!(err.expected(), Some("an identifier"));
+    assert_eq!(err.term(), None);
+
+    let err = world
+        .try_query_from_expr("Position Velocity")
+        .err()
+        .unwrap();
+    assert_eq!(err.message(), "unexpected identifier 'Velocity'");
+    assert_eq!(err.column(), Some(10));
+    assert_eq!(err.expected(), Some("',' or the end of the expression"));
+    assert_eq!(err.term(), Some("Position Velocity"));
+}
+
+#[test]
+fn query_rust_try_query_from_expr_unclosed() {
+    let world = query_expr_world();
+
+    let err = world
+        .try_query_from_expr("Position, [in Velocity")
+        .err()
+        .unwrap();
+    assert_eq!(err.message(), "unexpected keyword 'in'");
+    assert_eq!(err.column(), Some(12));
+    assert_eq!(err.expected(), Some("']'"));
+    assert_eq!(err.term(), Some("[in Velocity"));
+
+    let err = world
+        .try_query_from_expr("Position, Velocity(up ChildOf")
+        .err()
+        .unwrap();
+    assert_eq!(err.column(), Some(30));
+    assert_eq!(err.expected(), Some("')'"));
+    assert_eq!(err.term(), Some("Velocity(up ChildOf"));
+
+    let err = world.try_query_from_expr("Position, !").err().unwrap();
+    assert_eq!(err.message(), "unexpected end of script");
+    assert_eq!(err.column(), Some(12));
+    assert_eq!(err.expected(), Some("an identifier"));
+    assert_eq!(err.term(), Some("!"));
+}
+
+#[test]
+fn query_rust_try_query_from_expr_unresolved() {
+    let world = query_expr_world();
+
+    let err = world
+        .try_query_from_expr("Position, [in] Velocty(up ChildOf)")
+        .err()
+        .unwrap();
+    assert_eq!(err.message(), "unresolved identifier 'Velocty'");
+    assert_eq!(err.column(), Some(16));
+    assert_eq!(err.expected(), None);
+    assert_eq!(err.term(), Some("[in] Velocty(up ChildOf)"));
+
+    let err = world
+        .try_query_from_expr("Position, Velocity(up Parent)")
+        .err()
+        .unwrap();
+    assert_eq!(err.message(), "unresolved traversal relationship 'Parent'");
+    assert_eq!(err.column(), Some(23));
+    assert_eq!(err.term(), Some("Velocity(up Parent)"));
+}
+
+#[test]
+fn query_rust_try_query_from_expr_multiline() {
+    let world = query_expr_world();
+
+    let err = world
+        .try_query_from_expr("Position,\n  Velocity,\n  Dead)")
+        .err()
+        .unwrap();
+    assert_eq!(err.message(), "unexpected ')'");
+    assert_eq!((err.line(), err.column()), (Some(3), Some(7)));
+    assert_eq!(err.term(), Some("Dead)"));
+    assert_eq!(
+        err.to_string(),
+        "3:7: unexpected ')', expected ',' or the end of the expression"
+    );
+}
+
+#[test]
+fn query_rust_validate_query_expr() {
+    let world = query_expr_world();
+
+    assert_eq!(
+        world.validate_query_expr("Position, [in] Velocity(up ChildOf), !Dead"),
+        Ok(())
+    );
+    assert!(world.validate_query_expr("Position, Velocity(").is_err());
+
+    let err = world.validate_query_expr("Position\0").unwrap_err();
+    assert_eq!(err.message(), "unexpected nul byte");
+    assert_eq!(err.column(), Some(9));
+}
+
+#[test]
+fn query_rust_validate_query_expr_threads() {
+    let threads: Vec<_> = (0..4)
+        .map(|i| {
+            std::thread::spawn(move || {
+                let world = World::new();
+                let expr = if i % 2 == 0 { "ChildOf, (" } else { "Unknown" };
+                (0..50)
+                    .map(|_| {
+                        world
+                            .validate_query_expr(expr)
+                            .unwrap_err()
+                            .message()
+                            .to_owned()
+                    })
+                    .collect::<Vec<_>>()
+            })
+        })
+        .collect();
+
+    for (i, thread) in threads.into_iter().enumerate() {
+        let expected = if i % 2 == 0 {
+            "unexpected end of script"
+        } else {
+            "unresolved identifier 'Unknown'"
+        };
+        assert!(
+            thread
+                .join()
+                .unwrap()
+                .iter()
+                .all(|message| message == expected)
+        );
+    }
+}
+
+fn explain_world() -> World {
+    let world = World::new();
+    for _ in 0..3 {
+        let parent = world.entity().set(Position { x: 0, y: 0 });
+        for _ in 0..4 {
+            world.entity().child_of(parent).set(Velocity { x: 0, y: 0 });
+        }
+    }
+    world
+}
+
+#[test]
+fn query_rust_explain_up() {
+    let world = explain_world();
+
+    let query = world
+        .query::<&Position>()
+        .term_at(0)
+        .parent()
+        .with(Velocity::id())
+        .build();
+    let plan = query.explain();
+    let ops = plan.ops();
+    assert!(!plan.is_trivial());
+    assert_eq!(ops.last().unwrap().kind(), QueryOpKind::Yield);
+    assert!(
+        ops.iter()
+            .all(|op| op.profile().is_none() && !op.is_cached())
+    );
+
+    let up = ops.iter().find(|op| op.kind() == QueryOpKind::Up).unwrap();
+    assert_eq!(up.term_index(), Some(0));
+    assert_eq!(up.source().var(), Some("this"));
+    assert_eq!(up.first().entity(), Some(world.component_id::<Position>()));
+    assert_eq!(up.second(), &QueryOpRef::None);
+    let traversal = up.traversal().unwrap();
+    assert_eq!(traversal.relationship(), flecs::ChildOf::ID);
+    assert!(!traversal.includes_self() && !traversal.is_cascade());
+    assert_eq!(up.writes(), ["this"]);
+    assert!(up.reads().is_empty());
+
+    let and = &ops[up.index() + 1];
+    assert_eq!(and.kind(), QueryOpKind::And);
+    assert_eq!(and.term_index(), Some(1));
+    assert_eq!(and.traversal(), None);
+    assert!(and.writes().is_empty());
+    assert_eq!(and.reads(), ["this"]);
+}
+
+#[test]
+fn query_rust_explain_vars() {
+    let world = explain_world();
+
+    let query = world
+        .query::<()>()
+        .expr("(ChildOf, $parent), flecs.common_test.Position($parent)")
+        .build();
+    let plan = query.explain();
+
+    let child_of = plan
+        .ops()
+        .iter()
+        .find(|op| op.term_index() == Some(0))
+        .unwrap();
+    assert_eq!(child_of.first().entity(), Some(flecs::ChildOf::ID.into()));
+    assert_eq!(child_of.second().var(), Some("parent"));
+    assert_eq!(child_of.writes(), ["this", "parent"]);
+
+    let position = plan
+        .ops()
+        .iter()
+        .find(|op| op.term_index() == Some(1))
+        .unwrap();
+    assert_eq!(position.source().var(), Some("parent"));
+    assert!(position.writes().is_empty());
+    assert_eq!(position.reads(), ["parent"]);
+    assert!(
+        plan.to_string()
+            .contains("$parent (flecs.common_test.Position)")
+    );
+}
+
+#[test]
+fn query_rust_explain_cached() {
+    let world = explain_world();
+
+    let query = world
+        .query::<&Velocity>()
+        .with(flecs::ChildOf::ID)
+        .set_second("$parent")
+        .with(Position::id())
+        .set_src("$parent")
+        .set_cached()
+        .build();
+    let plan = query.explain();
+
+    let cache = plan.ops().iter().find(|op| op.is_cached()).unwrap();
+    assert_eq!(cache.kind(), QueryOpKind::Cache);
+    assert_eq!(cache.writes(), ["this"]);
+    let child_of = plan
+        .ops()
+        .iter()
+        .find(|op| op.term_index() == Some(1))
+        .unwrap();
+    assert!(!child_of.is_cached());
+    assert_eq!(child_of.reads(), ["this"]);
+
+    // queries of which all terms are cached don't have a plan
+    let query = world.query::<&Velocity>().set_cached().build();
+    assert!(query.explain().is_trivial());
+    let query = world.new_query::<&Position>();
+    assert!(query.explain().is_trivial());
+}
+
+#[test]
+#[cfg(debug_assertions)]
+fn query_rust_explain_profiled() {
+    let world = explain_world();
+
+    let query = world
+        .query::<&Position>()
+        .term_at(0)
+        .parent()
+        .with(Velocity::id())
+        .build();
+    let plan = query.explain_profiled();
+    assert!(plan.ops().iter().all(|op| op.profile().is_some()));
+
+    // the children of every parent are in a separate table
+    let up = plan
+        .ops()
+        .iter()
+        .find(|op| op.kind() == QueryOpKind::Up)
+        .unwrap();
+    assert_eq!(up.profile(), Some(QueryOpProfile { enter: 1, redo: 3 }));
+    assert!(plan.to_string().contains("     1 ->      3 <-  |"));
+
+    // profiling doesn't change the plan, or the results of the query
+    assert_eq!(query.explain().ops().len(), plan.ops().len());
+    assert_eq!(query.count(), 12);
+}
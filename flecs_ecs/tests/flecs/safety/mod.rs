@@ -1505,6 +1505,126 @@ mod observer_in_system {
     }
 }
 
+mod query_iterators {
+    use super::*;
+
+    #[test]
+    fn read_read_ok() {
+        let world = World::new();
+        world.entity().set(Foo(0));
+        let query0 = query!(world, &Foo).build();
+        let query1 = query!(world, &Foo).build();
+        for _chunk in query0.iter() {
+            query1.each(|_| {});
+            for _row in query1.iter_rows() {}
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn chunk_write_violation() {
+        let world = World::new();
+        world.entity().set(Foo(0));
+        let query0 = query!(world, &Foo).build();
+        let query1 = query!(world, &mut Foo).build();
+        for _chunk in query0.iter() {
+            query1.each(|_| {});
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn row_kept_write_violation() {
+        let world = World::new();
+        world.entity().set(Foo(0));
+        let query0 = query!(world, &mut Foo).build();
+        let query1 = query!(world, &Foo).build();
+        let rows = query0.iter_rows().collect::<Vec<_>>();
+        query1.each(|_| {});
+        drop(rows);
+    }
+
+    #[test]
+    #[should_panic]
+    fn par_each_write_violation() {
+        let world = World::new();
+        world.set_threads(2);
+        for i in 0..10 {
+            world.entity().set(Foo(i));
+        }
+        let query0 = query!(world, &Foo).build();
+        let query1 = query!(world, &mut Foo).build();
+        for _chunk in query0.iter() {
+            query1.par_each(|_| {});
+        }
+    }
+
+    #[test]
+    fn par_each_read_read_ok() {
+        let world = World::new();
+        world.set_threads(2);
+        for i in 0..10 {
+            world.entity().set(Foo(i));
+        }
+        let query0 = query!(world, &Foo).build();
+        let query1 = query!(world, &Foo).build();
+        for _chunk in query0.iter() {
+            query1.par_each(|_| {});
+        }
+        query1.par_each(|_| {});
+    }
+
+    #[test]
+    fn dropped_items_unlock() {
+        let world = World::new();
+        world.entity().set(Foo(0));
+        let query0 = query!(world, &mut Foo).build();
+        let query1 = query!(world, &mut Foo).build();
+        let rows = query0.iter_rows().collect::<Vec<_>>();
+        drop(rows);
+        if let Some(mut chunk) = query0.iter().next() {
+            chunk.columns()[0].0 = 1;
+        }
+        query1.each(|foo| assert_eq!(foo.0, 1));
+    }
+}
+
+mod table_columns {
+    use super::*;
+
+    #[test]
+    fn read_read() {
+        let world = World::new();
+        let entity = world.entity().set(Foo(0));
+        let table = entity.table().unwrap();
+        let _columns = table.columns_mut::<&Foo>().unwrap();
+        query!(world, &Foo).build().each(|_| {});
+    }
+
+    #[test]
+    #[should_panic]
+    fn write_read_violation() {
+        let world = World::new();
+        let entity = world.entity().set(Foo(0));
+        let table = entity.table().unwrap();
+        let _columns = table.columns_mut::<&mut Foo>().unwrap();
+        query!(world, &Foo).build().each(|_| {});
+    }
+
+    #[test]
+    fn dropped_columns_unlock() {
+        let world = World::new();
+        let entity = world.entity().set(Foo(0));
+        let table = entity.table().unwrap();
+        let mut columns = table.columns_mut::<&mut Foo>().unwrap();
+        columns.columns()[0].0 = 1;
+        drop(columns);
+        query!(world, &mut Foo)
+            .build()
+            .each(|foo| assert_eq!(foo.0, 1));
+    }
+}
+
 #[test]
 fn filter_does_not_panic() {
     let world = World::new();
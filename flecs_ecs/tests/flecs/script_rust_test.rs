@@ -0,0 +1,694 @@
+#![cfg(feature = "flecs_script")]
+#![allow(dead_code)]
+
+extern crate alloc;
+
+use alloc::sync::Arc;
+
+use flecs_ecs::addons::script::*;
+use flecs_ecs::prelude::*;
+
+#[derive(Component, Debug, Clone, PartialEq)]
+#[flecs(meta)]
+struct Size {
+    width: f32,
+    height: f32,
+}
+
+#[derive(Component)]
+struct Tracked(Arc<()>);
+
+#[test]
+fn script_vars_define_get() {
+    let world = World::new();
+
+    let mut vars = ScriptVars::new(&world);
+    vars.define("x", 10i32).define("y", 2.5f32);
+
+    assert_eq!(vars.get::<i32>("x"), Some(&10));
+    assert_eq!(vars.get::<f32>("y"), Some(&2.5));
+    assert_eq!(vars.get::<i32>("z"), None);
+
+    *vars.get_mut::<i32>("x").unwrap() = 20;
+    assert_eq!(vars.get::<i32>("x"), Some(&20));
+}
+
+#[test]
+#[should_panic(expected = "already defined")]
+fn script_vars_define_twice() {
+    let world = World::new();
+
+    let mut vars = ScriptVars::new(&world);
+    vars.define("x", 10i32).define("x", 20i32);
+}
+
+#[test]
+#[should_panic(expected = "variable 'x' is of type")]
+fn script_vars_get_wrong_type() {
+    let world = World::new();
+
+    let mut vars = ScriptVars::new(&world);
+    vars.define("x", 10i32);
+    vars.get::<f32>("x");
+}
+
+#[test]
+fn script_vars_scopes() {
+    let world = World::new();
+
+    let mut vars = ScriptVars::new(&world);
+    vars.define("x", 10i32).define("y", 1i32);
+
+    vars.push().define("x", 20i32);
+    assert_eq!(vars.get::<i32>("x"), Some(&20));
+    assert_eq!(vars.get::<i32>("y"), Some(&1));
+
+    vars.pop();
+    assert_eq!(vars.get::<i32>("x"), Some(&10));
+}
+
+#[test]
+#[should_panic(expected = "root scope")]
+fn script_vars_pop_root() {
+    let world = World::new();
+
+    ScriptVars::new(&world).pop();
+}
+
+#[test]
+fn script_vars_drop_values() {
+    let world = World::new();
+    let value = Arc::new(());
+
+    let mut vars = ScriptVars::new(&world);
+    vars.define("a", Tracked(value.clone()));
+    vars.push().define("b", Tracked(value.clone()));
+    assert_eq!(Arc::strong_count(&value), 3);
+
+    vars.pop();
+    assert_eq!(Arc::strong_count(&value), 2);
+
+    vars.push().define("b", Tracked(value.clone()));
+    drop(vars);
+    assert_eq!(Arc::strong_count(&value), 1);
+}
+
+#[test]
+fn script_eval_with_vars() {
+    let world = World::new();
+    world.component_named::<Size>("Size");
+
+    let script = Script::parse(
+        &world,
+        "test",
+        "e { Size: {width: $w, height: $h * 2} }",
+        None,
+    )
+    .unwrap();
+
+    let mut vars = ScriptVars::new(&world);
+    vars.define("w", 10.0f32).define("h", 5.0f32);
+    script.eval_with_vars(&mut vars).unwrap();
+
+    let e = world.lookup("e");
+    e.get::<&Size>(|size| {
+        assert_eq!(
+            *size,
+            Size {
+                width: 10.0,
+                height: 10.0
+            }
+        );
+    });
+
+    *vars.get_mut::<f32>("w").unwrap() = 3.0;
+    script.eval_with_vars(&mut vars).unwrap();
+    e.get::<&Size>(|size| {
+        assert_eq!(
+            *size,
+            Size {
+                width: 3.0,
+                height: 10.0
+            }
+        );
+    });
+}
+
+#[test]
+fn script_eval_missing_var() {
+    let world = World::new();
+    world.component_named::<Size>("Size");
+
+    let script = Script::parse(&world, "test", "e { Size: {width: $w} }", None).unwrap();
+
+    let mut vars = ScriptVars::new(&world);
+    let err = script.eval_with_vars(&mut vars).unwrap_err();
+    assert_eq!(err.file(), Some("test"));
+    assert_eq!(err.message(), "unresolved variable 'w'");
+}
+
+#[test]
+fn script_eval_template_with_vars() {
+    let world = World::new();
+    world.component_named::<Size>("Size");
+
+    world
+        .run_code(
+            "templates",
+            "template Box { prop size = f32: 1\n Size: {width: size, height: size} }",
+        )
+        .unwrap();
+
+    let script = Script::parse(&world, "instance", "b { Box: {size: $size} }", None).unwrap();
+
+    let mut vars = ScriptVars::new(&world);
+    vars.define("size", 4.0f32);
+    script.eval_with_vars(&mut vars).unwrap();
+
+    world.lookup("b").get::<&Size>(|size| {
+        assert_eq!(
+            *size,
+            Size {
+                width: 4.0,
+                height: 4.0
+            }
+        );
+    });
+}
+
+#[test]
+fn script_update_with_vars() {
+    let world = World::new();
+    world.component_named::<Size>("Size");
+
+    let script = world.script_named("main").build_from_code("").unwrap();
+
+    let mut vars = ScriptVars::new(&world);
+    vars.define("w", 1.0f32);
+    script
+        .update_with_vars(
+            &world,
+            None::<Entity>,
+            "e { Size: {width: $w, height: 0} }",
+            &mut vars,
+        )
+        .unwrap();
+    world.lookup("e").get::<&Size>(|size| {
+        assert_eq!(
+            *size,
+            Size {
+                width: 1.0,
+                height: 0.0
+            }
+        );
+    });
+
+    *vars.get_mut::<f32>("w").unwrap() = 2.0;
+    script
+        .update_with_vars(
+            &world,
+            None::<Entity>,
+            "f { Size: {width: $w, height: 0} }",
+            &mut vars,
+        )
+        .unwrap();
+    assert!(world.try_lookup("e").is_none());
+    world.lookup("f").get::<&Size>(|size| {
+        assert_eq!(
+            *size,
+            Size {
+                width: 2.0,
+                height: 0.0
+            }
+        );
+    });
+
+    let err = script
+        .update_with_vars(&world, None::<Entity>, "g { Size: {width: $x} }", &mut vars)
+        .unwrap_err();
+    assert_eq!(err.message(), "unresolved variable 'x'");
+    assert_eq!(script.error(), Some(err));
+}
+
+#[test]
+fn expr_eval() {
+    let world = World::new();
+    world.component_named::<Size>("Size");
+    world.entity_named("e").set(Size {
+        width: 10.0,
+        height: 5.0,
+    });
+
+    let mut vars = ScriptVars::new(&world);
+    vars.define("scale", 2i32);
+
+    assert_eq!(world.eval_expr::<i32>("1 + 2 * $scale", &vars), Ok(5));
+    assert_eq!(
+        world.eval_expr::<i64>("e[Size].width * $scale + 5", &vars),
+        Ok(25)
+    );
+}
+
+#[test]
+fn expr_eval_struct() {
+    let world = World::new();
+    world.component_named::<Size>("Size");
+
+    let vars = ScriptVars::new(&world);
+    let mut size = Size {
+        width: 0.0,
+        height: 0.0,
+    };
+    let expr = Expr::parse_as::<Size>(&world, "{width: 1, height: 2 + 3}", &vars).unwrap();
+    expr.eval_into(&mut size, &vars).unwrap();
+    assert_eq!(
+        size,
+        Size {
+            width: 1.0,
+            height: 5.0
+        }
+    );
+}
+
+#[test]
+fn expr_reuse() {
+    let world = World::new();
+
+    let mut vars = ScriptVars::new(&world);
+    vars.define("x", 1i32);
+
+    let expr = Expr::parse(&world, "$x * $x", &vars).unwrap();
+    for x in 1..4 {
+        *vars.get_mut::<i32>("x").unwrap() = x;
+        assert_eq!(expr.eval::<i32>(&vars), Ok(x * x));
+    }
+}
+
+#[test]
+fn expr_parse_error() {
+    let world = World::new();
+    let vars = ScriptVars::new(&world);
+
+    let err = world.eval_expr::<i32>("1 +\n(2 * )", &vars).unwrap_err();
+    assert_eq!(err.kind(), ExprErrorKind::Parse);
+    assert_eq!(err.message(), "unexpected ')'");
+    assert_eq!((err.line(), err.column()), (Some(2), Some(6)));
+}
+
+#[test]
+fn expr_unresolved_variable() {
+    let world = World::new();
+    let vars = ScriptVars::new(&world);
+
+    let err = Expr::parse(&world, "10 + $speed", &vars).err().unwrap();
+    assert_eq!(err.kind(), ExprErrorKind::Parse);
+    assert_eq!(err.message(), "unresolved variable 'speed'");
+    assert_eq!((err.line(), err.column()), (Some(1), Some(6)));
+    assert_eq!(err.to_string(), "1:6: unresolved variable 'speed'");
+}
+
+#[test]
+fn expr_error_repeated_token() {
+    let world = World::new();
+    world.component_named::<Size>("Size");
+
+    let mut vars = ScriptVars::new(&world);
+    vars.define(
+        "a",
+        Size {
+            width: 1.0,
+            height: 2.0,
+        },
+    );
+    vars.define("b", 1i32);
+
+    let err = Expr::parse(&world, "$a.width + $b.width", &vars)
+        .err()
+        .unwrap();
+    assert_eq!(err.kind(), ExprErrorKind::Parse);
+    assert_eq!(
+        err.message(),
+        "cannot resolve member on non-struct type 'flecs.meta.i32'"
+    );
+    // the second `width`, not the first
+    assert_eq!((err.line(), err.column()), (Some(1), Some(15)));
+}
+
+#[test]
+fn error_column_counts_characters() {
+    let world = World::new();
+    let vars = ScriptVars::new(&world);
+
+    let err = world.eval_expr::<i32>("\"é\" + )", &vars).unwrap_err();
+    assert_eq!((err.line(), err.column()), (Some(1), Some(7)));
+
+    let err = world.run_code("", "const x = \"é\" + )").unwrap_err();
+    assert_eq!((err.line(), err.column()), (Some(1), Some(17)));
+}
+
+#[test]
+fn script_error_parse() {
+    let world = World::new();
+
+    let err = world
+        .run_code("level.flecs", "e {\n  Position: {x: 10\n}\nf { ) }")
+        .unwrap_err();
+    assert_eq!(err.file(), Some("level.flecs"));
+    assert_eq!(err.line(), Some(4));
+    assert_eq!(err.column(), Some(5));
+    assert_eq!(err.snippet(), Some("f { ) }"));
+    assert_eq!(err.message(), "unexpected ')'");
+    assert_eq!(err.to_string(), "level.flecs:4:5: unexpected ')'");
+}
+
+#[test]
+fn script_error_eval() {
+    let world = World::new();
+
+    let script = Script::parse(&world, "main", "e {}\nf { Foo }", None).unwrap();
+    let err = script.eval(None).unwrap_err();
+    assert_eq!(err.file(), Some("main"));
+    assert_eq!(err.line(), Some(2));
+    assert_eq!(err.message(), "unresolved identifier 'Foo'");
+}
+
+#[test]
+fn script_error_missing_file() {
+    let world = World::new();
+
+    let err = world.run_file("does_not_exist.flecs").unwrap_err();
+    assert_eq!(err.file(), Some("does_not_exist.flecs"));
+    assert_eq!(err.line(), None);
+}
+
+#[test]
+fn script_error_build_from_code() {
+    let world = World::new();
+
+    let err = world
+        .script_named("broken")
+        .build_from_code("e {}\nf { Foo }")
+        .unwrap_err();
+    assert_eq!(err.file(), Some("broken"));
+    assert_eq!(err.line(), Some(2));
+    assert!(world.try_lookup("f").is_none());
+
+    // the script entity is kept, so the script can be fixed with an update
+    let script = world.script_entity_from(world.lookup("broken"));
+    assert_eq!(script.error(), Some(err));
+    script.update(&world, None::<Entity>, "f {}").unwrap();
+    assert_eq!(script.error(), None);
+    assert!(world.try_lookup("f").is_some());
+}
+
+#[test]
+fn script_error_build_deletes_entity() {
+    let world = World::new();
+    let count = world.count(id::<flecs::Script>());
+
+    assert!(world.script().build_from_code("e { ) }").is_err());
+    assert_eq!(world.count(id::<flecs::Script>()), count);
+}
+
+mod watcher {
+    extern crate std;
+
+    use core::time::Duration;
+    use std::fs;
+    use std::path::{Path, PathBuf};
+    use std::time::SystemTime;
+
+    use super::*;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("flecs_watch_{}_{name}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    // file systems can have a coarse modification time, so set it explicitly
+    fn write(path: &Path, code: &str, version: u64) {
+        fs::write(path, code).unwrap();
+        fs::File::options()
+            .write(true)
+            .open(path)
+            .unwrap()
+            .set_modified(SystemTime::UNIX_EPOCH + Duration::from_secs(1_000_000 + version))
+            .unwrap();
+    }
+
+    fn size(world: &World, name: &str) -> Size {
+        world.lookup(name).get::<&Size>(Size::clone)
+    }
+
+    #[test]
+    fn script_watcher_reload() {
+        let world = World::new();
+        world.component_named::<Size>("Size");
+        let dir = temp_dir("reload");
+        let file = dir.join("level.flecs");
+
+        write(
+            &file,
+            "prefab Box { Size: {1, 1} }\ne { Size: {1, 2} }\n_ {}",
+            1,
+        );
+        let mut watcher = ScriptWatcher::new(&world);
+        watcher.watch(&file);
+        assert_eq!(watcher.poll(), [ScriptWatchEvent::Loaded(file.clone())]);
+        assert!(watcher.poll().is_empty());
+
+        let e = world.lookup("e");
+        let prefab = world.lookup("Box");
+        let instance = world.entity().is_a(prefab);
+        let script = watcher.script(&file).unwrap();
+        let tag = (id::<flecs::Script>(), script.id());
+        assert_eq!(world.count(tag), 3);
+
+        write(
+            &file,
+            "prefab Box { Size: {5, 5} }\ne { Size: {3, 4} }\n_ {}",
+            2,
+        );
+        assert_eq!(watcher.poll(), [ScriptWatchEvent::Reloaded(file.clone())]);
+
+        assert_eq!(world.lookup("e"), e);
+        assert_eq!(world.lookup("Box"), prefab);
+        assert_eq!(
+            size(&world, "e"),
+            Size {
+                width: 3.0,
+                height: 4.0
+            }
+        );
+        assert!(instance.has((flecs::IsA::ID, prefab)));
+        assert_eq!(world.count(tag), 3);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn script_watcher_reload_template() {
+        let world = World::new();
+        world.component_named::<Size>("Size");
+        let dir = temp_dir("template");
+        let file = dir.join("templates.flecs");
+
+        let code = "template Square {\n  prop size = f32: 1\n  Size: {size, size}\n}\n";
+        write(&file, &format!("{code}s {{ Square: {{size: 2}} }}"), 1);
+        let mut watcher = ScriptWatcher::new(&world);
+        watcher.watch(&file);
+        watcher.poll();
+
+        write(&file, &format!("{code}s {{ Square: {{size: 3}} }}"), 2);
+        assert_eq!(watcher.poll(), [ScriptWatchEvent::Reloaded(file.clone())]);
+        assert_eq!(
+            size(&world, "s"),
+            Size {
+                width: 3.0,
+                height: 3.0
+            }
+        );
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn script_watcher_error_keeps_content() {
+        let world = World::new();
+        world.component_named::<Size>("Size");
+        let dir = temp_dir("error");
+        let file = dir.join("level.flecs");
+
+        write(&file, "e { Size: {1, 2} }", 1);
+        let mut watcher = ScriptWatcher::new(&world);
+        watcher.watch(&dir);
+        watcher.poll();
+
+        write(&file, "e { Size: {3, 4} }\nf { ) }", 2);
+        let events = watcher.poll();
+        let [ScriptWatchEvent::Failed(path, err)] = events.as_slice() else {
+            panic!("expected a failed reload, got {events:?}");
+        };
+        assert_eq!(path, &file);
+        assert_eq!(err.line(), Some(2));
+        assert_eq!(watcher.errors().count(), 1);
+        assert_eq!(
+            size(&world, "e"),
+            Size {
+                width: 1.0,
+                height: 2.0
+            }
+        );
+
+        write(&file, "e { Size: {3, 4} }\nf { Foo }", 3);
+        assert!(matches!(
+            watcher.poll().as_slice(),
+            [ScriptWatchEvent::Failed(_, _)]
+        ));
+        assert!(world.try_lookup("e").is_some());
+        assert!(watcher.script(&file).unwrap().error().is_some());
+
+        write(&file, "e { Size: {3, 4} }", 4);
+        assert_eq!(watcher.poll(), [ScriptWatchEvent::Reloaded(file.clone())]);
+        assert_eq!(watcher.errors().count(), 0);
+        assert!(watcher.script(&file).unwrap().error().is_none());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn script_watcher_eval_error_keeps_anonymous() {
+        let world = World::new();
+        world.component_named::<Size>("Size");
+        let dir = temp_dir("eval_error");
+        let file = dir.join("level.flecs");
+
+        write(&file, "_ { Size: {1, 2} }", 1);
+        let mut watcher = ScriptWatcher::new(&world);
+        watcher.watch(&file);
+        watcher.poll();
+        let tag = (id::<flecs::Script>(), watcher.script(&file).unwrap().id());
+        let anonymous = world.query::<()>().with(tag).build().first_entity();
+
+        write(&file, "_ { Size: {3, 4} }\n_ { Foo }", 2);
+        assert!(matches!(
+            watcher.poll().as_slice(),
+            [ScriptWatchEvent::Failed(_, _)]
+        ));
+        assert_eq!(world.count(tag), 1);
+        assert!(anonymous.is_alive());
+        assert_eq!(
+            anonymous.get::<&Size>(Size::clone),
+            Size {
+                width: 1.0,
+                height: 2.0
+            }
+        );
+
+        write(&file, "_ { Size: {3, 4} }", 3);
+        assert_eq!(watcher.poll(), [ScriptWatchEvent::Reloaded(file.clone())]);
+        assert_eq!(world.count(tag), 1);
+        assert!(!anonymous.is_alive());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn script_watcher_unload_failed() {
+        let world = World::new();
+        let dir = temp_dir("unload_failed");
+        let file = dir.join("broken.flecs");
+
+        write(&file, "e { ) }", 1);
+        let mut watcher = ScriptWatcher::new(&world);
+        watcher.watch(&dir);
+        assert!(matches!(
+            watcher.poll().as_slice(),
+            [ScriptWatchEvent::Failed(_, _)]
+        ));
+
+        fs::remove_file(&file).unwrap();
+        assert_eq!(watcher.poll(), [ScriptWatchEvent::Unloaded(file.clone())]);
+        assert_eq!(watcher.errors().count(), 0);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn script_watcher_deferred() {
+        let world = World::new();
+        world.component_named::<Size>("Size");
+        let dir = temp_dir("deferred");
+        let file = dir.join("deferred.flecs");
+
+        write(&file, "e { Size: {1, 1} }", 1);
+        let mut watcher = ScriptWatcher::new(&world);
+        watcher.watch(&file);
+        watcher.poll();
+
+        write(&file, "e { Size: {2, 2} }", 2);
+        world.defer_begin();
+        assert!(watcher.poll().is_empty());
+        world.defer_end();
+        assert_eq!(watcher.poll(), [ScriptWatchEvent::Reloaded(file.clone())]);
+        assert_eq!(
+            size(&world, "e"),
+            Size {
+                width: 2.0,
+                height: 2.0
+            }
+        );
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn script_watcher_unreadable_metadata() {
+        let world = World::new();
+        let dir = temp_dir("metadata");
+        let file = dir.join("dangling.flecs");
+        std::os::unix::fs::symlink(dir.join("missing.flecs"), &file).unwrap();
+
+        let mut watcher = ScriptWatcher::new(&world);
+        watcher.watch(&dir);
+        assert!(matches!(
+            watcher.poll().as_slice(),
+            [ScriptWatchEvent::Failed(_, _)]
+        ));
+        assert!(watcher.poll().is_empty());
+        assert_eq!(watcher.errors().count(), 1);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn script_watcher_directory() {
+        let world = World::new();
+        world.component_named::<Size>("Size");
+        let dir = temp_dir("directory");
+        let a = dir.join("a.flecs");
+        let b = dir.join("nested").join("b.flecs");
+        fs::create_dir_all(b.parent().unwrap()).unwrap();
+
+        write(&a, "a {}", 1);
+        write(&dir.join("notes.txt"), "not a script", 1);
+        let mut watcher = ScriptWatcher::new(&world);
+        watcher.watch(&dir);
+        assert_eq!(watcher.poll(), [ScriptWatchEvent::Loaded(a.clone())]);
+
+        write(&b, "b {}", 1);
+        assert_eq!(watcher.poll(), [ScriptWatchEvent::Loaded(b.clone())]);
+        assert!(world.try_lookup("b").is_some());
+
+        fs::remove_file(&b).unwrap();
+        assert_eq!(watcher.poll(), [ScriptWatchEvent::Unloaded(b.clone())]);
+        assert!(world.try_lookup("b").is_none());
+        assert!(watcher.script(&b).is_none());
+        assert!(world.try_lookup("a").is_some());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,263 @@
+#![allow(dead_code)]
+
+extern crate alloc;
+
+use alloc::sync::Arc;
+
+use flecs_ecs::prelude::*;
+
+#[derive(Component, Clone, Debug, PartialEq)]
+struct Position {
+    x: f32,
+    y: f32,
+}
+
+#[derive(Component, Clone, Debug, PartialEq)]
+struct Label(String);
+
+#[derive(Component)]
+struct Tag;
+
+#[derive(Component)]
+struct Likes;
+
+#[derive(Component, Clone)]
+struct Tracked(Arc<()>);
+
+#[derive(Component)]
+struct NotClone(u32);
+
+#[test]
+fn snapshot_restore_values() {
+    let world = World::new();
+
+    let e = world
+        .entity()
+        .set(Position { x: 1.0, y: 2.0 })
+        .set(Label("before".to_string()));
+    let snapshot = world.snapshot();
+
+    e.set(Position { x: 10.0, y: 20.0 })
+        .set(Label("after".to_string()));
+
+    world.restore(&snapshot);
+
+    e.get::<(&Position, &Label)>(|(pos, label)| {
+        assert_eq!(*pos, Position { x: 1.0, y: 2.0 });
+        assert_eq!(label.0, "before");
+    });
+}
+
+#[test]
+fn snapshot_restore_deletes_new_entities() {
+    let world = World::new();
+
+    let e = world.entity().add(Tag::id());
+    let snapshot = world.snapshot();
+
+    let created = world.entity().add(Tag::id());
+    let empty = world.entity();
+
+    world.restore(&snapshot);
+
+    assert!(world.is_alive(e));
+    assert!(!world.is_alive(created));
+    assert!(!world.is_alive(empty));
+    assert_eq!(world.count(Tag::id()), 1);
+}
+
+#[test]
+fn snapshot_restore_deleted_entity_with_same_id() {
+    let world = World::new();
+
+    let e = world.entity().set(Position { x: 1.0, y: 2.0 });
+    let id = e.id();
+    let snapshot = world.snapshot();
+
+    e.destruct();
+    // recycles the index of the deleted entity with a new generation
+    let recycled = world.entity().id();
+    assert_eq!(*recycled as u32, *id as u32);
+    assert_ne!(recycled, id);
+
+    world.restore(&snapshot);
+
+    assert!(world.is_alive(id));
+    assert!(!world.is_alive(recycled));
+    world.entity_from_id(id).get::<&Position>(|pos| {
+        assert_eq!(*pos, Position { x: 1.0, y: 2.0 });
+    });
+}
+
+#[test]
+fn snapshot_restore_names_and_hierarchy() {
+    let world = World::new();
+
+    let parent = world.entity_named("parent");
+    let child = world.entity_named("child").child_of(parent);
+    let snapshot = world.snapshot();
+
+    parent.destruct();
+    assert!(!world.is_alive(child));
+
+    world.restore(&snapshot);
+
+    assert!(world.is_alive(parent));
+    assert!(world.is_alive(child));
+    assert_eq!(world.lookup("parent::child"), child);
+    assert_eq!(child.parent().unwrap(), parent);
+}
+
+#[test]
+fn snapshot_restore_swapped_names() {
+    let world = World::new();
+
+    let a = world.entity_named("a");
+    let b = world.entity_named("b");
+    let snapshot = world.snapshot();
+
+    a.set_name("tmp");
+    b.set_name("a");
+    a.set_name("b");
+
+    world.restore(&snapshot);
+
+    assert_eq!(world.lookup("a"), a);
+    assert_eq!(world.lookup("b"), b);
+    assert!(world.try_lookup("tmp").is_none());
+}
+
+#[test]
+fn snapshot_restore_type() {
+    let world = World::new();
+
+    let bob = world.entity_named("bob");
+    let e = world
+        .entity()
+        .add(Tag::id())
+        .add((Likes::id(), bob))
+        .set(Position { x: 1.0, y: 2.0 });
+    let snapshot = world.snapshot();
+
+    e.remove(Tag::id())
+        .remove((Likes::id(), bob))
+        .remove(Position::id())
+        .set(Label("added".to_string()));
+    bob.destruct();
+
+    world.restore(&snapshot);
+
+    assert!(e.has(Tag::id()));
+    assert!(e.has((Likes::id(), bob)));
+    assert!(e.has(Position::id()));
+    assert!(!e.has(Label::id()));
+    e.get::<&Position>(|pos| {
+        assert_eq!(*pos, Position { x: 1.0, y: 2.0 });
+    });
+}
+
+#[test]
+fn snapshot_restore_multiple_times() {
+    let world = World::new();
+
+    let e = world.entity().set(Position { x: 0.0, y: 0.0 });
+    let snapshot = world.snapshot();
+
+    for i in 1..4 {
+        e.set(Position {
+            x: i as f32,
+            y: i as f32,
+        });
+        world.entity().set(Position { x: 0.0, y: 0.0 });
+
+        world.restore(&snapshot);
+
+        assert_eq!(world.count(Position::id()), 1);
+        e.get::<&Position>(|pos| {
+            assert_eq!(*pos, Position { x: 0.0, y: 0.0 });
+        });
+    }
+}
+
+#[test]
+fn snapshot_drop_releases_values() {
+    let world = World::new();
+    let counter = Arc::new(());
+
+    world.entity().set(Tracked(counter.clone()));
+    world.entity().set(Tracked(counter.clone()));
+    assert_eq!(Arc::strong_count(&counter), 3);
+
+    let snapshot = world.snapshot();
+    assert_eq!(snapshot.entity_count(), 2);
+    assert_eq!(Arc::strong_count(&counter), 5);
+
+    drop(snapshot);
+    assert_eq!(Arc::strong_count(&counter), 3);
+}
+
+#[test]
+fn snapshot_keeps_queries_and_components() {
+    let world = World::new();
+
+    world.entity().set(Position { x: 0.0, y: 0.0 });
+    let snapshot = world.snapshot();
+
+    let query = world.query::<&Position>().set_cached().build();
+    world.component::<Label>();
+    world.entity().set(Position { x: 1.0, y: 1.0 });
+
+    world.restore(&snapshot);
+
+    assert!(world.is_alive(query.entity()));
+    assert!(world.is_alive(world.component_id::<Label>()));
+    assert_eq!(query.count(), 1);
+}
+
+#[test]
+fn snapshot_contains() {
+    let world = World::new();
+
+    let e = world.entity();
+    let snapshot = world.snapshot();
+    let created = world.entity();
+
+    assert!(snapshot.contains(e));
+    assert!(!snapshot.contains(created));
+    assert!(!snapshot.contains(world.component_id::<Position>()));
+}
+
+#[test]
+fn snapshot_skips_not_clone() {
+    let world = World::new();
+
+    let e = world
+        .entity()
+        .set(NotClone(1))
+        .set(Position { x: 1.0, y: 1.0 });
+    let snapshot = world.snapshot();
+    assert!(snapshot.contains(e));
+
+    e.set(NotClone(2)).set(Position { x: 2.0, y: 2.0 });
+    world.restore(&snapshot);
+
+    e.get::<(&NotClone, &Position)>(|(not_clone, pos)| {
+        assert_eq!(not_clone.0, 2);
+        assert_eq!(*pos, Position { x: 1.0, y: 1.0 });
+    });
+}
+
+#[test]
+fn snapshot_keeps_world_alive() {
+    let world = World::new();
+
+    let counter = Arc::new(());
+    world.entity().set(Tracked(counter.clone()));
+    let snapshot = world.snapshot();
+
+    drop(world);
+    assert_eq!(Arc::strong_count(&counter), 3);
+
+    drop(snapshot);
+    assert_eq!(Arc::strong_count(&counter), 1);
+}
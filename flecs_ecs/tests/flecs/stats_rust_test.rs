@@ -0,0 +1,90 @@
+#![allow(clippy::float_cmp)]
+#![cfg(feature = "flecs_stats")]
+
+use crate::common_test::*;
+
+#[test]
+fn stats_world_counts() {
+    let world = World::new();
+    assert!(world.stats().is_none());
+    world.import::<stats::Stats>();
+    let empty = world.stats().unwrap();
+    assert_eq!(empty.entity_count().latest(), 0.0);
+
+    for _ in 0..10 {
+        world.entity().set(Position { x: 1, y: 2 });
+    }
+    world.progress();
+
+    let stats = world.stats().unwrap();
+    let entities = stats.entity_count();
+    assert!(entities.latest() >= 10.0);
+    assert!(entities.max() >= entities.latest());
+    assert!(entities.min() <= entities.average());
+    assert!(stats.table_count().latest() > 0.0);
+    assert!(stats.system_count().latest() > 0.0);
+}
+
+#[test]
+fn stats_world_window() {
+    let world = World::new();
+    world.import::<stats::Stats>();
+
+    world.progress_time(1.0 / 60.0);
+    let before = world.stats().unwrap().entity_count().latest();
+
+    for _ in 0..5 {
+        world.entity();
+    }
+    world.progress_time(1.0 / 60.0);
+
+    let entities = world.stats().unwrap().entity_count();
+    assert_eq!(entities.latest(), before + 5.0);
+    assert_eq!(entities.max(), before + 5.0);
+    assert!(entities.average() < entities.latest());
+}
+
+#[test]
+fn stats_system() {
+    let world = World::new();
+    world.import::<stats::Stats>();
+    world.entity().set(Position { x: 1, y: 2 });
+    world.entity().set(Position { x: 3, y: 4 });
+    world
+        .entity()
+        .set(Position { x: 5, y: 6 })
+        .set(Velocity { x: 1, y: 1 });
+
+    let system = world.system::<&mut Position>().each(|pos| pos.x += 1);
+    assert!(system.stats().is_none());
+
+    world.progress();
+
+    let stats = system.stats().unwrap();
+    assert_eq!(stats.matched_entity_count().latest(), 3.0);
+    assert_eq!(stats.matched_table_count().latest(), 2.0);
+    assert!(stats.time_spent().latest() >= 0.0);
+    assert!(!stats.is_task());
+}
+
+#[test]
+fn stats_pipeline() {
+    let world = World::new();
+    assert!(world.pipeline_stats().is_none());
+    world.import::<stats::Stats>();
+    world
+        .entity()
+        .set(Position { x: 1, y: 2 })
+        .set(Velocity { x: 1, y: 1 });
+
+    let a = world.system::<&Position>().each(|_| {});
+    let b = world.system::<&Velocity>().each(|_| {});
+    world.progress();
+
+    let stats = world.pipeline_stats().unwrap();
+    let systems = stats.systems();
+    let a_index = systems.iter().position(|&e| e == a.id()).unwrap();
+    let b_index = systems.iter().position(|&e| e == b.id()).unwrap();
+    assert!(a_index < b_index);
+    assert!(stats.sync_point_count() > 0);
+}
@@ -0,0 +1,182 @@
+#![allow(dead_code)]
+
+use flecs_ecs::core::*;
+
+use crate::common_test::*;
+
+#[test]
+fn table_columns_mut() {
+    let world = World::new();
+
+    for i in 0..10 {
+        world
+            .entity()
+            .set(Position { x: i, y: 0 })
+            .set(Velocity { x: 1, y: i });
+    }
+    let e = world
+        .entity()
+        .set(Position { x: 10, y: 0 })
+        .set(Velocity { x: 1, y: 10 });
+
+    let table = e.table().unwrap();
+    let mut columns = table.columns_mut::<(&mut Position, &Velocity)>().unwrap();
+    assert_eq!(columns.count(), 11);
+    let (positions, velocities) = columns.columns();
+    assert_eq!(positions.len(), 11);
+    assert_eq!(velocities.len(), 11);
+    for (pos, vel) in positions.iter_mut().zip(velocities) {
+        pos.x += vel.x;
+        pos.y += vel.y;
+    }
+    drop(columns);
+
+    let mut columns = table.columns_mut::<&Position>().unwrap();
+    let positions = columns.columns();
+    for (i, pos) in positions.iter().enumerate() {
+        assert_eq!(pos.x, i as i32 + 1);
+        assert_eq!(pos.y, i as i32);
+    }
+}
+
+#[test]
+fn table_columns_mut_optional() {
+    let world = World::new();
+
+    let e = world.entity().set(Position { x: 1, y: 2 });
+    let table = e.table().unwrap();
+
+    let mut columns = table
+        .columns_mut::<(&Position, Option<&mut Velocity>)>()
+        .unwrap();
+    let (positions, velocities) = columns.columns();
+    assert_eq!(positions.len(), 1);
+    assert!(velocities.is_none());
+    drop(columns);
+
+    assert!(table.columns_mut::<(&Position, &Velocity)>().is_none());
+    // tags don't have a column
+    e.add(TagA::id());
+    let table = e.table().unwrap();
+    assert!(table.columns_mut::<(&Position, &TagA)>().is_none());
+}
+
+#[test]
+fn table_columns_mut_range() {
+    let world = World::new();
+
+    let e = world.entity().set(Position { x: 0, y: 0 });
+    for i in 1..5 {
+        world.entity().set(Position { x: i, y: 0 });
+    }
+
+    let range = TableRange::new(e.table().unwrap(), 1, 3);
+    let mut columns = range.columns_mut::<&mut Position>().unwrap();
+    let positions = columns.columns();
+    assert_eq!(positions.iter().map(|p| p.x).collect::<Vec<_>>(), [1, 2, 3]);
+}
+
+#[test]
+fn table_columns_mut_unlocks_table() {
+    let world = World::new();
+
+    let e = world.entity().set(Position { x: 0, y: 0 });
+    let table = e.table().unwrap();
+    let columns = table.columns_mut::<&mut Position>().unwrap();
+    drop(columns);
+
+    // the table can be changed again once the columns are dropped
+    world.entity().set(Position { x: 1, y: 0 });
+    assert_eq!(table.count(), 2);
+}
+
+#[test]
+#[should_panic]
+fn table_columns_mut_aliasing() {
+    let world = World::new();
+
+    let e = world.entity().set(Position { x: 0, y: 0 });
+    let table = e.table().unwrap();
+    let _ = table.columns_mut::<(&mut Position, &Position)>();
+}
+
+#[test]
+fn table_columns_mut_shared_read() {
+    let world = World::new();
+
+    let e = world.entity().set(Position { x: 0, y: 0 });
+    let table = e.table().unwrap();
+    let mut columns = table.columns_mut::<(&Position, &Position)>().unwrap();
+    let (a, b) = columns.columns();
+    assert_eq!(a.len(), b.len());
+}
+
+#[test]
+fn table_columns_zip() {
+    let world = World::new();
+
+    for i in 0..100 {
+        world
+            .entity()
+            .set(Position { x: 0, y: 0 })
+            .set(Velocity { x: i, y: 1 })
+            .set(Mass { value: 2 });
+    }
+    let e = world
+        .entity()
+        .set(Position { x: 0, y: 0 })
+        .set(Velocity { x: 100, y: 1 });
+    let e = e.set(Mass { value: 2 });
+
+    let table = e.table().unwrap();
+    table
+        .columns_mut::<(&mut Position, &Velocity, &Mass)>()
+        .unwrap()
+        .columns()
+        .zip_for_each(|(pos, vel, mass)| {
+            pos.x += vel.x * mass.value;
+            pos.y += vel.y * mass.value;
+        });
+
+    let mut columns = table.columns_mut::<(&Position, &Velocity)>().unwrap();
+    let zipped = columns.columns().zip_iter();
+    assert_eq!(zipped.len(), 101);
+    for (pos, vel) in zipped {
+        assert_eq!(pos.x, vel.x * 2);
+        assert_eq!(pos.y, 2);
+    }
+
+    let sum = table.columns_mut::<&Position>().map(|mut columns| {
+        (columns.columns(),)
+            .zip_iter()
+            .map(|(pos,)| pos.y)
+            .sum::<i32>()
+    });
+    assert_eq!(sum, Some(202));
+}
+
+#[test]
+fn table_columns_zip_chunks() {
+    let world = World::new();
+
+    for i in 0..10 {
+        world
+            .entity()
+            .set(Position { x: 0, y: 0 })
+            .set(Velocity { x: i, y: 0 });
+    }
+
+    let query = world.new_query::<(&mut Position, &Velocity)>();
+    for mut chunk in query.iter() {
+        chunk.columns().zip_for_each(|(pos, vel)| pos.x += vel.x);
+    }
+    query.each(|(pos, vel)| assert_eq!(pos.x, vel.x));
+}
+
+#[test]
+#[should_panic]
+fn table_columns_zip_different_lengths() {
+    let a = [1, 2, 3];
+    let mut b = [0; 2];
+    (&a[..], &mut b[..]).zip_for_each(|(a, b)| *b = *a);
+}
@@ -0,0 +1,174 @@
+#![cfg(feature = "flecs_timer")]
+
+extern crate alloc;
+use alloc::rc::Rc;
+use core::cell::Cell;
+use core::time::Duration;
+
+use flecs_ecs::prelude::*;
+
+#[derive(Component)]
+struct Cooldown;
+
+#[test]
+fn timer_after() {
+    let world = World::new();
+    let fired = Rc::new(Cell::new(0));
+    let count = fired.clone();
+    let handle = world.after(Duration::from_secs(1), move |_| count.set(count.get() + 1));
+    assert!(handle.is_active(&world));
+
+    world.progress_time(0.5);
+    assert_eq!(fired.get(), 0);
+    world.progress_time(0.5);
+    assert_eq!(fired.get(), 1);
+    assert!(!handle.is_active(&world));
+
+    world.progress_time(1.0);
+    assert_eq!(fired.get(), 1);
+}
+
+#[test]
+fn timer_every() {
+    let world = World::new();
+    let fired = Rc::new(Cell::new(0));
+    let count = fired.clone();
+    let handle = world.every(Duration::from_millis(250), move |_| {
+        count.set(count.get() + 1);
+    });
+
+    for _ in 0..4 {
+        world.progress_time(0.25);
+    }
+    assert_eq!(fired.get(), 4);
+
+    handle.cancel(&world);
+    assert!(!handle.is_active(&world));
+    world.progress_time(0.25);
+    assert_eq!(fired.get(), 4);
+
+    // cancelling again does nothing
+    handle.cancel(&world);
+}
+
+#[test]
+fn timer_cancel_before_expired() {
+    let world = World::new();
+    let fired = Rc::new(Cell::new(false));
+    let flag = fired.clone();
+    let handle = world.after(Duration::from_secs(1), move |_| flag.set(true));
+
+    world.progress_time(0.5);
+    handle.cancel(&world);
+    world.progress_time(1.0);
+    assert!(!fired.get());
+}
+
+#[test]
+fn timer_callback_uses_world() {
+    let world = World::new();
+    world.after(Duration::from_secs(1), |world| {
+        world.entity().add(Cooldown);
+        // timers can be created from a callback
+        world.after(Duration::from_secs(1), |world| {
+            world.entity().add(Cooldown);
+        });
+    });
+
+    world.progress_time(1.0);
+    assert_eq!(world.count(Cooldown), 1);
+    world.progress_time(1.0);
+    assert_eq!(world.count(Cooldown), 2);
+}
+
+#[test]
+fn timer_first_created_from_system() {
+    let world = World::new();
+    let created = Cell::new(false);
+    world.system::<()>().run(move |it| {
+        if !created.replace(true) {
+            it.world().after(Duration::from_secs(1), |world| {
+                world.entity().add(Cooldown);
+            });
+        }
+    });
+
+    world.progress_time(1.0);
+    assert_eq!(world.count(Cooldown), 0);
+    world.progress_time(1.0);
+    assert_eq!(world.count(Cooldown), 1);
+}
+
+#[test]
+fn timer_every_cancel_from_callback() {
+    let world = World::new();
+    let fired = Rc::new(Cell::new(0));
+    let count = fired.clone();
+    let handle = Rc::new(Cell::new(None::<TimerHandle>));
+    let own_handle = handle.clone();
+    handle.set(Some(world.every(Duration::from_secs(1), move |world| {
+        count.set(count.get() + 1);
+        if count.get() == 2 {
+            own_handle.get().unwrap().cancel(world);
+        }
+    })));
+
+    for _ in 0..4 {
+        world.progress_time(1.0);
+    }
+    assert_eq!(fired.get(), 2);
+    assert!(!handle.get().unwrap().is_active(&world));
+}
+
+#[test]
+fn timer_entity_after() {
+    let world = World::new();
+    let e = world.entity().add(Cooldown);
+    let handle = e.after(Duration::from_secs(1), |e| {
+        e.remove(Cooldown);
+    });
+    // timers of an entity aren't its children
+    assert_eq!(world.count((flecs::ChildOf, e)), 0);
+
+    world.progress_time(1.0);
+    assert!(!e.has(Cooldown));
+    assert!(!handle.is_active(&world));
+}
+
+#[test]
+fn timer_entity_cancel_on_delete() {
+    let world = World::new();
+    let fired = Rc::new(Cell::new(0));
+    let e = world.entity();
+
+    let count = fired.clone();
+    let after = e.after(Duration::from_secs(1), move |_| count.set(count.get() + 1));
+    let count = fired.clone();
+    let every = e.every(Duration::from_secs(1), move |_| count.set(count.get() + 1));
+
+    world.progress_time(0.5);
+    e.destruct();
+    assert!(!after.is_active(&world));
+    assert!(!every.is_active(&world));
+
+    world.progress_time(1.0);
+    assert_eq!(fired.get(), 0);
+}
+
+#[test]
+fn timer_entity_every() {
+    let world = World::new();
+    let e = world.entity();
+    let id = e.id();
+    let fired = Rc::new(Cell::new(0));
+    let count = fired.clone();
+    e.every(Duration::from_secs(1), move |timer_entity| {
+        assert_eq!(timer_entity.id(), id);
+        count.set(count.get() + 1);
+    });
+
+    for _ in 0..3 {
+        world.progress_time(1.0);
+    }
+    assert_eq!(fired.get(), 3);
+}
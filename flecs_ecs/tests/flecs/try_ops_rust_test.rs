@@ -0,0 +1,179 @@
+#![allow(dead_code)]
+
+use crate::common_test::*;
+
+#[test]
+fn try_add_dead_entity() {
+    let world = World::new();
+
+    let e = world.entity();
+    e.destruct();
+
+    let err = e.try_add(Tag).unwrap_err();
+    assert_eq!(err.code(), FlecsErrorCode::InvalidParameter);
+    assert!(e.try_remove(Tag).is_err());
+    assert!(e.try_destruct().is_err());
+}
+
+#[test]
+fn try_add_invalid_id() {
+    let world = World::new();
+
+    let e = world.entity();
+    let target = world.entity();
+    target.destruct();
+
+    let err = e.try_add(target).unwrap_err();
+    assert_eq!(err.code(), FlecsErrorCode::InvalidParameter);
+    assert!(!e.has(target));
+}
+
+#[test]
+fn try_set_and_assign() {
+    let world = World::new();
+
+    let e = world.entity();
+
+    let err = e.try_assign(Position { x: 1, y: 2 }).unwrap_err();
+    assert_eq!(err.code(), FlecsErrorCode::InvalidOperation);
+
+    e.try_set(Position { x: 1, y: 2 }).unwrap();
+    e.try_assign(Position { x: 3, y: 4 }).unwrap();
+
+    e.get::<&Position>(|p| {
+        assert_eq!(p.x, 3);
+        assert_eq!(p.y, 4);
+    });
+}
+
+#[test]
+fn try_set_id_type_mismatch() {
+    let world = World::new();
+
+    let e = world.entity();
+    let velocity = world.component::<Velocity>();
+
+    let err = e.try_set_id(Position { x: 1, y: 2 }, velocity).unwrap_err();
+    assert_eq!(err.code(), FlecsErrorCode::InvalidParameter);
+    assert!(!e.has(velocity));
+}
+
+#[test]
+fn try_set_name_in_use() {
+    let world = World::new();
+
+    let parent = world.entity();
+    let a = world.entity_named("a").child_of(parent);
+
+    // same name in another scope is fine
+    world.entity().try_set_name("a").unwrap();
+
+    let err = world
+        .entity()
+        .child_of(parent)
+        .try_set_name("a")
+        .unwrap_err();
+    assert_eq!(err.code(), FlecsErrorCode::NameInUse);
+
+    // renaming to its own name is fine
+    a.try_set_name("a").unwrap();
+}
+
+#[test]
+fn try_set_name_ignores_scope() {
+    let world = World::new();
+
+    let root = world.entity();
+    let scope = world.entity_named("p");
+    world.entity_named("foo").child_of(scope);
+
+    // the name is checked against the parent of the entity, not the current scope
+    world.set_scope(scope);
+    let result = root.try_set_name("foo");
+    world.set_scope(0);
+    result.unwrap();
+    assert_eq!(root.name(), "foo");
+}
+
+#[test]
+fn try_set_name_with_separator() {
+    let world = World::new();
+
+    let a = world.entity().set_name("a::b");
+    assert_eq!(a.name(), "a::b");
+
+    // the name is matched literally, not as a path
+    let err = world.entity().try_set_name("a::b").unwrap_err();
+    assert_eq!(err.code(), FlecsErrorCode::NameInUse);
+}
+
+#[test]
+fn try_child_of_cycle() {
+    let world = World::new();
+
+    let a = world.entity();
+    let b = world.entity().child_of(a);
+    let c = world.entity().child_of(b);
+
+    assert_eq!(
+        a.try_child_of(c).unwrap_err().code(),
+        FlecsErrorCode::CycleDetected
+    );
+    assert_eq!(
+        a.try_child_of(a).unwrap_err().code(),
+        FlecsErrorCode::CycleDetected
+    );
+    assert!(!a.has((flecs::ChildOf::ID, c)));
+
+    let d = world.entity();
+    c.try_child_of(d).unwrap();
+    assert!(c.has((flecs::ChildOf::ID, d)));
+}
+
+#[test]
+fn try_is_a_cycle() {
+    let world = World::new();
+
+    let base = world.prefab();
+    let derived = world.prefab().is_a(base);
+
+    assert_eq!(
+        base.try_is_a(derived).unwrap_err().code(),
+        FlecsErrorCode::CycleDetected
+    );
+}
+
+#[test]
+fn try_world_operations() {
+    let world = World::new();
+
+    let e = world.entity();
+    e.destruct();
+
+    assert!(world.try_set_scope(e).is_err());
+    assert!(world.try_delete_entities_with(e).is_err());
+    assert!(world.try_remove_all(e).is_err());
+
+    let recycled = world.entity();
+    assert_eq!(
+        world.try_make_alive(e).unwrap_err().code(),
+        FlecsErrorCode::InvalidOperation
+    );
+    assert!(world.try_make_alive(recycled).is_ok());
+
+    let scope = world.entity();
+    world.try_set_scope(scope).unwrap();
+    assert_eq!(world.get_scope().unwrap(), scope);
+    world.try_set_scope(0).unwrap();
+    assert!(world.get_scope().is_none());
+
+    world.try_set(Position { x: 1, y: 2 }).unwrap();
+    world.get::<&Position>(|p| assert_eq!(p.x, 1));
+}
+
+#[test]
+fn flecs_error_display() {
+    let err = FlecsError::new(FlecsErrorCode::NameInUse, "name 'a' is taken");
+    assert_eq!(err.to_string(), "ECS_NAME_IN_USE: name 'a' is taken");
+    assert_eq!(err.message(), "name 'a' is taken");
+}
@@ -0,0 +1,85 @@
+//! This test needs to be a separate process, since the profiler records the
+//! performance traces of all worlds in the process.
+#![cfg(feature = "flecs_perf_trace")]
+
+use flecs_ecs::prelude::*;
+
+#[derive(Component)]
+struct Position {
+    x: f32,
+}
+
+#[test]
+fn profiler_chrome_trace() {
+    let world = World::new();
+    world.set_threads(2);
+
+    for i in 0..100 {
+        world.entity().set(Position { x: i as f32 });
+    }
+
+    world
+        .system_named::<&mut Position>("Move")
+        .par_each(|pos| pos.x += 1.0);
+
+    world
+        .system_named::<&Position>("Spawn")
+        .kind(flecs::pipeline::PostUpdate)
+        .run(|mut it| {
+            let world = it.world();
+            while it.next() {}
+            world.entity().set(Position { x: 0.0 });
+        });
+
+    assert!(!world.is_profiler_recording());
+    world.progress();
+
+    world.start_profiler(1000);
+    assert!(world.is_profiler_recording());
+    world.progress();
+    world.progress();
+    world.stop_profiler();
+    assert!(!world.is_profiler_recording());
+
+    // frames that run while the profiler is stopped aren't recorded
+    world.progress();
+
+    let trace = world.chrome_trace();
+    assert!(trace.starts_with("{\"traceEvents\":["));
+    assert!(trace.ends_with("],\"displayTimeUnit\":\"ms\"}"));
+
+    // the multi threaded system runs on both worker threads, in each recorded frame
+    let moves = trace
+        .matches("{\"name\":\"Move\",\"cat\":\"OnUpdate\",\"ph\":\"X\"")
+        .count();
+    assert_eq!(moves, 4);
+    let spawns = trace
+        .matches("{\"name\":\"Spawn\",\"cat\":\"PostUpdate\",\"ph\":\"X\"")
+        .count();
+    assert_eq!(spawns, 2);
+
+    assert!(trace.contains("\"phase\":\"::flecs::pipeline::OnUpdate\""));
+    assert!(trace.contains("{\"name\":\"flecs.commands.merge\",\"cat\":\"sync\""));
+    assert!(trace.matches("\"name\":\"thread_name\"").count() >= 2);
+
+    let path = std::env::temp_dir().join("flecs_profiler_chrome_trace.json");
+    world.write_chrome_trace(&path).unwrap();
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), trace);
+    std::fs::remove_file(&path).unwrap();
+
+    // restarting the profiler drops the recorded spans
+    world.start_profiler(1000);
+    world.stop_profiler();
+    assert!(!world.chrome_trace().contains("\"name\":\"Move\""));
+
+    // each thread only keeps the most recent spans
+    world.start_profiler(3);
+    for _ in 0..10 {
+        world.progress();
+    }
+    world.stop_profiler();
+
+    let trace = world.chrome_trace();
+    assert!(trace.matches("\"ph\":\"X\"").count() <= 3 * 3);
+    assert!(trace.contains("\"name\":\"Spawn\""));
+}
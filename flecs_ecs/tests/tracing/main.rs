@@ -0,0 +1,160 @@
+//! This test needs to be a separate process, since the OS API and the global
+//! `tracing` subscriber are process-global.
+#![cfg(feature = "flecs_tracing")]
+
+use core::fmt::Debug;
+use std::sync::Mutex;
+
+use flecs_ecs::prelude::*;
+use flecs_ecs::sys;
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::{Event, Level, Metadata, Subscriber};
+
+#[derive(Debug, Default, Clone, PartialEq)]
+struct Recorded {
+    level: Option<Level>,
+    target: String,
+    fields: Vec<(String, String)>,
+}
+
+impl Recorded {
+    fn field(&self, name: &str) -> Option<&str> {
+        self.fields
+            .iter()
+            .find(|(field, _)| field == name)
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+impl Visit for Recorded {
+    fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+        self.fields
+            .push((field.name().to_string(), format!("{value:?}")));
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.fields
+            .push((field.name().to_string(), value.to_string()));
+    }
+}
+
+#[derive(Default)]
+struct Recorder {
+    events: Mutex<Vec<Recorded>>,
+    spans: Mutex<Vec<Recorded>>,
+    entered: Mutex<Vec<u64>>,
+}
+
+impl Subscriber for &'static Recorder {
+    fn enabled(&self, _metadata: &Metadata<'_>) -> bool {
+        true
+    }
+
+    fn new_span(&self, span: &Attributes<'_>) -> Id {
+        let mut recorded = Recorded {
+            target: span.metadata().target().to_string(),
+            ..Default::default()
+        };
+        span.record(&mut recorded);
+        let mut spans = self.spans.lock().unwrap();
+        spans.push(recorded);
+        Id::from_u64(spans.len() as u64)
+    }
+
+    fn record(&self, _span: &Id, _values: &Record<'_>) {}
+
+    fn record_follows_from(&self, _span: &Id, _follows: &Id) {}
+
+    fn event(&self, event: &Event<'_>) {
+        let mut recorded = Recorded {
+            level: Some(*event.metadata().level()),
+            target: event.metadata().target().to_string(),
+            ..Default::default()
+        };
+        event.record(&mut recorded);
+        self.events.lock().unwrap().push(recorded);
+    }
+
+    fn enter(&self, span: &Id) {
+        self.entered.lock().unwrap().push(span.into_u64());
+    }
+
+    fn exit(&self, span: &Id) {
+        let mut entered = self.entered.lock().unwrap();
+        assert_eq!(entered.pop(), Some(span.into_u64()));
+    }
+}
+
+fn log(level: i32, line: i32, msg: &core::ffi::CStr) {
+    unsafe {
+        sys::ecs_log_(
+            level,
+            c"main.rs".as_ptr(),
+            line,
+            c"%s".as_ptr(),
+            msg.as_ptr(),
+        );
+    }
+}
+
+#[test]
+fn tracing() {
+    let recorder: &'static Recorder = Box::leak(Box::default());
+    tracing::subscriber::set_global_default(recorder).unwrap();
+
+    // creating a world installs the handlers
+    let _world = World::new();
+    recorder.events.lock().unwrap().clear();
+
+    set_log_level(0);
+    log(-2, 10, c"warning");
+    unsafe { sys::ecs_log_push_(0) };
+    log(0, 20, c"info");
+    unsafe { sys::ecs_log_pop_(0) };
+    log(1, 30, c"filtered debug");
+    log(-3, 40, c"error");
+
+    let events = recorder.events.lock().unwrap().clone();
+    assert_eq!(events.len(), 3);
+
+    let expected = [
+        (Level::WARN, "-2", "10", "0", "warning"),
+        (Level::INFO, "0", "20", "1", "info"),
+        (Level::ERROR, "-3", "40", "0", "error"),
+    ];
+    for (event, (level, flecs_level, line, depth, message)) in events.iter().zip(expected) {
+        assert_eq!(event.level, Some(level));
+        assert_eq!(event.target, "flecs");
+        assert_eq!(event.field("level"), Some(flecs_level));
+        assert_eq!(event.field("file"), Some("main.rs"));
+        assert_eq!(event.field("line"), Some(line));
+        assert_eq!(event.field("depth"), Some(depth));
+        assert_eq!(event.field("message"), Some(message));
+    }
+
+    #[cfg(feature = "flecs_perf_trace")]
+    {
+        // flecs traces its own operations too, so only the spans pushed here are checked
+        let first = recorder.spans.lock().unwrap().len();
+        unsafe {
+            sys::ecs_os_perf_trace_push_(c"main.rs".as_ptr(), 50, c"outer".as_ptr());
+            sys::ecs_os_perf_trace_push_(c"main.rs".as_ptr(), 51, c"inner".as_ptr());
+        }
+        assert_eq!(recorder.entered.lock().unwrap().len(), 2);
+
+        unsafe {
+            sys::ecs_os_perf_trace_pop_(c"main.rs".as_ptr(), 52, c"inner".as_ptr());
+            sys::ecs_os_perf_trace_pop_(c"main.rs".as_ptr(), 53, c"outer".as_ptr());
+        }
+        assert!(recorder.entered.lock().unwrap().is_empty());
+
+        let spans = recorder.spans.lock().unwrap();
+        let names = spans[first..]
+            .iter()
+            .filter(|span| span.target == "flecs")
+            .filter_map(|span| span.field("name"))
+            .collect::<Vec<_>>();
+        assert_eq!(names, ["outer", "inner"]);
+    }
+}
@@ -8544,6 +8544,66 @@ unsafe extern "C-unwind" {
 }
 #[repr(C)]
 #[derive(Debug, Copy, Clone)]
+pub struct ecs_rust_query_op_t {
+    pub kind: *const ::core::ffi::c_char,
+    pub term_index: i8,
+    pub src_var: i16,
+    pub first_var: i16,
+    pub second_var: i16,
+    pub src: ecs_entity_t,
+    pub first: ecs_entity_t,
+    pub second: ecs_entity_t,
+    pub written: u64,
+}
+unsafe extern "C-unwind" {
+    pub fn ecs_rust_query_op_count(query: *const ecs_query_t) -> i32;
+}
+unsafe extern "C-unwind" {
+    pub fn ecs_rust_query_get_op(
+        query: *const ecs_query_t,
+        index: i32,
+        op: *mut ecs_rust_query_op_t,
+    ) -> bool;
+}
+unsafe extern "C-unwind" {
+    pub fn ecs_rust_expr_parse(
+        world: *mut ecs_world_t,
+        expr: *const ::core::ffi::c_char,
+        desc: *const ecs_expr_eval_desc_t,
+    ) -> *mut ecs_script_t;
+}
+unsafe extern "C-unwind" {
+    pub fn ecs_rust_script_update_w_desc(
+        world: *mut ecs_world_t,
+        e: ecs_entity_t,
+        instance: ecs_entity_t,
+        code: *const ::core::ffi::c_char,
+        desc: *const ecs_script_eval_desc_t,
+    ) -> ::core::ffi::c_int;
+}
+unsafe extern "C-unwind" {
+    pub fn ecs_rust_pipeline_build(world: *mut ecs_world_t, pipeline: ecs_entity_t) -> i32;
+}
+unsafe extern "C-unwind" {
+    pub fn ecs_rust_pipeline_get_op(
+        world: *const ecs_world_t,
+        pipeline: ecs_entity_t,
+        index: i32,
+        offset: *mut i32,
+        count: *mut i32,
+        multi_threaded: *mut bool,
+        immediate: *mut bool,
+    ) -> bool;
+}
+unsafe extern "C-unwind" {
+    pub fn ecs_rust_pipeline_get_system(
+        world: *const ecs_world_t,
+        pipeline: ecs_entity_t,
+        index: i32,
+    ) -> ecs_entity_t;
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
 pub struct ecs_event_id_record_t {
     pub _address: u8,
 }
@@ -364,6 +364,22 @@ impl Default for crate::ecs_iter_to_json_desc_t {
     }
 }
 
+impl Default for crate::ecs_rust_query_op_t {
+    fn default() -> Self {
+        Self {
+            kind: core::ptr::null(),
+            term_index: -1,
+            src_var: -1,
+            first_var: -1,
+            second_var: -1,
+            src: Default::default(),
+            first: Default::default(),
+            second: Default::default(),
+            written: Default::default(),
+        }
+    }
+}
+
 unsafe impl Sync for crate::EcsIdentifier {}
 unsafe impl Send for crate::EcsIdentifier {}
 unsafe impl Send for crate::EcsPoly {}
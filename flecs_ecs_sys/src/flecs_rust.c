@@ -78,4 +78,270 @@ const ecs_type_info_t* ecs_rust_get_type_info_from_record(
     }
 error:
     return NULL;
-}
\ No newline at end of file
+}
+
+#ifdef FLECS_SCRIPT
+ecs_script_t* ecs_rust_expr_parse(
+    ecs_world_t *world,
+    const char *expr,
+    const ecs_expr_eval_desc_t *desc)
+{
+    ecs_expr_eval_desc_t priv_desc = {0};
+    if (desc) {
+        priv_desc = *desc;
+    }
+
+    if (!priv_desc.lookup_action) {
+        priv_desc.lookup_action = flecs_script_default_lookup;
+    }
+
+    ecs_script_t *script = flecs_script_new(world);
+    ecs_script_impl_t *impl = flecs_script_impl(script);
+
+    /* Owned by the script. Errors are reported relative to the code. */
+    if (priv_desc.name) {
+        script->name = ecs_os_strdup(priv_desc.name);
+    }
+    script->code = ecs_os_strdup(expr);
+
+    ecs_parser_t parser = {
+        .name = script->name,
+        .code = script->code,
+        .script = impl,
+        .scope = impl->root,
+        .significant_newline = false
+    };
+
+    impl->token_buffer_size = ecs_os_strlen(expr) * 2 + 1;
+    impl->token_buffer = flecs_alloc_w_dbg_info(
+        &impl->allocator, impl->token_buffer_size, "token buffer");
+    parser.token_cur = impl->token_buffer;
+
+    const char *ptr = flecs_script_parse_expr(
+        &parser, script->code, 0, &impl->expr);
+    if (!ptr) {
+        goto error;
+    }
+
+    impl->next_token = ptr;
+    impl->token_remaining = parser.token_cur;
+
+    if (flecs_expr_visit_type(script, impl->expr, &priv_desc)) {
+        goto error;
+    }
+
+    if (!priv_desc.disable_folding) {
+        if (flecs_expr_visit_fold(script, &impl->expr, &priv_desc)) {
+            goto error;
+        }
+    }
+
+    return script;
+error:
+    ecs_script_free(script);
+    return NULL;
+}
+
+int ecs_rust_script_update_w_desc(
+    ecs_world_t *world,
+    ecs_entity_t e,
+    ecs_entity_t instance,
+    const char *code,
+    const ecs_script_eval_desc_t *desc)
+{
+    ecs_assert(world != NULL, ECS_INTERNAL_ERROR, NULL);
+    ecs_assert(code != NULL, ECS_INTERNAL_ERROR, NULL);
+
+    const char *name = ecs_get_name(world, e);
+    EcsScript *s = ecs_ensure(world, e, EcsScript);
+    if (s->template_) {
+        char *template_name = ecs_get_path(world, s->template_->entity);
+        ecs_err("cannot update scripts for individual templates, "
+            "update parent script instead (tried to update '%s')",
+                template_name);
+        ecs_os_free(template_name);
+        return -1;
+    }
+
+    if (s->code) {
+        ecs_os_free(s->code);
+    }
+
+    s->code = ecs_os_strdup(code);
+
+    if (s->error) {
+        ecs_os_free(s->error);
+        s->error = NULL;
+    }
+
+    if (s->script) {
+        ecs_script_free(s->script);
+    }
+
+    ecs_script_eval_result_t eval_result = {NULL};
+
+    s->script = ecs_script_parse(world, name, code, desc, &eval_result);
+    if (s->script == NULL) {
+        s->error = eval_result.error;
+        return -1;
+    }
+
+    int result = 0;
+    bool is_defer = ecs_is_deferred(world);
+    ecs_suspend_readonly_state_t srs;
+    ecs_world_t *real_world = NULL;
+    if (is_defer) {
+        ecs_assert(flecs_poly_is(world, ecs_world_t), ECS_INTERNAL_ERROR, NULL);
+        real_world = flecs_suspend_readonly(world, &srs);
+        ecs_assert(real_world != NULL, ECS_INTERNAL_ERROR, NULL);
+    }
+
+    ecs_script_clear(world, e, instance);
+
+    ecs_entity_t prev = ecs_set_with(world, flecs_script_tag(e, instance));
+
+    if (ecs_script_eval(s->script, desc, &eval_result)) {
+        s->error = eval_result.error;
+        ecs_script_free(s->script);
+        s->script = NULL;
+        ecs_delete_with(world, ecs_pair_t(EcsScript, e));
+        result = -1;
+    }
+
+    ecs_set_with(world, prev);
+
+    if (is_defer) {
+        flecs_resume_readonly(real_world, &srs);
+    }
+
+    return result;
+}
+#endif
+
+int32_t ecs_rust_query_op_count(
+    const ecs_query_t *query)
+{
+    flecs_poly_assert(query, ecs_query_t);
+    return flecs_query_impl(query)->op_count;
+}
+
+static
+void flecs_rust_query_ref(
+    const ecs_query_op_t *op,
+    ecs_query_ref_t ref,
+    ecs_flags16_t kind,
+    int16_t *var,
+    ecs_entity_t *entity)
+{
+    ecs_flags16_t flags = flecs_query_ref_flags(op->flags, kind);
+    *var = -1;
+    *entity = 0;
+
+    if (flags & EcsQueryIsVar) {
+        *var = ref.var;
+    } else if (flags & EcsQueryIsEntity) {
+        /* These operations store bitsets or member offsets instead of entities */
+        switch(op->kind) {
+        case EcsQueryTriv:
+        case EcsQueryToggle:
+        case EcsQueryToggleOption:
+        case EcsQueryMemberEq:
+        case EcsQueryMemberNeq:
+            break;
+        default:
+            *entity = ref.entity;
+        }
+    }
+}
+
+bool ecs_rust_query_get_op(
+    const ecs_query_t *query,
+    int32_t index,
+    ecs_rust_query_op_t *op)
+{
+    flecs_poly_assert(query, ecs_query_t);
+
+    ecs_query_impl_t *impl = flecs_query_impl(query);
+    if (index < 0 || index >= impl->op_count) {
+        return false;
+    }
+
+    const ecs_query_op_t *qop = &impl->ops[index];
+    op->kind = flecs_query_op_str(qop->kind);
+    op->term_index = qop->term_index;
+    flecs_rust_query_ref(qop, qop->src, EcsQuerySrc, &op->src_var, &op->src);
+    flecs_rust_query_ref(qop, qop->first, EcsQueryFirst, &op->first_var, &op->first);
+    flecs_rust_query_ref(
+        qop, qop->second, EcsQuerySecond, &op->second_var, &op->second);
+    op->written = qop->written;
+    return true;
+}
+
+#ifdef FLECS_PIPELINE
+static
+ecs_pipeline_state_t* flecs_rust_pipeline_state(
+    const ecs_world_t *world,
+    ecs_entity_t pipeline)
+{
+    const EcsPipeline *p = ecs_get(world, pipeline, EcsPipeline);
+    ecs_check(p != NULL, ECS_INVALID_PARAMETER, "entity is not a pipeline");
+    return p->state;
+error:
+    return NULL;
+}
+
+int32_t ecs_rust_pipeline_build(
+    ecs_world_t *world,
+    ecs_entity_t pipeline)
+{
+    flecs_poly_assert(world, ecs_world_t);
+
+    ecs_pipeline_state_t *pq = flecs_rust_pipeline_state(world, pipeline);
+    if (!pq) {
+        return 0;
+    }
+
+    if (!(world->flags & EcsWorldReadonly)) {
+        ecs_run_aperiodic(world, 0);
+        flecs_pipeline_build(world, pq);
+    }
+
+    return ecs_vec_count(&pq->ops);
+}
+
+bool ecs_rust_pipeline_get_op(
+    const ecs_world_t *world,
+    ecs_entity_t pipeline,
+    int32_t index,
+    int32_t *offset,
+    int32_t *count,
+    bool *multi_threaded,
+    bool *immediate)
+{
+    ecs_pipeline_state_t *pq = flecs_rust_pipeline_state(world, pipeline);
+    if (!pq || index < 0 || index >= ecs_vec_count(&pq->ops)) {
+        return false;
+    }
+
+    ecs_pipeline_op_t *op = ecs_vec_get_t(&pq->ops, ecs_pipeline_op_t, index);
+    *offset = op->offset;
+    *count = op->count;
+    *multi_threaded = op->multi_threaded;
+    *immediate = op->immediate;
+    return true;
+}
+
+ecs_entity_t ecs_rust_pipeline_get_system(
+    const ecs_world_t *world,
+    ecs_entity_t pipeline,
+    int32_t index)
+{
+    ecs_pipeline_state_t *pq = flecs_rust_pipeline_state(world, pipeline);
+    if (!pq || index < 0 || index >= ecs_vec_count(&pq->systems)) {
+        return 0;
+    }
+
+    ecs_system_t *sys = ecs_vec_get_t(&pq->systems, ecs_system_t*, index)[0];
+    return sys->query->entity;
+}
+#endif
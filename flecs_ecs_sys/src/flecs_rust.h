@@ -16,4 +16,81 @@ FLECS_API
 const ecs_type_info_t* ecs_rust_get_type_info_from_record(
     const ecs_world_t *world,
     ecs_id_t id,
-    const ecs_component_record_t* idr);
\ No newline at end of file
+    const ecs_component_record_t* idr);
+
+/* Operation of the plan of a query. */
+typedef struct ecs_rust_query_op_t {
+    const char *kind;         /* Name of the operation in ecs_query_plan, padded with spaces */
+    int8_t term_index;        /* Term of the operation, or -1 */
+    int16_t src_var;          /* Variable of the source, or -1 */
+    int16_t first_var;        /* Variable of the first element of the id, or -1 */
+    int16_t second_var;       /* Variable of the second element of the id, or -1 */
+    ecs_entity_t src;         /* Fixed source, or 0 */
+    ecs_entity_t first;       /* Fixed first element of the id, or 0 */
+    ecs_entity_t second;      /* Fixed second element of the id, or 0 */
+    uint64_t written;         /* Bitset with the variables used by the operation */
+} ecs_rust_query_op_t;
+
+/* Gets the number of operations in the plan of a query, which is 0 if the
+ * query is evaluated without a plan. */
+FLECS_API
+int32_t ecs_rust_query_op_count(
+    const ecs_query_t *query);
+
+/* Gets operation index of the plan of a query. */
+FLECS_API
+bool ecs_rust_query_get_op(
+    const ecs_query_t *query,
+    int32_t index,
+    ecs_rust_query_op_t *op);
+
+#ifdef FLECS_SCRIPT
+/* Same as ecs_expr_parse, but the script keeps a copy of the expression and of
+ * desc->name, so that parse and eval errors report where they occurred. */
+FLECS_API
+ecs_script_t* ecs_rust_expr_parse(
+    ecs_world_t *world,
+    const char *expr,
+    const ecs_expr_eval_desc_t *desc);
+
+/* Same as ecs_script_update, but parses and evaluates the code with desc, such
+ * as to pass variables to the script. */
+FLECS_API
+int ecs_rust_script_update_w_desc(
+    ecs_world_t *world,
+    ecs_entity_t e,
+    ecs_entity_t instance,
+    const char *code,
+    const ecs_script_eval_desc_t *desc);
+#endif
+
+#ifdef FLECS_PIPELINE
+/* Builds the schedule of a pipeline if its systems changed, like the pipeline
+ * does at the start of a frame, and returns the number of operations in the
+ * schedule. A pipeline merges commands after each operation. The schedule is
+ * not rebuilt when the world is in readonly mode. */
+FLECS_API
+int32_t ecs_rust_pipeline_build(
+    ecs_world_t *world,
+    ecs_entity_t pipeline);
+
+/* Gets operation index of the schedule of a pipeline, which runs count systems
+ * starting at offset in the systems of the schedule. */
+FLECS_API
+bool ecs_rust_pipeline_get_op(
+    const ecs_world_t *world,
+    ecs_entity_t pipeline,
+    int32_t index,
+    int32_t *offset,
+    int32_t *count,
+    bool *multi_threaded,
+    bool *immediate);
+
+/* Gets system index of the schedule of a pipeline, or 0 if the index is out of
+ * range. */
+FLECS_API
+ecs_entity_t ecs_rust_pipeline_get_system(
+    const ecs_world_t *world,
+    ecs_entity_t pipeline,
+    int32_t index);
+#endif